@@ -0,0 +1,254 @@
+use crate::optimized::OptimizedEvent;
+use crate::xml::{escape, push_element, to_datetime};
+use crate::{EventType, EVENT_TYPES};
+use chrono::prelude::*;
+use chrono::SecondsFormat;
+use std::fmt::Write;
+
+const ATOM_NAMESPACE: &str = "http://www.w3.org/2005/Atom";
+const TAG_PREFIX: &str = "tag:sigtor.org,2020";
+const MAX_SUMMARY_CHARS: usize = 280;
+const MAX_HEADER_LINES: usize = 15;
+
+/**
+ * Which events a feed includes. Feed names are the lowercased `EventType`, e.g. "nwstor", or
+ * "all" for every event.
+ */
+#[derive(Debug, PartialEq)]
+pub enum FeedFilter {
+    All,
+    EventType(&'static EventType),
+}
+
+impl FeedFilter {
+    /**
+     * Returns None for names that aren't an event type, so a typo in a feed URL is an error rather
+     * than a feed that's always empty.
+     */
+    pub fn from_name(name: &str) -> Option<FeedFilter> {
+        let name = name.to_lowercase();
+        if name == "all" {
+            return Some(FeedFilter::All);
+        }
+
+        EVENT_TYPES
+            .iter()
+            .find(|x| get_type_name(x) == name)
+            .map(FeedFilter::EventType)
+    }
+
+    pub fn matches(&self, event_type: &EventType) -> bool {
+        match self {
+            FeedFilter::All => true,
+            FeedFilter::EventType(x) => *x == event_type,
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            FeedFilter::All => "all".to_string(),
+            FeedFilter::EventType(x) => get_type_name(x),
+        }
+    }
+}
+
+fn get_type_name(event_type: &EventType) -> String {
+    format!("{:?}", event_type).to_lowercase()
+}
+
+/**
+ * Builds an Atom (RFC 4287) feed from events, newest first. `now` is used as the feed's updated
+ * time when there are no events.
+ */
+pub fn to_atom(events: &[OptimizedEvent], filter: &FeedFilter, now: DateTime<Utc>) -> String {
+    let events: Vec<&OptimizedEvent> = events
+        .iter()
+        .rev()
+        .filter(|x| filter.matches(&x.event_type))
+        .collect();
+
+    let updated = events
+        .iter()
+        .map(|x| to_datetime(x.event_ts))
+        .max()
+        .unwrap_or(now);
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(xml, "<feed xmlns=\"{}\">", ATOM_NAMESPACE);
+    push_element(
+        &mut xml,
+        1,
+        "id",
+        &format!("{}:feeds/{}", TAG_PREFIX, filter.name()),
+    );
+    push_element(&mut xml, 1, "title", &format!("sware: {}", filter.name()));
+    push_element(&mut xml, 1, "updated", &to_atom_time(updated));
+    xml.push_str("  <author>\n");
+    push_element(&mut xml, 2, "name", "sware");
+    xml.push_str("  </author>\n");
+
+    for event in events {
+        xml.push_str("  <entry>\n");
+        push_element(
+            &mut xml,
+            2,
+            "id",
+            &format!("{}:events/{}", TAG_PREFIX, event.ingest_ts),
+        );
+        push_element(&mut xml, 2, "title", &event.title);
+        push_element(
+            &mut xml,
+            2,
+            "updated",
+            &to_atom_time(to_datetime(event.event_ts)),
+        );
+        if let Some(ext_uri) = &event.ext_uri {
+            let _ = writeln!(xml, "    <link href=\"{}\"/>", escape(ext_uri));
+        }
+        if let Some(text) = &event.text {
+            push_element(&mut xml, 2, "summary", &get_summary(text));
+        }
+        xml.push_str("  </entry>\n");
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/**
+ * Products start with a header ending in the issuance time, ie. "801 PM CDT TUE MAY 1 2018", which
+ * is skipped. The rest is collapsed onto one line and cut off at a word after MAX_SUMMARY_CHARS.
+ */
+fn get_summary(text: &str) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let body_start = lines
+        .iter()
+        .take(MAX_HEADER_LINES)
+        .position(|x| is_issuance_line(x))
+        .map_or(0, |x| x + 1);
+    let words: Vec<&str> = lines[body_start..]
+        .iter()
+        .flat_map(|x| x.split_whitespace())
+        .collect();
+
+    let mut summary = String::new();
+    for word in words {
+        if summary.len() + word.len() + 1 > MAX_SUMMARY_CHARS {
+            summary.push_str("...");
+            break;
+        }
+        if !summary.is_empty() {
+            summary.push(' ');
+        }
+        summary.push_str(word);
+    }
+    summary
+}
+
+fn is_issuance_line(line: &str) -> bool {
+    let is_year = |x: &str| x.len() == 4 && x.chars().all(|c| c.is_ascii_digit());
+    let line = line.trim().to_uppercase();
+    (line.contains(" AM ") || line.contains(" PM "))
+        && matches!(line.split_whitespace().next_back(), Some(x) if is_year(x))
+}
+
+fn to_atom_time(dt: DateTime<Utc>) -> String {
+    dt.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    fn get_event(event_type: EventType, ingest_ts: u128, title: &str) -> OptimizedEvent {
+        OptimizedEvent {
+            event_ts: 1525222860000000,
+            ext_uri: Some("https://api.weather.gov/products/1?a=1&b=2".to_string()),
+            ingest_ts,
+            text: Some("Text".to_string()),
+            ..test_util::get_event(event_type, title)
+        }
+    }
+
+    #[test]
+    fn feed_filter_should_match_event_type_names() {
+        assert!(FeedFilter::from_name("all")
+            .unwrap()
+            .matches(&EventType::SnReport));
+        assert!(FeedFilter::from_name("NwsTor")
+            .unwrap()
+            .matches(&EventType::NwsTor));
+        assert!(FeedFilter::from_name("nwstor")
+            .unwrap()
+            .matches(&EventType::NwsTor));
+        assert!(!FeedFilter::from_name("nwstor")
+            .unwrap()
+            .matches(&EventType::NwsSvr));
+        assert!(FeedFilter::from_name("nwsfwd").is_some());
+    }
+
+    #[test]
+    fn feed_filter_should_reject_unknown_names() {
+        assert_eq!(FeedFilter::from_name("nwstornado"), None);
+        assert_eq!(FeedFilter::from_name(""), None);
+    }
+
+    #[test]
+    fn get_summary_should_skip_product_header() {
+        let text = "\n271 \nWFUS53 KTOP 020101\nTORTOP\n\nBULLETIN - EAS ACTIVATION REQUESTED\nTornado Warning\nNational Weather Service Topeka KS\n801 PM CDT TUE MAY 1 2018\n\nThe National Weather Service in Topeka has issued a\n\n* Tornado Warning for...\n  Northwestern Riley County in northeastern Kansas...\n";
+        assert_eq!(
+            get_summary(text),
+            "The National Weather Service in Topeka has issued a * Tornado Warning for... Northwestern Riley County in northeastern Kansas..."
+        );
+    }
+
+    #[test]
+    fn get_summary_should_truncate_long_text() {
+        let text =
+            "At 800 PM CDT, a large and extremely dangerous tornado was located. ".repeat(10);
+        let result = get_summary(&text);
+        assert!(result.len() <= MAX_SUMMARY_CHARS + 3);
+        assert!(result.starts_with("At 800 PM CDT, a large"));
+        assert!(result.ends_with("..."));
+    }
+
+    #[test]
+    fn to_atom_should_serialize_matching_events() {
+        let events = vec![
+            get_event(EventType::NwsTor, 1, "Tornado Warning (KTOP)"),
+            get_event(EventType::NwsSvr, 2, "Severe Thunderstorm Warning (KDMX)"),
+        ];
+        let now = Utc::now();
+        let result = to_atom(&events, &FeedFilter::from_name("nwstor").unwrap(), now);
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom">
+  <id>tag:sigtor.org,2020:feeds/nwstor</id>
+  <title>sware: nwstor</title>
+  <updated>2018-05-02T01:01:00Z</updated>
+  <author>
+    <name>sware</name>
+  </author>
+  <entry>
+    <id>tag:sigtor.org,2020:events/1</id>
+    <title>Tornado Warning (KTOP)</title>
+    <updated>2018-05-02T01:01:00Z</updated>
+    <link href="https://api.weather.gov/products/1?a=1&amp;b=2"/>
+    <summary>Text</summary>
+  </entry>
+</feed>
+"#;
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn to_atom_should_list_newest_first() {
+        let events = vec![
+            get_event(EventType::NwsTor, 1, "first"),
+            get_event(EventType::NwsSvr, 2, "second"),
+        ];
+        let result = to_atom(&events, &FeedFilter::All, Utc::now());
+        assert!(result.find("second").unwrap() < result.find("first").unwrap());
+    }
+}
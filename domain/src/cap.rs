@@ -0,0 +1,311 @@
+use crate::optimized::{OptimizedEvent, Warning, Watch};
+use crate::xml::{push_element, to_datetime};
+use crate::{Coordinates, EventType, WatchStatus, WatchType};
+use std::fmt::Write;
+
+const CAP_NAMESPACE: &str = "urn:oasis:names:tc:emergency:cap:1.2";
+const SENDER: &str = "sigtor.org";
+
+/**
+ * Serializes an event as a Common Alerting Protocol (CAP 1.2) alert. Only warnings and watches
+ * map onto CAP's urgency/severity/certainty model, so other events return None.
+ */
+pub fn to_cap(event: &OptimizedEvent) -> Option<String> {
+    let info = match (&event.warning, &event.watch) {
+        (Some(warning), _) => Info::from_warning(&event.event_type, warning),
+        (_, Some(watch)) => Info::from_watch(watch),
+        _ => return None,
+    };
+
+    let msg_type = match &event.watch {
+        Some(watch) if watch.status == WatchStatus::Cancelled => "Cancel",
+        _ => "Alert",
+    };
+
+    let wfo = event.location.as_ref().and_then(|x| x.wfo.as_ref());
+    let sender_name = match wfo {
+        Some(wfo) => format!("NWS {}", wfo),
+        None => "NWS Storm Prediction Center".to_string(),
+    };
+
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(xml, "<alert xmlns=\"{}\">", CAP_NAMESPACE);
+    push_element(
+        &mut xml,
+        1,
+        "identifier",
+        &format!("sware-{}", event.ingest_ts),
+    );
+    push_element(&mut xml, 1, "sender", SENDER);
+    push_element(&mut xml, 1, "sent", &to_cap_time(event.event_ts));
+    push_element(&mut xml, 1, "status", "Actual");
+    push_element(&mut xml, 1, "msgType", msg_type);
+    push_element(&mut xml, 1, "scope", "Public");
+    xml.push_str("  <info>\n");
+    push_element(&mut xml, 2, "category", "Met");
+    push_element(&mut xml, 2, "event", info.event);
+    push_element(&mut xml, 2, "urgency", info.urgency);
+    push_element(&mut xml, 2, "severity", info.severity);
+    push_element(&mut xml, 2, "certainty", info.certainty);
+    if let Some(valid_ts) = event.valid_ts {
+        push_element(&mut xml, 2, "effective", &to_cap_time(valid_ts));
+        push_element(&mut xml, 2, "onset", &to_cap_time(valid_ts));
+    }
+    if let Some(expires_ts) = event.expires_ts {
+        push_element(&mut xml, 2, "expires", &to_cap_time(expires_ts));
+    }
+    push_element(&mut xml, 2, "senderName", &sender_name);
+    push_element(&mut xml, 2, "headline", &event.title);
+    if let Some(text) = &event.text {
+        push_element(&mut xml, 2, "description", text);
+    }
+    if let Some(ext_uri) = &event.ext_uri {
+        push_element(&mut xml, 2, "web", ext_uri);
+    }
+    xml.push_str("    <area>\n");
+    let area_desc = info
+        .area_desc
+        .or_else(|| wfo.cloned())
+        .unwrap_or_else(|| event.title.to_string());
+    push_element(&mut xml, 3, "areaDesc", &area_desc);
+    if let Some(location) = &event.location {
+        match (&location.poly, &location.point) {
+            (Some(poly), _) if poly.len() >= 3 => {
+                push_element(&mut xml, 3, "polygon", &to_cap_polygon(poly))
+            }
+            (_, Some(point)) => push_element(
+                &mut xml,
+                3,
+                "circle",
+                &format!("{},{} 0", point.lat, point.lon),
+            ),
+            _ => (),
+        }
+    }
+    xml.push_str("    </area>\n");
+    xml.push_str("  </info>\n");
+    xml.push_str("</alert>\n");
+
+    Some(xml)
+}
+
+struct Info {
+    event: &'static str,
+    urgency: &'static str,
+    severity: &'static str,
+    certainty: &'static str,
+    area_desc: Option<String>,
+}
+
+impl Info {
+    fn from_warning(event_type: &EventType, warning: &Warning) -> Info {
+        let is_tor_emergency = warning.is_tor_emergency.unwrap_or(false);
+        let was_observed = warning.was_observed.unwrap_or(false);
+
+        let event = match event_type {
+            EventType::NwsTor => "Tornado Warning",
+            EventType::NwsSvr => "Severe Thunderstorm Warning",
+            EventType::NwsFfw => "Flash Flood Warning",
+            EventType::NwsSmw => "Special Marine Warning",
+            EventType::NwsSps => "Special Weather Statement",
+            _ => "Warning",
+        };
+
+        let severity = if is_tor_emergency || warning.is_pds {
+            "Extreme"
+        } else {
+            "Severe"
+        };
+
+        let certainty = if is_tor_emergency || was_observed {
+            "Observed"
+        } else {
+            "Likely"
+        };
+
+        Info {
+            event,
+            urgency: "Immediate",
+            severity,
+            certainty,
+            area_desc: Some(warning.issued_for.to_string()),
+        }
+    }
+
+    fn from_watch(watch: &Watch) -> Info {
+        let event = match watch.watch_type {
+            WatchType::Tornado => "Tornado Watch",
+            WatchType::SevereThunderstorm => "Severe Thunderstorm Watch",
+            WatchType::Other => "Watch",
+        };
+
+        let severity = if watch.is_pds { "Extreme" } else { "Severe" };
+
+        Info {
+            event,
+            urgency: "Expected",
+            severity,
+            certainty: "Possible",
+            area_desc: watch.issued_for.clone(),
+        }
+    }
+}
+
+/**
+ * CAP times are ISO 8601 with an explicit offset, and UTC is written as "-00:00" rather than "Z".
+ */
+fn to_cap_time(micros: u64) -> String {
+    to_datetime(micros)
+        .format("%Y-%m-%dT%H:%M:%S-00:00")
+        .to_string()
+}
+
+/**
+ * CAP polygons are space-delimited "lat,lon" pairs where the first and last pairs must match.
+ */
+fn to_cap_polygon(poly: &[Coordinates]) -> String {
+    let mut pairs: Vec<String> = poly
+        .iter()
+        .map(|x| format!("{},{}", x.lat, x.lon))
+        .collect();
+    if poly.first() != poly.last() {
+        pairs.push(pairs[0].to_string());
+    }
+    pairs.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimized::Location;
+    use crate::test_util;
+
+    fn get_tor_event() -> OptimizedEvent {
+        OptimizedEvent {
+            event_ts: 1525222860000000,
+            expires_ts: Some(1525225500000000),
+            ingest_ts: 1525222861000000,
+            location: Some(Location {
+                wfo: Some("KTOP".to_string()),
+                point: Some(Coordinates {
+                    lat: 39.52,
+                    lon: -97.28,
+                }),
+                poly: Some(vec![
+                    Coordinates {
+                        lat: 39.77,
+                        lon: -96.97,
+                    },
+                    Coordinates {
+                        lat: 39.5,
+                        lon: -96.8,
+                    },
+                    Coordinates {
+                        lat: 39.39,
+                        lon: -97.37,
+                    },
+                ]),
+                county: None,
+            }),
+            text: Some("Tornado Warning & stuff".to_string()),
+            valid_ts: Some(1525222860000000),
+            warning: Some(Warning {
+                is_pds: false,
+                is_tor_emergency: Some(true),
+                was_observed: Some(false),
+                issued_for: "Northwestern Riley County".to_string(),
+                motion_deg: Some(245),
+                motion_kt: Some(24),
+                source: None,
+                time: Some(1525222800000000),
+                vtec: None,
+                hail_size: None,
+                wind_gust: None,
+                waterspout: None,
+                mpd_source_id: None,
+                storm_points: vec![],
+                severity: None,
+            }),
+            ..test_util::get_event(EventType::NwsTor, "Tornado Warning (KTOP)")
+        }
+    }
+
+    #[test]
+    fn to_cap_should_serialize_tor_warning() {
+        let result = to_cap(&get_tor_event()).unwrap();
+        let expected = r#"<?xml version="1.0" encoding="UTF-8"?>
+<alert xmlns="urn:oasis:names:tc:emergency:cap:1.2">
+  <identifier>sware-1525222861000000</identifier>
+  <sender>sigtor.org</sender>
+  <sent>2018-05-02T01:01:00-00:00</sent>
+  <status>Actual</status>
+  <msgType>Alert</msgType>
+  <scope>Public</scope>
+  <info>
+    <category>Met</category>
+    <event>Tornado Warning</event>
+    <urgency>Immediate</urgency>
+    <severity>Extreme</severity>
+    <certainty>Observed</certainty>
+    <effective>2018-05-02T01:01:00-00:00</effective>
+    <onset>2018-05-02T01:01:00-00:00</onset>
+    <expires>2018-05-02T01:45:00-00:00</expires>
+    <senderName>NWS KTOP</senderName>
+    <headline>Tornado Warning (KTOP)</headline>
+    <description>Tornado Warning &amp; stuff</description>
+    <area>
+      <areaDesc>Northwestern Riley County</areaDesc>
+      <polygon>39.77,-96.97 39.5,-96.8 39.39,-97.37 39.77,-96.97</polygon>
+    </area>
+  </info>
+</alert>
+"#;
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn to_cap_should_derive_certainty_from_warning() {
+        let mut event = get_tor_event();
+        if let Some(warning) = event.warning.as_mut() {
+            warning.is_tor_emergency = Some(false);
+        }
+        let result = to_cap(&event).unwrap();
+        assert!(result.contains("<severity>Severe</severity>"));
+        assert!(result.contains("<certainty>Likely</certainty>"));
+    }
+
+    #[test]
+    fn to_cap_should_cancel_cancelled_watches() {
+        let mut event = get_tor_event();
+        event.event_type = EventType::NwsSel;
+        event.location = None;
+        event.warning = None;
+        event.watch = Some(Watch {
+            is_pds: true,
+            id: 26,
+            watch_type: WatchType::Tornado,
+            status: WatchStatus::Cancelled,
+            issued_for: None,
+            max_hail_size: None,
+            max_wind_gust_kt: None,
+            motion_deg: None,
+            motion_kt: None,
+            replaces_ids: vec![],
+            probabilities: None,
+        });
+        let result = to_cap(&event).unwrap();
+        assert!(result.contains("<msgType>Cancel</msgType>"));
+        assert!(result.contains("<event>Tornado Watch</event>"));
+        assert!(result.contains("<urgency>Expected</urgency>"));
+        assert!(result.contains("<severity>Extreme</severity>"));
+        assert!(result.contains("<senderName>NWS Storm Prediction Center</senderName>"));
+    }
+
+    #[test]
+    fn to_cap_should_skip_events_without_warning_or_watch() {
+        let mut event = get_tor_event();
+        event.warning = None;
+        assert_eq!(to_cap(&event), None);
+    }
+}
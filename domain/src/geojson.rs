@@ -0,0 +1,345 @@
+use crate::optimized::{
+    FireHazard, FireOutlook, Location, MesoscaleDiscussion, OptimizedEvent, Outlook, Report,
+    TornadoSurvey, TropicalCyclone, Warning, Watch, WinterHazard,
+};
+use crate::{AreaForecastDiscussion, Coordinates, EventType};
+
+/**
+ * GeoJSON (RFC 7946) representation of events. Polygons take precedence over points, so warnings,
+ * watches and MDs render as areas while reports render as points. Tornado survey paths render as
+ * lines. Every other event field is carried along as a property.
+ */
+#[derive(Debug, Serialize)]
+pub struct FeatureCollection<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub features: Vec<Feature<'a>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Feature<'a> {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub geometry: Option<Geometry>,
+    pub properties: Properties<'a>,
+}
+
+/**
+ * The event's fields, borrowed rather than copied into a serde_json Value, which can't hold the
+ * u128 ingest_ts. Geometry is already represented, so it's left out to keep the properties small.
+ */
+#[derive(Debug, Serialize)]
+pub struct Properties<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub afd: Option<&'a AreaForecastDiscussion>,
+    pub event_ts: u64,
+    pub event_type: &'a EventType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_ts: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ext_uri: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fire: Option<&'a FireHazard>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fire_outlook: Option<&'a FireOutlook>,
+    pub ingest_ts: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<LocationProperties<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub md: Option<&'a MesoscaleDiscussion>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outlook: Option<&'a Outlook>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<&'a Report>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub survey: Option<SurveyProperties<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<&'a str>,
+    pub title: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tropical: Option<&'a TropicalCyclone>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid_ts: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<&'a Warning>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub watch: Option<&'a Watch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winter: Option<&'a WinterHazard>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LocationProperties<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wfo: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub county: Option<&'a str>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SurveyProperties<'a> {
+    pub name: &'a str,
+    pub rating: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_wind_mph: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_length_mi: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_width_yd: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fatalities: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub injuries: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_ts: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_ts: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<&'a str>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+#[serde(tag = "type", content = "coordinates")]
+pub enum Geometry {
+    Point([f32; 2]),
+    LineString(Vec<[f32; 2]>),
+    Polygon(Vec<Vec<[f32; 2]>>),
+}
+
+impl<'a> FeatureCollection<'a> {
+    pub fn new(events: &'a [OptimizedEvent]) -> FeatureCollection<'a> {
+        FeatureCollection {
+            kind: "FeatureCollection",
+            features: events.iter().map(Feature::new).collect(),
+        }
+    }
+}
+
+impl<'a> Feature<'a> {
+    pub fn new(event: &'a OptimizedEvent) -> Feature<'a> {
+        let geometry = match &event.survey {
+            Some(survey) if survey.path.len() >= 2 => Some(Geometry::line_string(&survey.path)),
+            _ => event.location.as_ref().and_then(|location| {
+                match (&location.poly, &location.point) {
+                    (Some(poly), _) if poly.len() >= 3 => Some(Geometry::polygon(poly)),
+                    (_, Some(point)) => Some(Geometry::point(point)),
+                    _ => None,
+                }
+            }),
+        };
+
+        Feature {
+            kind: "Feature",
+            geometry,
+            properties: Properties::new(event),
+        }
+    }
+}
+
+impl<'a> Properties<'a> {
+    fn new(event: &'a OptimizedEvent) -> Properties<'a> {
+        Properties {
+            afd: event.afd.as_ref(),
+            event_ts: event.event_ts,
+            event_type: &event.event_type,
+            expires_ts: event.expires_ts,
+            ext_uri: event.ext_uri.as_deref(),
+            fire: event.fire.as_ref(),
+            fire_outlook: event.fire_outlook.as_ref(),
+            ingest_ts: event.ingest_ts,
+            location: event.location.as_ref().map(LocationProperties::new),
+            md: event.md.as_ref(),
+            outlook: event.outlook.as_ref(),
+            report: event.report.as_ref(),
+            source_id: event.source_id.as_deref(),
+            survey: event.survey.as_ref().map(SurveyProperties::new),
+            text: event.text.as_deref(),
+            title: &event.title,
+            tropical: event.tropical.as_ref(),
+            valid_ts: event.valid_ts,
+            warning: event.warning.as_ref(),
+            watch: event.watch.as_ref(),
+            winter: event.winter.as_ref(),
+        }
+    }
+}
+
+impl<'a> LocationProperties<'a> {
+    fn new(location: &'a Location) -> LocationProperties<'a> {
+        LocationProperties {
+            wfo: location.wfo.as_deref(),
+            county: location.county.as_deref(),
+        }
+    }
+}
+
+impl<'a> SurveyProperties<'a> {
+    fn new(survey: &'a TornadoSurvey) -> SurveyProperties<'a> {
+        SurveyProperties {
+            name: &survey.name,
+            rating: &survey.rating,
+            peak_wind_mph: survey.peak_wind_mph,
+            path_length_mi: survey.path_length_mi,
+            path_width_yd: survey.path_width_yd,
+            fatalities: survey.fatalities,
+            injuries: survey.injuries,
+            start_ts: survey.start_ts,
+            end_ts: survey.end_ts,
+            summary: survey.summary.as_deref(),
+        }
+    }
+}
+
+impl Geometry {
+    fn point(coordinates: &Coordinates) -> Geometry {
+        Geometry::Point(to_position(coordinates))
+    }
+
+    fn line_string(path: &[Coordinates]) -> Geometry {
+        Geometry::LineString(path.iter().map(to_position).collect())
+    }
+
+    /**
+     * GeoJSON linear rings must be closed, but NWS polygons don't repeat the first vertex.
+     */
+    fn polygon(poly: &[Coordinates]) -> Geometry {
+        let mut ring: Vec<[f32; 2]> = poly.iter().map(to_position).collect();
+        if ring.first() != ring.last() {
+            ring.push(ring[0]);
+        }
+        Geometry::Polygon(vec![ring])
+    }
+}
+
+// GeoJSON positions are longitude first
+fn to_position(coordinates: &Coordinates) -> [f32; 2] {
+    [coordinates.lon, coordinates.lat]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util;
+
+    fn get_event(point: Option<Coordinates>, poly: Option<Vec<Coordinates>>) -> OptimizedEvent {
+        OptimizedEvent {
+            location: Some(Location {
+                wfo: Some("KTOP".to_string()),
+                point,
+                poly,
+                county: None,
+            }),
+            ..test_util::get_event(EventType::NwsTor, "Tornado Warning (KTOP)")
+        }
+    }
+
+    #[test]
+    fn polygon_should_be_closed_and_preferred_over_point() {
+        let poly = vec![
+            Coordinates {
+                lat: 39.77,
+                lon: -96.97,
+            },
+            Coordinates {
+                lat: 39.5,
+                lon: -96.8,
+            },
+            Coordinates {
+                lat: 39.39,
+                lon: -97.37,
+            },
+        ];
+        let event = get_event(
+            Some(Coordinates {
+                lat: 39.52,
+                lon: -97.28,
+            }),
+            Some(poly),
+        );
+        let feature = Feature::new(&event);
+        let expected = Geometry::Polygon(vec![vec![
+            [-96.97, 39.77],
+            [-96.8, 39.5],
+            [-97.37, 39.39],
+            [-96.97, 39.77],
+        ]]);
+        assert_eq!(feature.geometry, Some(expected));
+    }
+
+    #[test]
+    fn point_should_be_used_without_polygon() {
+        let event = get_event(
+            Some(Coordinates {
+                lat: 26.8,
+                lon: -80.64,
+            }),
+            None,
+        );
+        let feature = Feature::new(&event);
+        assert_eq!(feature.geometry, Some(Geometry::Point([-80.64, 26.8])));
+    }
+
+    #[test]
+    fn feature_collection_should_serialize_as_geojson() {
+        let event = get_event(
+            Some(Coordinates {
+                lat: 26.8,
+                lon: -80.64,
+            }),
+            None,
+        );
+        let result = serde_json::to_string(&FeatureCollection::new(&[event])).unwrap();
+        let expected = r#"{"type":"FeatureCollection","features":[{"type":"Feature","geometry":{"type":"Point","coordinates":[-80.64,26.8]},"properties":{"event_ts":0,"event_type":"NwsTor","ingest_ts":0,"location":{"wfo":"KTOP"},"title":"Tornado Warning (KTOP)"}}]}"#;
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn survey_path_should_be_a_line_string() {
+        let mut event = get_event(
+            Some(Coordinates {
+                lat: 34.84,
+                lon: -99.55,
+            }),
+            None,
+        );
+        event.survey = Some(TornadoSurvey {
+            name: "Mangum Tornado".to_string(),
+            rating: "EF2".to_string(),
+            peak_wind_mph: None,
+            path_length_mi: None,
+            path_width_yd: None,
+            fatalities: None,
+            injuries: None,
+            start_ts: None,
+            end_ts: None,
+            path: vec![
+                Coordinates {
+                    lat: 34.84,
+                    lon: -99.55,
+                },
+                Coordinates {
+                    lat: 34.99,
+                    lon: -99.5,
+                },
+            ],
+            summary: None,
+        });
+        let feature = Feature::new(&event);
+        let expected = Geometry::LineString(vec![[-99.55, 34.84], [-99.5, 34.99]]);
+        assert_eq!(feature.geometry, Some(expected));
+        assert_eq!(
+            serde_json::to_string(&feature.properties.survey).unwrap(),
+            r#"{"name":"Mangum Tornado","rating":"EF2"}"#
+        );
+    }
+
+    #[test]
+    fn missing_location_should_have_null_geometry() {
+        let mut event = get_event(None, None);
+        event.location = None;
+        let feature = Feature::new(&event);
+        assert_eq!(feature.geometry, None);
+    }
+}
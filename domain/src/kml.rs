@@ -0,0 +1,390 @@
+use crate::optimized::OptimizedEvent;
+use crate::xml::{escape, push_element};
+use crate::{Coordinates, EventType, HazardType, Units, WatchType};
+use std::collections::BTreeSet;
+use std::fmt::Write as FmtWrite;
+use std::io::{Cursor, Write};
+use zip::result::ZipResult;
+use zip::write::FileOptions;
+use zip::CompressionMethod;
+
+const KML_NAMESPACE: &str = "http://www.opengis.net/kml/2.2";
+const REPORT_ICON: &str = "http://maps.google.com/mapfiles/kml/shapes/placemark_circle.png";
+
+/**
+ * Builds a KML document of events for Google Earth and similar tools. Warnings, watches and MDs are
+ * drawn as polygons styled by type and severity, reports as points labeled with magnitude, and
+ * tornado survey paths as lines. Events without geometry are skipped.
+ */
+pub fn to_kml(events: &[OptimizedEvent]) -> String {
+    let mut styles: BTreeSet<&'static str> = BTreeSet::new();
+    let mut placemarks = String::new();
+
+    for event in events {
+        let location = match &event.location {
+            Some(location) => location,
+            None => continue,
+        };
+
+        if let Some(survey) = event.survey.as_ref().filter(|x| x.path.len() >= 2) {
+            styles.insert("survey");
+            push_path(&mut placemarks, event, &survey.path, "survey");
+            continue;
+        }
+
+        match (&location.poly, &location.point, &event.report) {
+            (Some(poly), _, _) if poly.len() >= 3 => {
+                let style = get_polygon_style(event);
+                styles.insert(style);
+                push_polygon(&mut placemarks, event, poly, style);
+            }
+            (_, Some(point), Some(report)) => {
+                let style = get_report_style(&report.hazard);
+                styles.insert(style);
+                let label = get_magnitude_label(report.magnitude, &report.units);
+                push_point(&mut placemarks, event, point, style, &label);
+            }
+            (_, Some(point), None) => {
+                styles.insert("default");
+                push_point(&mut placemarks, event, point, "default", "");
+            }
+            _ => (),
+        }
+    }
+
+    let mut kml = String::new();
+    kml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(kml, "<kml xmlns=\"{}\">", KML_NAMESPACE);
+    kml.push_str("  <Document>\n");
+    push_element(&mut kml, 2, "name", "sware events");
+    for style in styles {
+        push_style(&mut kml, style);
+    }
+    kml.push_str(&placemarks);
+    kml.push_str("  </Document>\n");
+    kml.push_str("</kml>\n");
+    kml
+}
+
+/**
+ * Zips a KML document into a KMZ archive.
+ */
+pub fn to_kmz(kml: &str) -> ZipResult<Vec<u8>> {
+    let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    zip.start_file("doc.kml", options)?;
+    zip.write_all(kml.as_bytes())?;
+    Ok(zip.finish()?.into_inner())
+}
+
+/**
+ * Builds a KML NetworkLink that has Google Earth reload `href` every `refresh_seconds`.
+ */
+pub fn to_network_link(href: &str, refresh_seconds: u32) -> String {
+    let mut kml = String::new();
+    kml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(kml, "<kml xmlns=\"{}\">", KML_NAMESPACE);
+    kml.push_str("  <NetworkLink>\n");
+    push_element(&mut kml, 2, "name", "sware events");
+    kml.push_str("    <Link>\n");
+    push_element(&mut kml, 3, "href", href);
+    push_element(&mut kml, 3, "refreshMode", "onInterval");
+    push_element(&mut kml, 3, "refreshInterval", &refresh_seconds.to_string());
+    kml.push_str("    </Link>\n");
+    kml.push_str("  </NetworkLink>\n");
+    kml.push_str("</kml>\n");
+    kml
+}
+
+fn get_polygon_style(event: &OptimizedEvent) -> &'static str {
+    if let Some(warning) = &event.warning {
+        let is_tor_emergency = warning.is_tor_emergency.unwrap_or(false);
+        return match event.event_type {
+            EventType::NwsTor if is_tor_emergency => "tor-emergency",
+            EventType::NwsTor if warning.is_pds => "tor-pds",
+            EventType::NwsTor => "tor",
+            EventType::NwsSvr if warning.is_pds => "svr-pds",
+            EventType::NwsSvr => "svr",
+            EventType::NwsFfw => "ffw",
+            EventType::NwsSmw => "smw",
+            EventType::NwsSps => "sps",
+            _ => "default",
+        };
+    }
+
+    if let Some(watch) = &event.watch {
+        return match watch.watch_type {
+            WatchType::Tornado if watch.is_pds => "watch-tor-pds",
+            WatchType::Tornado => "watch-tor",
+            _ => "watch-svr",
+        };
+    }
+
+    if event.md.is_some() {
+        return "md";
+    }
+
+    "default"
+}
+
+fn get_report_style(hazard: &HazardType) -> &'static str {
+    match hazard {
+        HazardType::Tornado => "report-tornado",
+        HazardType::Funnel | HazardType::WallCloud => "report-rotation",
+        HazardType::Hail => "report-hail",
+        HazardType::Wind | HazardType::Downburst | HazardType::MarineWind => "report-wind",
+        HazardType::Flood | HazardType::FlashFlood | HazardType::HeavyRain => "report-flood",
+        HazardType::Snow | HazardType::FreezingRain => "report-winter",
+        _ => "report-other",
+    }
+}
+
+/**
+ * KML colors are aabbggrr. Polygon fills share the line color at a lower opacity.
+ */
+fn get_style_color(style: &str) -> &'static str {
+    match style {
+        "tor-emergency" => "ffff00ff",
+        "tor-pds" => "ff800080",
+        "tor" | "report-tornado" => "ff0000ff",
+        "svr-pds" => "ff0080ff",
+        "svr" | "report-wind" => "ff00ffff",
+        "ffw" | "report-flood" => "ff00ff00",
+        "smw" => "ff00a5ff",
+        "sps" => "ffb5e4ff",
+        "survey" => "ff000080",
+        "watch-tor-pds" => "ff0000c0",
+        "watch-tor" => "ff4040ff",
+        "watch-svr" => "ff40c0ff",
+        "md" => "ffff8000",
+        "report-hail" => "ff00a000",
+        "report-rotation" => "ff0080ff",
+        "report-winter" => "ffffc000",
+        _ => "ffc0c0c0",
+    }
+}
+
+fn push_style(kml: &mut String, style: &str) {
+    let color = get_style_color(style);
+    let _ = writeln!(kml, "    <Style id=\"{}\">", style);
+    if style.starts_with("report-") || style == "default" {
+        kml.push_str("      <IconStyle>\n");
+        push_element(kml, 4, "color", color);
+        let _ = writeln!(kml, "        <Icon><href>{}</href></Icon>", REPORT_ICON);
+        kml.push_str("      </IconStyle>\n");
+    }
+    kml.push_str("      <LineStyle>\n");
+    push_element(kml, 4, "color", color);
+    push_element(kml, 4, "width", "2");
+    kml.push_str("      </LineStyle>\n");
+    kml.push_str("      <PolyStyle>\n");
+    push_element(kml, 4, "color", &format!("40{}", &color[2..]));
+    kml.push_str("      </PolyStyle>\n");
+    kml.push_str("    </Style>\n");
+}
+
+fn push_polygon(kml: &mut String, event: &OptimizedEvent, poly: &[Coordinates], style: &str) {
+    let mut coordinates: Vec<String> = poly.iter().map(to_kml_coordinate).collect();
+    if poly.first() != poly.last() {
+        coordinates.push(coordinates[0].to_string());
+    }
+
+    kml.push_str("    <Placemark>\n");
+    push_element(kml, 3, "name", &event.title);
+    let _ = writeln!(kml, "      <styleUrl>#{}</styleUrl>", style);
+    push_description(kml, event);
+    kml.push_str("      <Polygon>\n");
+    kml.push_str("        <outerBoundaryIs>\n");
+    kml.push_str("          <LinearRing>\n");
+    push_element(kml, 6, "coordinates", &coordinates.join(" "));
+    kml.push_str("          </LinearRing>\n");
+    kml.push_str("        </outerBoundaryIs>\n");
+    kml.push_str("      </Polygon>\n");
+    kml.push_str("    </Placemark>\n");
+}
+
+fn push_path(kml: &mut String, event: &OptimizedEvent, path: &[Coordinates], style: &str) {
+    let coordinates: Vec<String> = path.iter().map(to_kml_coordinate).collect();
+
+    kml.push_str("    <Placemark>\n");
+    push_element(kml, 3, "name", &event.title);
+    let _ = writeln!(kml, "      <styleUrl>#{}</styleUrl>", style);
+    push_description(kml, event);
+    kml.push_str("      <LineString>\n");
+    push_element(kml, 4, "coordinates", &coordinates.join(" "));
+    kml.push_str("      </LineString>\n");
+    kml.push_str("    </Placemark>\n");
+}
+
+fn push_point(
+    kml: &mut String,
+    event: &OptimizedEvent,
+    point: &Coordinates,
+    style: &str,
+    label: &str,
+) {
+    kml.push_str("    <Placemark>\n");
+    let name = if label.is_empty() {
+        event.title.to_string()
+    } else {
+        label.to_string()
+    };
+    push_element(kml, 3, "name", &name);
+    let _ = writeln!(kml, "      <styleUrl>#{}</styleUrl>", style);
+    push_description(kml, event);
+    kml.push_str("      <Point>\n");
+    push_element(kml, 4, "coordinates", &to_kml_coordinate(point));
+    kml.push_str("      </Point>\n");
+    kml.push_str("    </Placemark>\n");
+}
+
+fn push_description(kml: &mut String, event: &OptimizedEvent) {
+    let description = match &event.text {
+        Some(text) => format!("{}\n\n{}", event.title, text),
+        None => event.title.to_string(),
+    };
+    let _ = writeln!(
+        kml,
+        "      <description>{}</description>",
+        escape(&description)
+    );
+}
+
+fn get_magnitude_label(magnitude: Option<f32>, units: &Option<Units>) -> String {
+    match (magnitude, units) {
+        (Some(magnitude), Some(Units::Inches)) => format!("{}\"", magnitude),
+        (Some(magnitude), Some(Units::Mph)) => format!("{}mph", magnitude),
+        (Some(magnitude), Some(Units::Knots)) => format!("{}kt", magnitude),
+        (Some(magnitude), None) => magnitude.to_string(),
+        _ => String::new(),
+    }
+}
+
+// KML coordinates are longitude first
+fn to_kml_coordinate(coordinates: &Coordinates) -> String {
+    format!("{},{},0", coordinates.lon, coordinates.lat)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimized::{Location, Report, Warning};
+    use crate::test_util::get_event;
+    use std::io::Read;
+
+    fn get_tor_event(is_pds: bool, is_tor_emergency: bool) -> OptimizedEvent {
+        let mut event = get_event(EventType::NwsTor, "Tornado Warning (KTOP)");
+        event.location = Some(Location {
+            wfo: Some("KTOP".to_string()),
+            point: None,
+            poly: Some(vec![
+                Coordinates {
+                    lat: 39.77,
+                    lon: -96.97,
+                },
+                Coordinates {
+                    lat: 39.5,
+                    lon: -96.8,
+                },
+                Coordinates {
+                    lat: 39.39,
+                    lon: -97.37,
+                },
+            ]),
+            county: None,
+        });
+        event.warning = Some(Warning {
+            is_pds,
+            is_tor_emergency: Some(is_tor_emergency),
+            was_observed: Some(false),
+            issued_for: "Riley".to_string(),
+            motion_deg: None,
+            motion_kt: None,
+            source: None,
+            time: Some(1525222800000000),
+            vtec: None,
+            hail_size: None,
+            wind_gust: None,
+            waterspout: None,
+            mpd_source_id: None,
+            storm_points: vec![],
+            severity: None,
+        });
+        event
+    }
+
+    fn get_hail_report() -> OptimizedEvent {
+        let mut event = get_event(EventType::NwsLsr, "Report: 1.25\" Hail (KSJT)");
+        event.location = Some(Location {
+            wfo: Some("KSJT".to_string()),
+            point: Some(Coordinates {
+                lat: 32.07,
+                lon: -100.66,
+            }),
+            poly: None,
+            county: None,
+        });
+        event.report = Some(Report {
+            reporter: "STORM CHASER".to_string(),
+            hazard: HazardType::Hail,
+            magnitude: Some(1.25),
+            units: Some(Units::Inches),
+            was_measured: Some(false),
+            report_ts: None,
+            lsr_source_id: None,
+        });
+        event
+    }
+
+    #[test]
+    fn get_polygon_style_should_reflect_severity() {
+        assert_eq!(get_polygon_style(&get_tor_event(false, false)), "tor");
+        assert_eq!(get_polygon_style(&get_tor_event(true, false)), "tor-pds");
+        assert_eq!(
+            get_polygon_style(&get_tor_event(true, true)),
+            "tor-emergency"
+        );
+    }
+
+    #[test]
+    fn to_kml_should_include_polygons_and_reports() {
+        let events = vec![
+            get_tor_event(false, false),
+            get_hail_report(),
+            get_event(EventType::NwsAfd, "No geometry"),
+        ];
+        let result = to_kml(&events);
+        assert!(result.contains("<Style id=\"tor\">"));
+        assert!(result.contains("<Style id=\"report-hail\">"));
+        assert!(result.contains("<styleUrl>#tor</styleUrl>"));
+        assert!(result.contains(
+            "<coordinates>-96.97,39.77,0 -96.8,39.5,0 -97.37,39.39,0 -96.97,39.77,0</coordinates>"
+        ));
+        assert!(result.contains("<name>1.25&quot;</name>"));
+        assert!(result.contains("<coordinates>-100.66,32.07,0</coordinates>"));
+        assert!(!result.contains("No geometry"));
+    }
+
+    #[test]
+    fn to_kmz_should_zip_kml() {
+        let kml = to_kml(&[get_hail_report()]);
+        let kmz = to_kmz(&kml).unwrap();
+        let mut archive = zip::ZipArchive::new(Cursor::new(kmz)).unwrap();
+        let mut contents = String::new();
+        archive
+            .by_name("doc.kml")
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(kml, contents);
+    }
+
+    #[test]
+    fn to_network_link_should_refresh_on_interval() {
+        let result = to_network_link("http://localhost:8080/events.kml", 60);
+        assert!(result.contains("<href>http://localhost:8080/events.kml</href>"));
+        assert!(result.contains("<refreshMode>onInterval</refreshMode>"));
+        assert!(result.contains("<refreshInterval>60</refreshInterval>"));
+    }
+}
@@ -1,45 +1,69 @@
 #[macro_use]
 extern crate serde_derive;
 
+pub mod atom;
+pub mod cap;
+pub mod geojson;
+pub mod kml;
+pub mod mpds;
 pub mod optimized;
+pub mod reports;
+
+#[cfg(test)]
+mod test_util;
+mod xml;
 
 use std::collections::HashMap;
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Event {
+    pub afd: Option<AreaForecastDiscussion>,
     pub event_ts: u64,
     pub event_type: EventType,
     pub expires_ts: Option<u64>,
     pub ext_uri: Option<String>,
+    pub fire: Option<FireHazard>,
+    pub fire_outlook: Option<FireOutlook>,
     pub ingest_ts: u128,
     pub location: Option<Location>,
     pub md: Option<MesoscaleDiscussion>,
     pub outlook: Option<Outlook>,
     pub report: Option<Report>,
+    pub source_id: Option<String>, // identifies the source record, ie. nws:<product id>:<segment>
+    pub survey: Option<TornadoSurvey>,
     pub text: Option<String>,
     pub title: String,
+    pub tropical: Option<TropicalCyclone>,
     pub valid_ts: Option<u64>,
     pub warning: Option<Warning>,
     pub watch: Option<Watch>,
+    pub winter: Option<WinterHazard>,
 }
 
 impl Event {
     pub fn new(event_ts: u64, event_type: EventType, title: String) -> Event {
         Event {
+            afd: None,
             event_ts,
             event_type,
             expires_ts: None,
             ext_uri: None,
+            fire: None,
+            fire_outlook: None,
             ingest_ts: 0,
             location: None,
             md: None,
             outlook: None,
             report: None,
+            source_id: None,
+            survey: None,
             text: None,
             title,
+            tropical: None,
             valid_ts: None,
             warning: None,
             watch: None,
+            winter: None,
         }
     }
 }
@@ -61,8 +85,53 @@ pub enum EventType {
     NwsSvs,
     NwsSwo,
     NwsTor,
+    MpingReport,
+    SpcReport,
+    NwsWsw,
+    NwsSmw,
+    NwsSps,
+    NwsTornadoSurvey,
+    NwsWwp,
+    NwsMpd,
+    NwsTcp,
+    NwsHls,
+    NwsRfw,
+    NwsFwd,
 }
 
+/**
+ * Every `EventType`, ie. for looking one up by name. New event types need to be added here too.
+ */
+pub const EVENT_TYPES: [EventType; 27] = [
+    EventType::SnReport,
+    EventType::SpcSfcoa,
+    EventType::NwsAfd,
+    EventType::NwsFfa,
+    EventType::NwsFla,
+    EventType::NwsFfw,
+    EventType::NwsFlw,
+    EventType::NwsLsr,
+    EventType::NwsPts,
+    EventType::NwsSel,
+    EventType::NwsSev,
+    EventType::NwsSvr,
+    EventType::NwsSvs,
+    EventType::NwsSwo,
+    EventType::NwsTor,
+    EventType::MpingReport,
+    EventType::SpcReport,
+    EventType::NwsWsw,
+    EventType::NwsSmw,
+    EventType::NwsSps,
+    EventType::NwsTornadoSurvey,
+    EventType::NwsWwp,
+    EventType::NwsMpd,
+    EventType::NwsTcp,
+    EventType::NwsHls,
+    EventType::NwsRfw,
+    EventType::NwsFwd,
+];
+
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Location {
     pub wfo: Option<String>,
@@ -71,7 +140,7 @@ pub struct Location {
     pub county: Option<String>,
 }
 
-#[derive(Debug, Deserialize, PartialEq, Serialize)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
 pub struct Coordinates {
     pub lat: f32,
     pub lon: f32,
@@ -107,7 +176,8 @@ pub struct Report {
     pub magnitude: Option<f32>,
     pub units: Option<Units>,
     pub was_measured: Option<bool>,
-    pub report_ts: Option<u64>, // only populated for LSRs
+    pub report_ts: Option<u64>, // only populated for LSRs and SPC reports
+    pub lsr_source_id: Option<String>, // SPC reports only, the LSR it was reconciled with
 }
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -117,13 +187,33 @@ pub enum Units {
     Inches,
 }
 
-#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Watch {
     pub is_pds: bool,
     pub id: u16,
     pub watch_type: WatchType,
     pub status: WatchStatus,
     pub issued_for: Option<String>,
+    pub max_hail_size: Option<f32>, // inches
+    pub max_wind_gust_kt: Option<u16>,
+    pub motion_deg: Option<u16>,
+    pub motion_kt: Option<u16>,
+    pub replaces_ids: Vec<u16>, // watches this watch replaces, which end when it starts
+    pub probabilities: Option<WatchProbabilities>, // from the WWP, set on SELs when storing
+}
+
+/**
+ * SPC's probabilities, in percent, of the events in a watch from its WWP product.
+ */
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WatchProbabilities {
+    pub tornadoes: u8,        // 2 or more tornadoes
+    pub strong_tornadoes: u8, // 1 or more EF2-EF5 tornadoes
+    pub wind: u8,             // 10 or more severe wind events
+    pub sig_wind: u8,         // 1 or more wind events >= 65 knots
+    pub hail: u8,             // 10 or more severe hail events
+    pub sig_hail: u8,         // 1 or more hail events >= 2 inches
+    pub combined: u8,         // 6 or more combined severe hail/wind events
 }
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -140,16 +230,23 @@ pub enum WatchStatus {
     Unknown,
 }
 
-#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Warning {
     pub is_pds: bool,
     pub is_tor_emergency: Option<bool>, // TOR only
     pub was_observed: Option<bool>,     // TOR only
     pub issued_for: String,
-    pub motion_deg: Option<u16>, // TOR and SVR only
-    pub motion_kt: Option<u16>,  // TOR and SVR only
-    pub source: Option<String>,  // TOR and SVR only
-    pub time: String,
+    pub motion_deg: Option<u16>,        // TOR and SVR only
+    pub motion_kt: Option<u16>,         // TOR and SVR only
+    pub source: Option<String>,         // TOR and SVR only
+    pub time: Option<u64>,              // when the storm was at the TIME...MOT...LOC points
+    pub vtec: Option<String>,           // event tracking number, ie. KTOP.TO.W.0009.2018
+    pub hail_size: Option<f32>,         // inches, SMW and SPS only
+    pub wind_gust: Option<String>,      // ie. >34KTS, SMW and SPS only
+    pub waterspout: Option<String>,     // POSSIBLE or OBSERVED, SMW and SPS only
+    pub mpd_source_id: Option<String>,  // FFW only, the MPD it was issued under, set when storing
+    pub storm_points: Vec<Coordinates>, // every TIME...MOT...LOC point, ie. along a line of storms
+    pub severity: Option<String>,       // alerts only, the CAP severity, ie. Extreme or Severe
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
@@ -186,6 +283,16 @@ pub enum MdConcerning {
     NewTorWatch,
     NewSvrWatch,
     Unknown,
+    SevereWatchUnlikely,
+    HeavyRain,
+    HeavySnow,
+    Blizzard,
+    SnowSquall,
+    FreezingRain,
+    WinterMixedPrecipitation,
+    FlashFloodingLikely,
+    FlashFloodingPossible,
+    FlashFloodingUnlikely,
 }
 
 #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -195,6 +302,163 @@ pub struct MesoscaleDiscussion {
     pub concerning: MdConcerning,
     pub watch_issuance_probability: Option<u16>,
     pub wfos: Vec<String>,
+    pub summary: Option<String>,
+    pub watch_ids: Vec<u16>, // watches the MD is concerning, for continuations
+}
+
+/**
+ * A winter hazard from a WSW product segment. Updates to a hazard keep its VTEC tracking number.
+ */
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WinterHazard {
+    pub hazard_type: WinterHazardType,
+    pub action: String, // VTEC action, ie. NEW, CON, EXT or CAN
+    pub vtec: String,   // event tracking number, ie. KMPX.BZ.W.0002.2019
+    pub zones: Vec<String>,
+    pub what: Option<String>,
+    pub r#where: Option<String>,
+    pub when: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum WinterHazardType {
+    WinterStormWarning,
+    WinterStormWatch,
+    BlizzardWarning,
+    IceStormWarning,
+    WinterWeatherAdvisory,
+}
+
+/**
+ * A fire weather hazard from an RFW product segment. Like winter hazards, updates to a hazard keep
+ * its VTEC tracking number.
+ */
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FireHazard {
+    pub hazard_type: FireHazardType,
+    pub action: String, // VTEC action, ie. NEW, CON, EXT or CAN
+    pub vtec: String,   // event tracking number, ie. KABQ.FW.W.0005.2019
+    pub zones: Vec<String>,
+    pub affected_area: Option<String>,
+    pub wind: Option<String>,
+    pub humidity: Option<String>,
+    pub impacts: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum FireHazardType {
+    RedFlagWarning,
+    FireWeatherWatch,
+}
+
+/**
+ * An SPC fire weather outlook (FWD). Fire weather risk categories have nothing to do with the
+ * convective ones in `OutlookRisk`, so they get their own.
+ */
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FireOutlook {
+    pub day: FireOutlookDay,
+    pub max_risk: Option<FireOutlookRisk>, // None when there are no elevated areas
+    pub has_dry_thunderstorms: bool,
+    pub synopsis: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum FireOutlookDay {
+    Day1,
+    Day2,
+    Day38,
+}
+
+#[derive(Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum FireOutlookRisk {
+    ELEV,
+    CRIT,
+    EXTM,
+}
+
+/**
+ * A tornado from an NWS damage survey PNS. The path runs from where the tornado touched down to
+ * where it lifted.
+ */
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct TornadoSurvey {
+    pub name: String,   // ie. Mangum Tornado
+    pub rating: String, // ie. EF2, or EFU when there was nothing to rate
+    pub peak_wind_mph: Option<u16>,
+    pub path_length_mi: Option<f32>,
+    pub path_width_yd: Option<u16>,
+    pub fatalities: Option<u16>,
+    pub injuries: Option<u16>,
+    pub start_ts: Option<u64>,
+    pub end_ts: Option<u64>,
+    pub path: Vec<Coordinates>,
+    pub summary: Option<String>,
+}
+
+/**
+ * A tropical cyclone from an NHC public advisory (TCP) or a WFO's hurricane local statement (HLS).
+ * The storm ID stays the same for every product about a storm, even as it's upgraded, downgraded
+ * or named, so it's what ties a storm's advisories together.
+ */
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct TropicalCyclone {
+    pub storm_id: String, // basin, number and year, ie. AL092022
+    pub name: String,     // ie. Hurricane Ian or Tropical Depression Nine
+    pub advisory: String, // ie. 23, or 23A for intermediate advisories
+    pub max_wind_mph: Option<u16>,
+    pub pressure_mb: Option<u16>, // TCP only
+    pub motion_deg: Option<u16>,
+    pub motion_mph: Option<u16>,
+    pub headline: Option<String>,
+}
+
+/**
+ * An AFD split into its sections, with any mentions of severe convection pulled out so clients
+ * don't have to read every discussion to find them.
+ */
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AreaForecastDiscussion {
+    pub sections: Vec<AfdSection>,
+    pub highlights: Vec<AfdHighlight>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AfdSection {
+    pub section_type: AfdSectionType,
+    pub name: String, // as written in the product, ie. SHORT TERM or MPX WATCHES/WARNINGS/ADVISORIES
+    pub text: String,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum AfdSectionType {
+    Synopsis,
+    Update,
+    Discussion,
+    NearTerm,
+    ShortTerm,
+    LongTerm,
+    Aviation,
+    Marine,
+    FireWeather,
+    Hydrology,
+    WatchesWarnings,
+    Other,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AfdHighlight {
+    pub keyword: AfdKeyword,
+    pub section_type: AfdSectionType,
+    pub snippet: String, // the sentence the keyword was found in
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum AfdKeyword {
+    Tornado,
+    Supercell,
+    SignificantSevere,
+    Hail, // only mentions with a size, ie. golf ball size hail or hail up to 2 inches
 }
 
 #[derive(Debug, Deserialize)]
@@ -246,3 +510,102 @@ pub struct Product {
     #[serde(rename = "productText")]
     pub product_text: String,
 }
+
+#[derive(Debug, Deserialize)]
+pub struct AlertsResult {
+    pub features: Vec<Alert>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Alert {
+    pub id: String,
+    pub geometry: Option<AlertGeometry>,
+    pub properties: AlertProperties,
+}
+
+/**
+ * Warnings use Polygons, and most zone-based alerts have no geometry at all. The active alerts
+ * include every type of alert though, so any other geometry is accepted and ignored rather than
+ * failing the whole response.
+ */
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type")]
+pub enum AlertGeometry {
+    Polygon {
+        coordinates: Vec<Vec<Vec<f32>>>,
+    },
+    #[serde(other)]
+    Unsupported,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlertProperties {
+    pub id: String,
+    #[serde(rename = "areaDesc")]
+    pub area_desc: String,
+    pub sent: String,
+    pub effective: Option<String>,
+    pub onset: Option<String>,
+    pub expires: Option<String>,
+    pub ends: Option<String>,
+    #[serde(rename = "messageType")]
+    pub message_type: String,
+    pub severity: String,
+    pub certainty: String,
+    pub urgency: String,
+    pub event: String,
+    #[serde(rename = "senderName")]
+    pub sender_name: String,
+    pub headline: Option<String>,
+    pub description: Option<String>,
+    #[serde(default)]
+    pub parameters: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SnReportsResult {
+    pub reports: Vec<SnReport>,
+}
+
+/**
+ * A report from the SpotterNetwork JSON API. Unlike the placefile, reports have a stable id.
+ * NOTE: Unverified - these fields haven't been checked against real API responses yet.
+ */
+#[derive(Debug, Deserialize)]
+pub struct SnReport {
+    pub id: u64,
+    pub lat: f32,
+    pub lon: f32,
+    #[serde(rename = "type")]
+    pub report_type: u8, // same hazard codes as the placefile icons
+    pub reporter: String,
+    pub report_time: String, // RFC3339
+    pub hail_size: Option<f32>,
+    pub wind_speed: Option<u16>,
+    #[serde(default)]
+    pub wind_measured: bool,
+    pub notes: Option<String>,
+}
+
+/**
+ * A page of reports from the mPING API.
+ */
+#[derive(Debug, Deserialize)]
+pub struct MpingReportsResult {
+    pub next: Option<String>,
+    pub results: Vec<MpingReport>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MpingReport {
+    pub id: u64,
+    pub obtime: String, // RFC3339
+    pub category: String,
+    pub description: String,
+    pub geom: MpingGeometry,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MpingGeometry {
+    pub coordinates: Vec<f32>, // lon, lat
+}
@@ -0,0 +1,134 @@
+use crate::optimized::OptimizedEvent;
+use crate::{Coordinates, Event, EventType};
+
+/**
+ * Whether a Flash Flood Warning was issued inside a WPC MPD, ie. while the MPD was valid and with
+ * the center of the warning's polygon inside the MPD's. Warnings follow county and basin lines, so
+ * they often spill over the edge of the MPD even when they were issued for it.
+ */
+pub fn is_ffw_in_mpd(ffw: &Event, mpd: &OptimizedEvent) -> bool {
+    if ffw.event_type != EventType::NwsFfw || mpd.event_type != EventType::NwsMpd {
+        return false;
+    }
+
+    let valid_ts = mpd.valid_ts.unwrap_or(mpd.event_ts);
+    let is_valid = match mpd.expires_ts {
+        Some(expires_ts) => ffw.event_ts >= valid_ts && ffw.event_ts <= expires_ts,
+        None => false,
+    };
+
+    let ffw_poly = ffw.location.as_ref().and_then(|x| x.poly.as_ref());
+    let mpd_poly = mpd.location.as_ref().and_then(|x| x.poly.as_ref());
+    match (ffw_poly.and_then(|x| get_center(x)), mpd_poly) {
+        (Some(center), Some(mpd_poly)) => is_valid && contains(mpd_poly, &center),
+        _ => false,
+    }
+}
+
+fn get_center(poly: &[Coordinates]) -> Option<Coordinates> {
+    if poly.is_empty() {
+        return None;
+    }
+
+    let count = poly.len() as f32;
+    Some(Coordinates {
+        lat: poly.iter().map(|x| x.lat).sum::<f32>() / count,
+        lon: poly.iter().map(|x| x.lon).sum::<f32>() / count,
+    })
+}
+
+/**
+ * Ray casting, which is plenty for polygons the size of an MPD.
+ */
+fn contains(poly: &[Coordinates], point: &Coordinates) -> bool {
+    let mut is_inside = false;
+    let mut previous = match poly.last() {
+        Some(previous) => previous,
+        None => return false,
+    };
+
+    for current in poly {
+        if (current.lat > point.lat) != (previous.lat > point.lat) {
+            let crossing_lon = current.lon
+                + (point.lat - current.lat) / (previous.lat - current.lat)
+                    * (previous.lon - current.lon);
+            if point.lon < crossing_lon {
+                is_inside = !is_inside;
+            }
+        }
+        previous = current;
+    }
+
+    is_inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimized::Location;
+    use crate::test_util;
+
+    fn get_mpd() -> OptimizedEvent {
+        OptimizedEvent {
+            event_ts: 1558491300000000,
+            expires_ts: Some(1558512000000000),
+            location: Some(Location {
+                wfo: None,
+                point: None,
+                poly: Some(vec![
+                    Coordinates {
+                        lat: 34.92,
+                        lon: -96.49,
+                    },
+                    Coordinates {
+                        lat: 33.29,
+                        lon: -97.07,
+                    },
+                    Coordinates {
+                        lat: 34.6,
+                        lon: -97.6,
+                    },
+                ]),
+                county: None,
+            }),
+            source_id: Some("nws:mpd:0".to_string()),
+            valid_ts: Some(1558491300000000),
+            ..test_util::get_event(EventType::NwsMpd, "WPC MPD: Flash Flooding Likely")
+        }
+    }
+
+    fn get_ffw(event_ts: u64, lat: f32) -> Event {
+        let mut ffw = Event::new(event_ts, EventType::NwsFfw, "FFW".to_string());
+        ffw.location = Some(crate::Location {
+            wfo: Some("KOUN".to_string()),
+            point: None,
+            poly: Some(vec![
+                Coordinates {
+                    lat: lat + 0.1,
+                    lon: -97.2,
+                },
+                Coordinates {
+                    lat: lat - 0.1,
+                    lon: -97.2,
+                },
+                Coordinates { lat, lon: -97.0 },
+            ]),
+            county: None,
+        });
+        ffw
+    }
+
+    #[test]
+    fn is_ffw_in_mpd_should_check_polygon_and_valid_time() {
+        let mpd = get_mpd();
+        assert!(is_ffw_in_mpd(&get_ffw(1558500000000000, 34.3), &mpd));
+        // north of the MPD
+        assert!(!is_ffw_in_mpd(&get_ffw(1558500000000000, 35.5), &mpd));
+        // after the MPD expired
+        assert!(!is_ffw_in_mpd(&get_ffw(1558520000000000, 34.3), &mpd));
+
+        let mut not_ffw = get_ffw(1558500000000000, 34.3);
+        not_ffw.event_type = EventType::NwsSvr;
+        assert!(!is_ffw_in_mpd(&not_ffw, &mpd));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::{
-    Coordinates, EventType, HazardType, MdConcerning, OutlookRisk, SwoType, Units, WatchStatus,
-    WatchType,
+    AreaForecastDiscussion, Coordinates, EventType, FireHazardType, FireOutlookDay,
+    FireOutlookRisk, HazardType, MdConcerning, OutlookRisk, SwoType, Units, WatchProbabilities,
+    WatchStatus, WatchType, WinterHazardType,
 };
 use std::collections::HashMap;
 
@@ -9,12 +10,18 @@ use std::collections::HashMap;
  */
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct OptimizedEvent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub afd: Option<AreaForecastDiscussion>,
     pub event_ts: u64,
     pub event_type: EventType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_ts: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ext_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fire: Option<FireHazard>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fire_outlook: Option<FireOutlook>,
     pub ingest_ts: u128,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub location: Option<Location>,
@@ -25,14 +32,22 @@ pub struct OptimizedEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub report: Option<Report>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub survey: Option<TornadoSurvey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<String>,
     pub title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub tropical: Option<TropicalCyclone>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub valid_ts: Option<u64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub warning: Option<Warning>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub watch: Option<Watch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winter: Option<WinterHazard>,
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
@@ -58,10 +73,12 @@ pub struct Report {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub was_measured: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub report_ts: Option<u64>, // only populated for LSRs
+    pub report_ts: Option<u64>, // only populated for LSRs and SPC reports
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lsr_source_id: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Watch {
     pub is_pds: bool,
     pub id: u16,
@@ -69,9 +86,21 @@ pub struct Watch {
     pub status: WatchStatus,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub issued_for: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_hail_size: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_wind_gust_kt: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub motion_deg: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub motion_kt: Option<u16>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub replaces_ids: Vec<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub probabilities: Option<WatchProbabilities>,
 }
 
-#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
 pub struct Warning {
     pub is_pds: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -85,7 +114,22 @@ pub struct Warning {
     pub motion_kt: Option<u16>, // TOR and SVR only
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source: Option<String>, // TOR and SVR only
-    pub time: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vtec: Option<String>, // event tracking number, ie. KTOP.TO.W.0009.2018
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hail_size: Option<f32>, // inches, SMW and SPS only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wind_gust: Option<String>, // ie. >34KTS, SMW and SPS only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waterspout: Option<String>, // POSSIBLE or OBSERVED, SMW and SPS only
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mpd_source_id: Option<String>, // FFW only, the MPD it was issued under
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub storm_points: Vec<Coordinates>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub severity: Option<String>, // alerts only
 }
 
 #[derive(Debug, Deserialize, PartialEq, Serialize)]
@@ -104,4 +148,89 @@ pub struct MesoscaleDiscussion {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub watch_issuance_probability: Option<u16>,
     pub wfos: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub watch_ids: Vec<u16>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WinterHazard {
+    pub hazard_type: WinterHazardType,
+    pub action: String,
+    pub vtec: String,
+    pub zones: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub what: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#where: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FireHazard {
+    pub hazard_type: FireHazardType,
+    pub action: String,
+    pub vtec: String,
+    pub zones: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub affected_area: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub humidity: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub impacts: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FireOutlook {
+    pub day: FireOutlookDay,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_risk: Option<FireOutlookRisk>,
+    pub has_dry_thunderstorms: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub synopsis: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct TornadoSurvey {
+    pub name: String,
+    pub rating: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_wind_mph: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_length_mi: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path_width_yd: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fatalities: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub injuries: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_ts: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_ts: Option<u64>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub path: Vec<Coordinates>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Deserialize, PartialEq, Serialize)]
+pub struct TropicalCyclone {
+    pub storm_id: String,
+    pub name: String,
+    pub advisory: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_wind_mph: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pressure_mb: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub motion_deg: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub motion_mph: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headline: Option<String>,
 }
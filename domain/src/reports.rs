@@ -0,0 +1,166 @@
+use crate::optimized::OptimizedEvent;
+use crate::{Coordinates, Event, EventType, HazardType};
+
+// SPC reports keep the LSR's time and location, but locations get rounded and times corrected
+const MATCH_THRESHOLD_MICROS: u64 = 15 * 60 * 1_000_000;
+const MATCH_THRESHOLD_DEGREES: f32 = 0.1;
+
+/**
+ * The parts of a report used to decide whether an SPC report and an LSR describe the same thing.
+ */
+#[derive(Debug, PartialEq)]
+pub struct ReportKey<'a> {
+    pub hazard: &'a HazardType,
+    pub ts: u64,
+    pub point: &'a Coordinates,
+}
+
+impl<'a> ReportKey<'a> {
+    pub fn from_event(event: &'a Event) -> Option<ReportKey<'a>> {
+        let report = event.report.as_ref()?;
+        Some(ReportKey {
+            hazard: &report.hazard,
+            ts: report.report_ts.unwrap_or(event.event_ts),
+            point: event.location.as_ref()?.point.as_ref()?,
+        })
+    }
+
+    pub fn from_optimized(event: &'a OptimizedEvent) -> Option<ReportKey<'a>> {
+        let report = event.report.as_ref()?;
+        Some(ReportKey {
+            hazard: &report.hazard,
+            ts: report.report_ts.unwrap_or(event.event_ts),
+            point: event.location.as_ref()?.point.as_ref()?,
+        })
+    }
+
+    pub fn matches(&self, other: &ReportKey) -> bool {
+        let ts_delta = self.ts.max(other.ts) - self.ts.min(other.ts);
+
+        self.hazard == other.hazard
+            && ts_delta <= MATCH_THRESHOLD_MICROS
+            && (self.point.lat - other.point.lat).abs() <= MATCH_THRESHOLD_DEGREES
+            && (self.point.lon - other.point.lon).abs() <= MATCH_THRESHOLD_DEGREES
+    }
+}
+
+/**
+ * Counts of tornado, wind and hail reports, the categories SPC publishes.
+ */
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct ReportCounts {
+    pub tornado: usize,
+    pub wind: usize,
+    pub hail: usize,
+}
+
+/**
+ * Counts SPC reports, plus any LSRs that no SPC report has been reconciled with yet. Once SPC has
+ * filtered a day's reports, the counts line up with SPC's.
+ */
+pub fn count_reports(events: &[OptimizedEvent]) -> ReportCounts {
+    let reconciled: Vec<&str> = events
+        .iter()
+        .filter(|x| x.event_type == EventType::SpcReport)
+        .filter_map(|x| x.report.as_ref()?.lsr_source_id.as_deref())
+        .collect();
+
+    let mut counts = ReportCounts::default();
+
+    events
+        .iter()
+        .filter(|x| match x.event_type {
+            EventType::SpcReport => true,
+            EventType::NwsLsr => match &x.source_id {
+                Some(source_id) => !reconciled.contains(&source_id.as_str()),
+                None => true,
+            },
+            _ => false,
+        })
+        .filter_map(|x| x.report.as_ref())
+        .for_each(|report| match report.hazard {
+            HazardType::Tornado => counts.tornado += 1,
+            HazardType::Wind => counts.wind += 1,
+            HazardType::Hail => counts.hail += 1,
+            _ => (),
+        });
+
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::optimized::{Location, Report};
+    use crate::test_util;
+
+    fn get_event(
+        event_type: EventType,
+        hazard: HazardType,
+        source_id: &str,
+        lsr_source_id: Option<&str>,
+    ) -> OptimizedEvent {
+        OptimizedEvent {
+            event_ts: 1522113360000000,
+            location: Some(Location {
+                wfo: None,
+                point: Some(Coordinates {
+                    lat: 32.07,
+                    lon: -100.66,
+                }),
+                poly: None,
+                county: None,
+            }),
+            report: Some(Report {
+                reporter: "SPC".to_string(),
+                hazard,
+                magnitude: None,
+                units: None,
+                was_measured: None,
+                report_ts: Some(1522112100000000),
+                lsr_source_id: lsr_source_id.map(|x| x.to_string()),
+            }),
+            source_id: Some(source_id.to_string()),
+            ..test_util::get_event(event_type, "Report")
+        }
+    }
+
+    #[test]
+    fn report_keys_should_match_within_thresholds() {
+        let lsr = get_event(EventType::NwsLsr, HazardType::Hail, "nws:1:0", None);
+        let mut spc = get_event(EventType::SpcReport, HazardType::Hail, "spc:1", None);
+        let lsr_key = ReportKey::from_optimized(&lsr).unwrap();
+        assert!(ReportKey::from_optimized(&spc).unwrap().matches(&lsr_key));
+
+        if let Some(report) = spc.report.as_mut() {
+            report.report_ts = Some(1522112100000000 + MATCH_THRESHOLD_MICROS + 1);
+        }
+        assert!(!ReportKey::from_optimized(&spc).unwrap().matches(&lsr_key));
+
+        let wind = get_event(EventType::SpcReport, HazardType::Wind, "spc:2", None);
+        assert!(!ReportKey::from_optimized(&wind).unwrap().matches(&lsr_key));
+    }
+
+    #[test]
+    fn count_reports_should_not_double_count_reconciled_lsrs() {
+        let events = vec![
+            get_event(EventType::NwsLsr, HazardType::Hail, "nws:1:0", None),
+            get_event(EventType::NwsLsr, HazardType::Tornado, "nws:2:0", None),
+            get_event(EventType::NwsLsr, HazardType::Flood, "nws:3:0", None),
+            get_event(
+                EventType::SpcReport,
+                HazardType::Hail,
+                "spc:1",
+                Some("nws:1:0"),
+            ),
+            get_event(EventType::SpcReport, HazardType::Wind, "spc:2", None),
+            get_event(EventType::SnReport, HazardType::Hail, "sn-api:1", None),
+        ];
+        let expected = ReportCounts {
+            tornado: 1,
+            wind: 1,
+            hail: 1,
+        };
+        assert_eq!(count_reports(&events), expected);
+    }
+}
@@ -0,0 +1,32 @@
+use crate::optimized::OptimizedEvent;
+use crate::EventType;
+
+/**
+ * Builds an event with only its type and title set, so tests can fill in just the fields they
+ * check with struct update syntax.
+ */
+pub fn get_event(event_type: EventType, title: &str) -> OptimizedEvent {
+    OptimizedEvent {
+        afd: None,
+        event_ts: 0,
+        event_type,
+        expires_ts: None,
+        ext_uri: None,
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0,
+        location: None,
+        md: None,
+        outlook: None,
+        report: None,
+        source_id: None,
+        survey: None,
+        text: None,
+        title: title.to_string(),
+        tropical: None,
+        valid_ts: None,
+        warning: None,
+        watch: None,
+        winter: None,
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::prelude::*;
+use std::fmt::Write;
+use std::time::{Duration, UNIX_EPOCH};
+
+/**
+ * Helpers shared by the XML-based output formats.
+ */
+pub fn push_element(xml: &mut String, depth: usize, name: &str, value: &str) {
+    let _ = writeln!(
+        xml,
+        "{}<{}>{}</{}>",
+        "  ".repeat(depth),
+        name,
+        escape(value),
+        name
+    );
+}
+
+pub fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+pub fn to_datetime(micros: u64) -> DateTime<Utc> {
+    (UNIX_EPOCH + Duration::from_micros(micros)).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_should_replace_reserved_characters() {
+        let result = escape(r#"<a href="x">Tom & Jerry's</a>"#);
+        let expected = "&lt;a href=&quot;x&quot;&gt;Tom &amp; Jerry&apos;s&lt;/a&gt;";
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn to_datetime_should_convert_micros() {
+        let result = to_datetime(1525222860000000);
+        assert_eq!(result.to_rfc3339(), "2018-05-02T01:01:00+00:00");
+    }
+}
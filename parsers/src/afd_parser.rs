@@ -1,33 +1,168 @@
 use domain::Product;
-use domain::{Event, EventType};
+use domain::{
+    AfdHighlight, AfdKeyword, AfdSection, AfdSectionType, AreaForecastDiscussion, Event, EventType,
+};
+use regex::{Regex, RegexBuilder};
 use util::safe_result;
 
+const SECTION_PATTERN: &str = r"^\.(?P<name>[A-Z][A-Z0-9 /&]*?)\.{3}";
+const TORNADO_PATTERN: &str = r"\btornad(o|oes|ic)\b";
+const SUPERCELL_PATTERN: &str = r"\bsupercells?\b";
+const SIGNIFICANT_SEVERE_PATTERN: &str = r"\bsignificant severe\b";
+const HAIL_PATTERN: &str = r#"(\b(\d+(\.\d+)?|one|two|three)(\s*(to|-)\s*\d+(\.\d+)?)?\s*(inch|inches|in\.|")|\b(golf ?ball|baseball|softball|tennis ball|hen egg|ping pong|quarter|half dollar))[\s-]+(sized?\s+)?hail\b|\bhail\s+(up to|of|around|to)\s+(\d+(\.\d+)?|one|two|three)(\s*(to|-)\s*\d+(\.\d+)?)?\s*(inch|inches|in\.|")"#;
+
+lazy_static! {
+    static ref SECTION_REGEX: Regex = Regex::new(SECTION_PATTERN).expect("Unable to compile regex");
+    static ref KEYWORD_REGEXES: Vec<(AfdKeyword, Regex)> = vec![
+        (AfdKeyword::Tornado, build_regex(TORNADO_PATTERN)),
+        (AfdKeyword::Supercell, build_regex(SUPERCELL_PATTERN)),
+        (
+            AfdKeyword::SignificantSevere,
+            build_regex(SIGNIFICANT_SEVERE_PATTERN)
+        ),
+        (AfdKeyword::Hail, build_regex(HAIL_PATTERN)),
+    ];
+}
+
+/**
+ * Splits an AFD into its sections and flags sentences that mention severe convection. The text
+ * isn't kept on the event since the sections already carry all of it.
+ */
 pub fn parse(product: &Product) -> Option<Event> {
     let wfo = product.issuing_office.to_string();
     let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
     let title = format!("Area Forecast Discussion ({})", wfo);
     let ext_uri = Some(product._id.to_string());
 
+    let sections = get_sections(&product.product_text);
+    let highlights = sections.iter().flat_map(get_highlights).collect();
+
     let event = Event {
+        afd: Some(AreaForecastDiscussion {
+            sections,
+            highlights,
+        }),
         event_ts,
         event_type: EventType::NwsAfd,
         expires_ts: None,
         ext_uri,
+        fire: None,
+        fire_outlook: None,
         ingest_ts: 0,
         location: None,
         md: None,
         outlook: None,
         report: None,
+        source_id: None,
+        survey: None,
         text: None,
         title,
+        tropical: None,
         valid_ts: None,
         warning: None,
         watch: None,
+        winter: None,
     };
 
     Some(event)
 }
 
+/**
+ * Sections start with a header like `.SHORT TERM...(Tonight through Saturday)` and run until the
+ * next header, or the `&&` and `$$` separators.
+ */
+fn get_sections(text: &str) -> Vec<AfdSection> {
+    let mut sections: Vec<AfdSection> = vec![];
+    let mut current: Option<(String, Vec<&str>)> = None;
+
+    for line in text.lines() {
+        let trimmed = line.trim();
+        let header = SECTION_REGEX.captures(trimmed);
+
+        if header.is_some() || trimmed.starts_with("&&") || trimmed.starts_with("$$") {
+            if let Some((name, lines)) = current.take() {
+                sections.push(get_section(name, &lines));
+            }
+        }
+
+        match (&mut current, header) {
+            (_, Some(header)) => current = Some((header["name"].trim().to_string(), vec![])),
+            (Some((_, lines)), None) => lines.push(line),
+            (None, None) => (),
+        }
+    }
+
+    if let Some((name, lines)) = current {
+        sections.push(get_section(name, &lines));
+    }
+
+    sections
+}
+
+fn get_section(name: String, lines: &[&str]) -> AfdSection {
+    AfdSection {
+        section_type: get_section_type(&name),
+        name,
+        text: lines.join("\n").trim().to_string(),
+    }
+}
+
+fn get_section_type(name: &str) -> AfdSectionType {
+    match name {
+        x if x.contains("SYNOPSIS") => AfdSectionType::Synopsis,
+        x if x.contains("UPDATE") => AfdSectionType::Update,
+        x if x.contains("NEAR TERM") => AfdSectionType::NearTerm,
+        x if x.contains("SHORT TERM") => AfdSectionType::ShortTerm,
+        x if x.contains("LONG TERM") => AfdSectionType::LongTerm,
+        x if x.contains("AVIATION") => AfdSectionType::Aviation,
+        x if x.contains("MARINE") => AfdSectionType::Marine,
+        x if x.contains("FIRE WEATHER") => AfdSectionType::FireWeather,
+        x if x.contains("HYDROLOGY") => AfdSectionType::Hydrology,
+        x if x.contains("WATCHES/WARNINGS") => AfdSectionType::WatchesWarnings,
+        x if x.contains("DISCUSSION") => AfdSectionType::Discussion,
+        _ => AfdSectionType::Other,
+    }
+}
+
+/**
+ * Returns a highlight for each keyword found in each sentence, with the sentence as the snippet.
+ */
+fn get_highlights(section: &AfdSection) -> Vec<AfdHighlight> {
+    let text = section
+        .text
+        .split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ");
+    let mut highlights = vec![];
+
+    for sentence in text.split(". ") {
+        for (keyword, regex) in KEYWORD_REGEXES.iter() {
+            if regex.is_match(sentence) {
+                let snippet = if sentence.ends_with('.') {
+                    sentence.to_string()
+                } else {
+                    format!("{}.", sentence)
+                };
+
+                highlights.push(AfdHighlight {
+                    keyword: *keyword,
+                    section_type: section.section_type,
+                    snippet,
+                });
+            }
+        }
+    }
+
+    highlights
+}
+
+fn build_regex(pattern: &str) -> Regex {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .expect("Unable to compile regex")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,12 +171,83 @@ mod tests {
     #[test]
     fn parse_afd_product() {
         let product = get_product_from_file("../data/products/afd-mpx");
-        let result = parse(&product).unwrap();
+        let mut result = parse(&product).unwrap();
+        let afd = result.afd.take().unwrap();
+        let names: Vec<&str> = afd.sections.iter().map(|x| x.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "UPDATE",
+                "SHORT TERM",
+                "LONG TERM",
+                "AVIATION",
+                "MPX WATCHES/WARNINGS/ADVISORIES"
+            ]
+        );
+        assert_eq!(
+            afd.sections[4].section_type,
+            AfdSectionType::WatchesWarnings
+        );
+        assert!(afd.sections[4]
+            .text
+            .starts_with("WI...Winter Storm Warning"));
+        assert!(afd.sections[4].text.ends_with("MNZ074-082-091."));
+        assert!(afd.highlights.is_empty());
+
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1523671620000000,"event_type":"NwsAfd","expires_ts":null,"ext_uri":"https://api.weather.gov/products/d0b93b47-1052-4b07-965e-286025226ba8","ingest_ts":0,"location":null,"md":null,"outlook":null,"report":null,"text":null,"title":"Area Forecast Discussion (KMPX)","valid_ts":null,"warning":null,"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1523671620000000,"event_type":"NwsAfd","expires_ts":null,"ext_uri":"https://api.weather.gov/products/d0b93b47-1052-4b07-965e-286025226ba8","fire":null,"fire_outlook":null,"ingest_ts":0,"location":null,"md":null,"outlook":null,"report":null,"source_id":null,"survey":null,"text":null,"title":"Area Forecast Discussion (KMPX)","tropical":null,"valid_ts":null,"warning":null,"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
+    #[test]
+    fn parse_should_highlight_convective_keywords() {
+        let product = get_product_from_file("../data/products/afd-oax-severe");
+        let afd = parse(&product).unwrap().afd.unwrap();
+        assert_eq!(afd.sections.len(), 5);
+        assert_eq!(afd.sections[0].section_type, AfdSectionType::Synopsis);
+
+        let highlights: Vec<(AfdKeyword, AfdSectionType)> = afd
+            .highlights
+            .iter()
+            .map(|x| (x.keyword, x.section_type))
+            .collect();
+        let expected = vec![
+            (AfdKeyword::Tornado, AfdSectionType::ShortTerm),
+            (AfdKeyword::Supercell, AfdSectionType::ShortTerm),
+            (AfdKeyword::Tornado, AfdSectionType::ShortTerm),
+            (AfdKeyword::Hail, AfdSectionType::ShortTerm),
+            (AfdKeyword::SignificantSevere, AfdSectionType::ShortTerm),
+            (AfdKeyword::Hail, AfdSectionType::Aviation),
+        ];
+        assert_eq!(highlights, expected);
+        assert_eq!(
+            afd.highlights[0].snippet,
+            "Discrete supercells are likely initially, capable of tornadoes and very large hail."
+        );
+        assert_eq!(
+            afd.highlights[3].snippet,
+            "Hail up to 2.5 inches in diameter will be possible with the strongest storms."
+        );
+    }
+
+    #[test]
+    fn get_highlights_should_require_hail_size() {
+        let section = AfdSection {
+            section_type: AfdSectionType::Discussion,
+            name: "DISCUSSION".to_string(),
+            text: "Small hail is possible. Quarter size hail is possible. Hail of 1-2\" too"
+                .to_string(),
+        };
+        let snippets: Vec<String> = get_highlights(&section)
+            .into_iter()
+            .map(|x| x.snippet)
+            .collect();
+        assert_eq!(
+            snippets,
+            vec!["Quarter size hail is possible.", "Hail of 1-2\" too."]
+        );
+    }
+
     #[test]
     fn bad_timestamp() {
         let mut product = get_product_from_file("../data/products/afd-mpx");
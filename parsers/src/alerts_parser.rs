@@ -0,0 +1,310 @@
+use crate::nws_regexes::Regexes;
+use crate::parser_util::get_vtec_id;
+use domain::{Alert, AlertGeometry, Coordinates, Event, EventType, Location, Warning};
+use regex::Regex;
+use util;
+use util::safe_result;
+
+const MOTION_PATTERN: &str =
+    r"^(?P<time>[^.]+)\.{3}.+?\.{3}(?P<deg>\d+)DEG\.{3}(?P<kt>\d+)KT\.{3}(?P<loc>.+)$";
+
+lazy_static! {
+    static ref MOTION_REGEX: Regex = Regex::new(MOTION_PATTERN).expect("Unable to compile regex");
+}
+
+/**
+ * Parses a batch of alerts from the NWS /alerts API. Only new warnings we also parse from products
+ * are handled, and each carries the VTEC tracking number so the store can reconcile it with the
+ * copy parsed from the product. Updates and cancellations are left to the SVS parser.
+ */
+pub fn parse(alerts: &[&Alert]) -> Vec<Event> {
+    let regexes = Regexes::new();
+    alerts
+        .iter()
+        .filter_map(|alert| parse_alert(alert, &regexes))
+        .collect()
+}
+
+fn parse_alert(alert: &Alert, regexes: &Regexes) -> Option<Event> {
+    let properties = &alert.properties;
+    if properties.message_type != "Alert" {
+        return None;
+    }
+
+    let (event_type, name) = match properties.event.as_ref() {
+        "Tornado Warning" => (EventType::NwsTor, "Tornado Warning"),
+        "Severe Thunderstorm Warning" => (EventType::NwsSvr, "Severe Thunderstorm Warning"),
+        "Flash Flood Warning" => (EventType::NwsFfw, "Flash Flood Warning"),
+        _ => return None,
+    };
+
+    let vtec = get_parameter(alert, "VTEC").and_then(|x| get_vtec_id(x, regexes));
+    let wfo = match &vtec {
+        Some(vtec) => vtec[0..4].to_string(),
+        None => {
+            warn!("Alert {} has no VTEC", properties.id);
+            return None;
+        }
+    };
+
+    let event_ts = safe_result!(util::rfc3339_to_ticks(&properties.sent));
+    let valid_ts = properties
+        .onset
+        .as_ref()
+        .or(properties.effective.as_ref())
+        .and_then(|x| util::rfc3339_to_ticks(x).ok());
+    let expires_ts = properties
+        .ends
+        .as_ref()
+        .or(properties.expires.as_ref())
+        .and_then(|x| util::rfc3339_to_ticks(x).ok());
+
+    let motion =
+        get_parameter(alert, "eventMotionDescription").and_then(|x| MOTION_REGEX.captures(x));
+    let storm_points: Vec<Coordinates> = motion
+        .as_ref()
+        .map(|x| {
+            x["loc"]
+                .split_whitespace()
+                .filter_map(|point| {
+                    let mut latlon = point.split(',').filter_map(|x| x.parse().ok());
+                    Some(Coordinates {
+                        lat: latlon.next()?,
+                        lon: latlon.next()?,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let point = storm_points.first().copied();
+    let motion_deg = motion
+        .as_ref()
+        .and_then(|x| x.name("deg")?.as_str().parse().ok());
+    let motion_kt = motion
+        .as_ref()
+        .and_then(|x| x.name("kt")?.as_str().parse().ok());
+    let time = motion
+        .as_ref()
+        .and_then(|x| util::rfc3339_to_ticks(&x["time"]).ok());
+
+    let poly = match &alert.geometry {
+        Some(AlertGeometry::Polygon { coordinates }) => coordinates.first().map(|ring| {
+            ring.iter()
+                .filter(|position| position.len() >= 2)
+                .map(|position| Coordinates {
+                    lat: position[1],
+                    lon: position[0],
+                })
+                .collect::<Vec<Coordinates>>()
+        }),
+        _ => None,
+    };
+
+    let description = properties.description.as_ref();
+    let lower_case_text = description.map(|x| x.to_lowercase()).unwrap_or_default();
+    let is_tor = event_type == EventType::NwsTor;
+
+    let warning = Some(Warning {
+        is_pds: lower_case_text.contains("particularly dangerous situation"),
+        was_observed: if is_tor {
+            Some(get_parameter(alert, "tornadoDetection") == Some("OBSERVED"))
+        } else {
+            None
+        },
+        is_tor_emergency: if is_tor {
+            Some(get_parameter(alert, "tornadoDamageThreat") == Some("CATASTROPHIC"))
+        } else {
+            None
+        },
+        motion_deg,
+        motion_kt,
+        source: None,
+        issued_for: properties.area_desc.to_string(),
+        time,
+        vtec,
+        hail_size: None,
+        wind_gust: None,
+        waterspout: None,
+        mpd_source_id: None,
+        storm_points,
+        severity: Some(properties.severity.to_string()),
+    });
+
+    let location = Some(Location {
+        wfo: Some(wfo.to_string()),
+        point,
+        poly,
+        county: None,
+    });
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type,
+        expires_ts,
+        ext_uri: Some(alert.id.to_string()),
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0,
+        location,
+        md: None,
+        outlook: None,
+        report: None,
+        source_id: Some(format!("alerts:{}", properties.id)),
+        survey: None,
+        text: description.cloned(),
+        title: format!("{} ({})", name, wfo),
+        tropical: None,
+        valid_ts,
+        warning,
+        watch: None,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+/**
+ * Fills in what a warning's alert has that the copy parsed from its product doesn't, ie. the alert
+ * link. Anything both have comes from the product, since its text, source, and LAT...LON and
+ * TIME...MOT...LOC data are more complete.
+ */
+pub fn merge(event: &mut Event, alert: Event) {
+    event.ext_uri = event.ext_uri.take().or(alert.ext_uri);
+    event.valid_ts = event.valid_ts.or(alert.valid_ts);
+    event.expires_ts = event.expires_ts.or(alert.expires_ts);
+    event.text = event.text.take().or(alert.text);
+
+    match (event.location.as_mut(), alert.location) {
+        (Some(location), Some(alert_location)) => {
+            location.wfo = location.wfo.take().or(alert_location.wfo);
+            location.point = location.point.or(alert_location.point);
+            location.poly = location.poly.take().or(alert_location.poly);
+            location.county = location.county.take().or(alert_location.county);
+        }
+        (None, alert_location) => event.location = alert_location,
+        _ => (),
+    }
+
+    match (event.warning.as_mut(), alert.warning) {
+        (Some(warning), Some(alert_warning)) => {
+            warning.is_tor_emergency = warning.is_tor_emergency.or(alert_warning.is_tor_emergency);
+            warning.was_observed = warning.was_observed.or(alert_warning.was_observed);
+            warning.motion_deg = warning.motion_deg.or(alert_warning.motion_deg);
+            warning.motion_kt = warning.motion_kt.or(alert_warning.motion_kt);
+            warning.time = warning.time.or(alert_warning.time);
+            warning.vtec = warning.vtec.take().or(alert_warning.vtec);
+            warning.severity = warning.severity.take().or(alert_warning.severity);
+            if warning.storm_points.is_empty() {
+                warning.storm_points = alert_warning.storm_points;
+            }
+        }
+        (None, alert_warning) => event.warning = alert_warning,
+        _ => (),
+    }
+}
+
+fn get_parameter<'a>(alert: &'a Alert, name: &str) -> Option<&'a str> {
+    alert
+        .properties
+        .parameters
+        .get(name)
+        .and_then(|values| values.first())
+        .map(|value| value.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::get_product_from_file;
+    use super::*;
+    use crate::tor_parser;
+    use domain::AlertsResult;
+    use std::fs::File;
+    use std::io::Read;
+
+    fn get_alerts() -> Vec<Alert> {
+        let mut f = File::open("../data/alerts-active").expect("alerts file not found");
+        let mut contents = String::new();
+        f.read_to_string(&mut contents)
+            .expect("something went wrong reading the file");
+        let result: AlertsResult = serde_json::from_str(&contents).unwrap();
+        result.features
+    }
+
+    #[test]
+    fn parse_tor_alert_happy_path() {
+        let alerts = get_alerts();
+        let result = parse_alert(&alerts[0], &Regexes::new()).unwrap();
+        let serialized_result = serde_json::to_string(&result).unwrap();
+        let expected = r#"{"afd":null,"event_ts":1525222860000000,"event_type":"NwsTor","expires_ts":1525225500000000,"ext_uri":"https://api.weather.gov/alerts/NWS-IDP-PROD-3562245-3098516","fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KTOP","point":{"lat":39.52,"lon":-97.28},"poly":[{"lat":39.77,"lon":-96.97},{"lat":39.5,"lon":-96.8},{"lat":39.39,"lon":-97.37},{"lat":39.59,"lon":-97.37},{"lat":39.77,"lon":-96.97}],"county":null},"md":null,"outlook":null,"report":null,"source_id":"alerts:NWS-IDP-PROD-3562245-3098516","survey":null,"text":"At 800 PM CDT, a large and extremely dangerous tornado was located\n2 miles south of Clifton, moving northeast at 25 mph.\n\nThis is a PARTICULARLY DANGEROUS SITUATION. TAKE COVER NOW!","title":"Tornado Warning (KTOP)","tropical":null,"valid_ts":1525222860000000,"warning":{"is_pds":true,"is_tor_emergency":true,"was_observed":true,"issued_for":"Riley, KS; Washington, KS; Clay, KS","motion_deg":245,"motion_kt":24,"source":null,"time":1525222800000000,"vtec":"KTOP.TO.W.0009.2018","hail_size":null,"wind_gust":null,"waterspout":null,"mpd_source_id":null,"storm_points":[{"lat":39.52,"lon":-97.28}],"severity":"Extreme"},"watch":null,"winter":null}"#;
+        assert_eq!(expected, serialized_result);
+    }
+
+    #[test]
+    fn parse_svr_alert_should_match_text_parser_vtec() {
+        let alerts = get_alerts();
+        let result = parse_alert(&alerts[1], &Regexes::new()).unwrap();
+        assert_eq!(result.event_type, EventType::NwsSvr);
+        assert_eq!(result.title, "Severe Thunderstorm Warning (KDMX)");
+        let warning = result.warning.unwrap();
+        assert_eq!(warning.vtec, Some("KDMX.SV.W.0002.2018".to_string()));
+        assert_eq!(warning.was_observed, None);
+        assert_eq!(warning.severity, Some("Severe".to_string()));
+    }
+
+    #[test]
+    fn merge_should_prefer_product_fields() {
+        let alerts = get_alerts();
+        let regexes = Regexes::new();
+        let mut event =
+            tor_parser::parse(&get_product_from_file("../data/products/tor-normal")).unwrap();
+        if let Some(warning) = event.warning.as_mut() {
+            warning.motion_deg = None;
+        }
+        merge(&mut event, parse_alert(&alerts[0], &regexes).unwrap());
+
+        assert_eq!(
+            event.ext_uri,
+            Some("https://api.weather.gov/alerts/NWS-IDP-PROD-3562245-3098516".to_string())
+        );
+        assert!(event.text.unwrap().contains("TIME...MOT...LOC"));
+        assert_eq!(event.location.unwrap().poly.unwrap().len(), 4);
+        let warning = event.warning.unwrap();
+        assert_eq!(warning.source, Some("Radar indicated rotation".to_string()));
+        assert!(!warning.is_pds);
+        assert_eq!(warning.motion_deg, Some(245));
+        assert_eq!(warning.severity, Some("Extreme".to_string()));
+    }
+
+    #[test]
+    fn unsupported_geometry_should_not_fail_the_batch() {
+        let contents = std::fs::read_to_string("../data/alerts-active").unwrap();
+        let mut result: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        let multi_polygon = serde_json::json!({
+            "type": "MultiPolygon",
+            "coordinates": [[[[-97.0, 39.7], [-96.8, 39.5], [-97.3, 39.3], [-97.0, 39.7]]]]
+        });
+        result["features"][0]["geometry"] = multi_polygon.clone();
+        result["features"][2]["geometry"] = multi_polygon;
+        let result: AlertsResult = serde_json::from_value(result).unwrap();
+
+        assert_eq!(result.features.len(), 3);
+        let event = parse_alert(&result.features[0], &Regexes::new()).unwrap();
+        assert_eq!(event.location.unwrap().poly, None);
+        let event = parse_alert(&result.features[1], &Regexes::new()).unwrap();
+        assert_eq!(event.location.unwrap().poly.unwrap().len(), 5);
+    }
+
+    #[test]
+    fn parse_should_skip_unsupported_alerts() {
+        let alerts = get_alerts();
+        assert!(parse_alert(&alerts[2], &Regexes::new()).is_none());
+
+        let alerts: Vec<&Alert> = alerts.iter().collect();
+        let vtecs: Vec<String> = parse(&alerts)
+            .into_iter()
+            .filter_map(|x| x.warning?.vtec)
+            .collect();
+        assert_eq!(vtecs, vec!["KTOP.TO.W.0009.2018", "KDMX.SV.W.0002.2018"]);
+    }
+}
@@ -1,6 +1,6 @@
 use crate::nws_regexes::Regexes;
-use crate::parser_util::{short_time_to_ticks, str_to_latlon};
-use domain::{Coordinates, Event, EventType, Location, Product, Warning};
+use crate::parser_util::{get_polygon, get_vtec_id, short_time_to_ticks};
+use domain::{Event, EventType, Location, Product, Warning};
 use util;
 use util::safe_result;
 
@@ -10,7 +10,6 @@ use util::safe_result;
 pub fn parse(product: &Product) -> Option<Event> {
     let regexes = Regexes::new();
     let text = &product.product_text;
-    let poly_captures = regexes.poly.captures_iter(&text);
     let valid_range = regexes.valid.captures(&text).unwrap();
     let issued_for = regexes.warning_for.captures(&text).unwrap();
     let issued_for = issued_for[1]
@@ -21,15 +20,6 @@ pub fn parse(product: &Product) -> Option<Event> {
     let if_len = issued_for.len() - 1;
     let issued_for = issued_for[..if_len].to_string();
 
-    let mut poly: Vec<Coordinates> = vec![];
-    for latlon in poly_captures {
-        let splits: Vec<&str> = latlon[0].split(' ').collect();
-        poly.push(Coordinates {
-            lat: str_to_latlon(splits[0], false),
-            lon: str_to_latlon(splits[1], true),
-        });
-    }
-
     let wfo = product.issuing_office.to_string();
     let valid_ts = Some(safe_result!(short_time_to_ticks(&valid_range[1])));
     let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
@@ -39,7 +29,7 @@ pub fn parse(product: &Product) -> Option<Event> {
     let location = Some(Location {
         wfo: Some(wfo),
         point: None,
-        poly: Some(poly),
+        poly: Some(get_polygon(text, &regexes)),
         county: None,
     });
 
@@ -53,24 +43,38 @@ pub fn parse(product: &Product) -> Option<Event> {
         motion_kt: None,
         source: None,
         issued_for,
-        time: "N/A".to_string(),
+        time: None,
+        vtec: get_vtec_id(text, &regexes),
+        hail_size: None,
+        wind_gust: None,
+        waterspout: None,
+        mpd_source_id: None,
+        storm_points: vec![],
+        severity: None,
     });
 
     let event = Event {
+        afd: None,
         event_ts,
         event_type: EventType::NwsFfw,
         expires_ts,
         ext_uri: None,
+        fire: None,
+        fire_outlook: None,
         ingest_ts: 0,
         location,
         md: None,
         outlook: None,
         report: None,
+        source_id: None,
+        survey: None,
         text: Some(text.to_string()),
         title,
+        tropical: None,
         valid_ts,
         warning,
         watch: None,
+        winter: None,
     };
 
     Some(event)
@@ -86,7 +90,7 @@ mod tests {
         let product = get_product_from_file("../data/products/ffw");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1525225920000000,"event_type":"NwsFfw","expires_ts":1525239900000000,"ext_uri":null,"ingest_ts":0,"location":{"wfo":"KGID","point":null,"poly":[{"lat":39.35,"lon":-98.47},{"lat":39.53,"lon":-97.93},{"lat":39.22,"lon":-97.93},{"lat":39.22,"lon":-98.49},{"lat":39.13,"lon":-98.49},{"lat":39.13,"lon":-98.89}],"county":null},"md":null,"outlook":null,"report":null,"text":"\n500 \nWGUS53 KGID 020152\nFFWGID\nKSC123-141-020545-\n/O.NEW.KGID.FF.W.0001.180502T0152Z-180502T0545Z/\n/00000.0.ER.000000T0000Z.000000T0000Z.000000T0000Z.OO/\n\nBULLETIN - EAS ACTIVATION REQUESTED\nFlash Flood Warning\nNational Weather Service Hastings NE\n852 PM CDT TUE MAY 1 2018\n\nThe National Weather Service in Hastings has issued a\n\n* Flash Flood Warning for...\n  Mitchell County in north central Kansas...\n  Southeastern Osborne County in north central Kansas...\n\n* Until 1245 AM CDT\n\n* At 844 PM CDT, Doppler radar indicated thunderstorms producing\n  heavy rain across the warned area. Flash flooding is expected to \n  begin shortly. Three to five inches of rain have been estimated to \n  have already fallen for some areas, with potentially another \n  couple of inches of rain before ending Tuesday night.\n\n* Some locations that will experience flooding include...\n  Beloit, Tipton, Asherville, Simpson, Hunter and Victor and along \n  the Solomon River. \n\nLAT...LON 3935 9847 3953 9793 3922 9793 3922 9849\n      3913 9849 3913 9889\n\n$$\n\nHeinlein\n\n","title":"Flash Flood Warning (KGID)","valid_ts":1525225920000000,"warning":{"is_pds":false,"is_tor_emergency":null,"was_observed":null,"issued_for":"Mitchell County in north central Kansas, Southeastern Osborne County in north central Kansas","motion_deg":null,"motion_kt":null,"source":null,"time":"N/A"},"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1525225920000000,"event_type":"NwsFfw","expires_ts":1525239900000000,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KGID","point":null,"poly":[{"lat":39.35,"lon":-98.47},{"lat":39.53,"lon":-97.93},{"lat":39.22,"lon":-97.93},{"lat":39.22,"lon":-98.49},{"lat":39.13,"lon":-98.49},{"lat":39.13,"lon":-98.89}],"county":null},"md":null,"outlook":null,"report":null,"source_id":null,"survey":null,"text":"\n500 \nWGUS53 KGID 020152\nFFWGID\nKSC123-141-020545-\n/O.NEW.KGID.FF.W.0001.180502T0152Z-180502T0545Z/\n/00000.0.ER.000000T0000Z.000000T0000Z.000000T0000Z.OO/\n\nBULLETIN - EAS ACTIVATION REQUESTED\nFlash Flood Warning\nNational Weather Service Hastings NE\n852 PM CDT TUE MAY 1 2018\n\nThe National Weather Service in Hastings has issued a\n\n* Flash Flood Warning for...\n  Mitchell County in north central Kansas...\n  Southeastern Osborne County in north central Kansas...\n\n* Until 1245 AM CDT\n\n* At 844 PM CDT, Doppler radar indicated thunderstorms producing\n  heavy rain across the warned area. Flash flooding is expected to \n  begin shortly. Three to five inches of rain have been estimated to \n  have already fallen for some areas, with potentially another \n  couple of inches of rain before ending Tuesday night.\n\n* Some locations that will experience flooding include...\n  Beloit, Tipton, Asherville, Simpson, Hunter and Victor and along \n  the Solomon River. \n\nLAT...LON 3935 9847 3953 9793 3922 9793 3922 9849\n      3913 9849 3913 9889\n\n$$\n\nHeinlein\n\n","title":"Flash Flood Warning (KGID)","tropical":null,"valid_ts":1525225920000000,"warning":{"is_pds":false,"is_tor_emergency":null,"was_observed":null,"issued_for":"Mitchell County in north central Kansas, Southeastern Osborne County in north central Kansas","motion_deg":null,"motion_kt":null,"source":null,"time":null,"vtec":"KGID.FF.W.0001.2018","hail_size":null,"wind_gust":null,"waterspout":null,"mpd_source_id":null,"storm_points":[],"severity":null},"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 }
@@ -0,0 +1,159 @@
+use crate::nws_regexes::Regexes;
+use crate::parser_util::day_time_to_ticks;
+use domain::{Event, EventType, FireOutlook, FireOutlookDay, FireOutlookRisk, Product};
+use regex::Regex;
+use util::safe_result;
+
+const DAY_PATTERN: &str = r"Day (?P<day>1|2|3-8) Fire Weather Outlook";
+const SECTION_PATTERN: &str = r"(?m)^\.{3}(?P<header>[A-Z][a-z][^\n]*?)\.{3}\s*$";
+const SYNOPSIS_PATTERN: &str = r"\.{3}Synopsis\.{3}\s*(?P<text>[\s\S]+?)\n\n";
+
+lazy_static! {
+    static ref DAY_REGEX: Regex = Regex::new(DAY_PATTERN).expect("Unable to compile regex");
+    static ref SECTION_REGEX: Regex = Regex::new(SECTION_PATTERN).expect("Unable to compile regex");
+    static ref SYNOPSIS_REGEX: Regex =
+        Regex::new(SYNOPSIS_PATTERN).expect("Unable to compile regex");
+}
+
+/**
+ * Parses an SPC Fire Weather Outlook (FWD). Each area SPC outlooks gets its own section, ie.
+ * "...Critical -- Eastern New Mexico...", so the max risk is the highest category with a section.
+ * The all-caps headline is skipped, since it also says things like "NO CRITICAL AREAS".
+ */
+pub fn parse(product: &Product) -> Option<Event> {
+    let regexes = Regexes::new();
+    let text = &product.product_text;
+    let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
+
+    let day = match DAY_REGEX.captures(text) {
+        Some(day) => get_outlook_day(&day["day"]),
+        None => {
+            warn!("FWD is not a fire weather outlook: {}", text);
+            return None;
+        }
+    };
+
+    let (valid_ts, expires_ts) = match regexes.md_valid.captures(text) {
+        Some(valid) => (
+            day_time_to_ticks(&valid[1], event_ts).ok(),
+            day_time_to_ticks(&valid[2], event_ts).ok(),
+        ),
+        None => (None, None),
+    };
+
+    let headers: Vec<&str> = SECTION_REGEX
+        .captures_iter(text)
+        .filter_map(|x| x.name("header"))
+        .map(|x| x.as_str())
+        .collect();
+    let max_risk = get_max_risk(&headers);
+    let has_dry_thunderstorms = headers.iter().any(|x| x.contains("Dry Thunderstorm"));
+    let synopsis = SYNOPSIS_REGEX.captures(text).map(|x| {
+        x["text"]
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+    });
+
+    let title = match &max_risk {
+        Some(max_risk) => format!("SPC {:?} Fire Outlook: {:?}", day, max_risk),
+        None => format!("SPC {:?} Fire Outlook", day),
+    };
+
+    let fire_outlook = FireOutlook {
+        day,
+        max_risk,
+        has_dry_thunderstorms,
+        synopsis,
+    };
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type: EventType::NwsFwd,
+        expires_ts,
+        ext_uri: None,
+        fire: None,
+        fire_outlook: Some(fire_outlook),
+        ingest_ts: 0,
+        location: None,
+        md: None,
+        outlook: None,
+        report: None,
+        source_id: None,
+        survey: None,
+        text: Some(text.to_string()),
+        title,
+        tropical: None,
+        valid_ts,
+        warning: None,
+        watch: None,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+fn get_outlook_day(day: &str) -> FireOutlookDay {
+    match day {
+        "1" => FireOutlookDay::Day1,
+        "2" => FireOutlookDay::Day2,
+        _ => FireOutlookDay::Day38,
+    }
+}
+
+fn get_max_risk(headers: &[&str]) -> Option<FireOutlookRisk> {
+    if headers.iter().any(|x| x.contains("Extremely Critical")) {
+        Some(FireOutlookRisk::EXTM)
+    } else if headers.iter().any(|x| x.contains("Critical")) {
+        Some(FireOutlookRisk::CRIT)
+    } else if headers.iter().any(|x| x.contains("Elevated")) {
+        Some(FireOutlookRisk::ELEV)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::get_product_from_file;
+    use super::*;
+
+    #[test]
+    fn parse_day1_fire_outlook() {
+        let product = get_product_from_file("../data/products/fwd-day1");
+        let result = parse(&product).unwrap();
+        assert_eq!(result.event_type, EventType::NwsFwd);
+        assert_eq!(result.title, "SPC Day1 Fire Outlook: EXTM");
+        assert_eq!(result.valid_ts, Some(1555952400000000));
+        assert_eq!(result.expires_ts, Some(1556020800000000));
+
+        let serialized_result = serde_json::to_string(&result.fire_outlook).unwrap();
+        let expected = r#"{"day":"Day1","max_risk":"EXTM","has_dry_thunderstorms":false,"synopsis":"A strong mid-level trough will move across the Four Corners today, with a deepening surface low over southeast Colorado. Strong southwesterly flow will overspread much of New Mexico and far west Texas during the afternoon."}"#;
+        assert_eq!(expected, serialized_result);
+    }
+
+    #[test]
+    fn parse_should_skip_headline() {
+        let mut product = get_product_from_file("../data/products/fwd-day1");
+        let text = &product.product_text;
+        let end = text.find("...Extremely Critical").unwrap();
+        product.product_text = text[..end]
+            .replace("Day 1", "Day 3-8")
+            .replace("EXTREMELY CRITICAL", "NO CRITICAL AREAS");
+        let result = parse(&product).unwrap();
+        assert_eq!(result.title, "SPC Day38 Fire Outlook");
+        assert_eq!(result.fire_outlook.unwrap().max_risk, None);
+    }
+
+    #[test]
+    fn get_max_risk_should_use_highest_section() {
+        let headers = vec!["Synopsis", "Elevated -- Great Basin", "Critical -- Utah"];
+        assert_eq!(get_max_risk(&headers), Some(FireOutlookRisk::CRIT));
+        let headers = vec![
+            "Synopsis",
+            "Isolated Dry Thunderstorms -- Northern California",
+        ];
+        assert_eq!(get_max_risk(&headers), None);
+    }
+}
@@ -0,0 +1,490 @@
+use domain::Coordinates;
+
+/**
+ * Anchor points SPC uses to describe watch areas, ie. "40 miles south southwest of Terre Haute IN".
+ * Coordinates are close enough for drawing a watch outline, not for anything finer.
+ */
+const ANCHORS: &[(&str, &str, f32, f32)] = &[
+    ("Anniston", "AL", 33.66, -85.83),
+    ("Birmingham", "AL", 33.52, -86.80),
+    ("Dothan", "AL", 31.22, -85.39),
+    ("Huntsville", "AL", 34.73, -86.59),
+    ("Mobile", "AL", 30.69, -88.04),
+    ("Montgomery", "AL", 32.37, -86.30),
+    ("Muscle Shoals", "AL", 34.74, -87.67),
+    ("Tuscaloosa", "AL", 33.21, -87.57),
+    ("Batesville", "AR", 35.77, -91.64),
+    ("El Dorado", "AR", 33.21, -92.67),
+    ("Fayetteville", "AR", 36.06, -94.16),
+    ("Flippin", "AR", 36.28, -92.60),
+    ("Fort Smith", "AR", 35.39, -94.40),
+    ("Harrison", "AR", 36.23, -93.11),
+    ("Hot Springs", "AR", 34.50, -93.06),
+    ("Jonesboro", "AR", 35.84, -90.70),
+    ("Little Rock", "AR", 34.75, -92.29),
+    ("Pine Bluff", "AR", 34.23, -92.00),
+    ("Russellville", "AR", 35.28, -93.13),
+    ("Texarkana", "AR", 33.44, -94.04),
+    ("Akron", "CO", 40.16, -103.21),
+    ("Colorado Springs", "CO", 38.83, -104.82),
+    ("Denver", "CO", 39.74, -104.99),
+    ("Fort Collins", "CO", 40.59, -105.08),
+    ("La Junta", "CO", 37.98, -103.54),
+    ("Lamar", "CO", 38.09, -102.62),
+    ("Limon", "CO", 39.26, -103.69),
+    ("Pueblo", "CO", 38.25, -104.61),
+    ("Trinidad", "CO", 37.17, -104.50),
+    ("Washington", "DC", 38.90, -77.04),
+    ("Dover", "DE", 39.16, -75.52),
+    ("Apalachicola", "FL", 29.73, -84.98),
+    ("Crestview", "FL", 30.76, -86.57),
+    ("Cross City", "FL", 29.63, -83.13),
+    ("Daytona Beach", "FL", 29.21, -81.02),
+    ("Fort Myers", "FL", 26.64, -81.87),
+    ("Gainesville", "FL", 29.65, -82.32),
+    ("Jacksonville", "FL", 30.33, -81.66),
+    ("Miami", "FL", 25.76, -80.19),
+    ("Orlando", "FL", 28.54, -81.38),
+    ("Panama City", "FL", 30.16, -85.66),
+    ("Pensacola", "FL", 30.42, -87.22),
+    ("Tallahassee", "FL", 30.44, -84.28),
+    ("Tampa", "FL", 27.95, -82.46),
+    ("Vero Beach", "FL", 27.64, -80.40),
+    ("Albany", "GA", 31.58, -84.16),
+    ("Alma", "GA", 31.54, -82.46),
+    ("Athens", "GA", 33.96, -83.38),
+    ("Atlanta", "GA", 33.75, -84.39),
+    ("Augusta", "GA", 33.47, -81.97),
+    ("Columbus", "GA", 32.46, -84.99),
+    ("Macon", "GA", 32.84, -83.63),
+    ("Rome", "GA", 34.26, -85.16),
+    ("Savannah", "GA", 32.08, -81.09),
+    ("Valdosta", "GA", 30.83, -83.28),
+    ("Waycross", "GA", 31.21, -82.35),
+    ("Ames", "IA", 42.03, -93.62),
+    ("Burlington", "IA", 40.81, -91.11),
+    ("Carroll", "IA", 42.07, -94.87),
+    ("Cedar Rapids", "IA", 41.98, -91.67),
+    ("Davenport", "IA", 41.52, -90.58),
+    ("Des Moines", "IA", 41.59, -93.62),
+    ("Dubuque", "IA", 42.50, -90.66),
+    ("Estherville", "IA", 43.40, -94.83),
+    ("Fort Dodge", "IA", 42.50, -94.17),
+    ("Lamoni", "IA", 40.62, -93.93),
+    ("Mason City", "IA", 43.15, -93.20),
+    ("Ottumwa", "IA", 41.02, -92.41),
+    ("Sioux City", "IA", 42.50, -96.40),
+    ("Spencer", "IA", 43.14, -95.14),
+    ("Waterloo", "IA", 42.49, -92.34),
+    ("Bloomington", "IL", 40.48, -88.99),
+    ("Carbondale", "IL", 37.73, -89.22),
+    ("Champaign", "IL", 40.12, -88.24),
+    ("Chicago", "IL", 41.88, -87.63),
+    ("Danville", "IL", 40.12, -87.63),
+    ("Decatur", "IL", 39.84, -88.95),
+    ("Effingham", "IL", 39.12, -88.54),
+    ("Lawrenceville", "IL", 38.73, -87.68),
+    ("Marion", "IL", 37.73, -88.93),
+    ("Moline", "IL", 41.51, -90.52),
+    ("Mount Vernon", "IL", 38.32, -88.90),
+    ("Peoria", "IL", 40.69, -89.59),
+    ("Quincy", "IL", 39.94, -91.41),
+    ("Rockford", "IL", 42.27, -89.09),
+    ("Salem", "IL", 38.63, -88.95),
+    ("Springfield", "IL", 39.78, -89.65),
+    ("Bloomington", "IN", 39.17, -86.53),
+    ("Evansville", "IN", 37.97, -87.57),
+    ("Fort Wayne", "IN", 41.08, -85.14),
+    ("Indianapolis", "IN", 39.77, -86.16),
+    ("Lafayette", "IN", 40.42, -86.88),
+    ("Muncie", "IN", 40.19, -85.39),
+    ("South Bend", "IN", 41.68, -86.25),
+    ("Terre Haute", "IN", 39.47, -87.41),
+    ("Chanute", "KS", 37.68, -95.46),
+    ("Coffeyville", "KS", 37.04, -95.62),
+    ("Concordia", "KS", 39.57, -97.66),
+    ("Dodge City", "KS", 37.75, -100.02),
+    ("Elkhart", "KS", 37.01, -101.89),
+    ("Emporia", "KS", 38.40, -96.18),
+    ("Fort Scott", "KS", 37.84, -94.71),
+    ("Garden City", "KS", 37.97, -100.87),
+    ("Goodland", "KS", 39.35, -101.71),
+    ("Great Bend", "KS", 38.36, -98.76),
+    ("Hays", "KS", 38.88, -99.33),
+    ("Hill City", "KS", 39.36, -99.84),
+    ("Hutchinson", "KS", 38.06, -97.93),
+    ("Lawrence", "KS", 38.97, -95.24),
+    ("Liberal", "KS", 37.04, -100.92),
+    ("Manhattan", "KS", 39.18, -96.57),
+    ("Medicine Lodge", "KS", 37.28, -98.58),
+    ("Pratt", "KS", 37.64, -98.74),
+    ("Russell", "KS", 38.90, -98.86),
+    ("Salina", "KS", 38.84, -97.61),
+    ("Scott City", "KS", 38.48, -100.91),
+    ("Topeka", "KS", 39.05, -95.68),
+    ("Wichita", "KS", 37.69, -97.34),
+    ("Winfield", "KS", 37.24, -96.99),
+    ("Bowling Green", "KY", 36.99, -86.44),
+    ("Covington", "KY", 39.08, -84.51),
+    ("Frankfort", "KY", 38.20, -84.87),
+    ("Hopkinsville", "KY", 36.87, -87.49),
+    ("Jackson", "KY", 37.55, -83.38),
+    ("Lexington", "KY", 38.04, -84.50),
+    ("London", "KY", 37.13, -84.08),
+    ("Louisville", "KY", 38.25, -85.76),
+    ("Owensboro", "KY", 37.77, -87.11),
+    ("Paducah", "KY", 37.08, -88.60),
+    ("Somerset", "KY", 37.09, -84.60),
+    ("Alexandria", "LA", 31.31, -92.45),
+    ("Baton Rouge", "LA", 30.45, -91.19),
+    ("Fort Polk", "LA", 31.05, -93.19),
+    ("Houma", "LA", 29.60, -90.72),
+    ("Lafayette", "LA", 30.22, -92.02),
+    ("Lake Charles", "LA", 30.23, -93.22),
+    ("Monroe", "LA", 32.51, -92.12),
+    ("Natchitoches", "LA", 31.76, -93.09),
+    ("New Orleans", "LA", 29.95, -90.07),
+    ("Shreveport", "LA", 32.53, -93.75),
+    ("Boston", "MA", 42.36, -71.06),
+    ("Worcester", "MA", 42.26, -71.80),
+    ("Baltimore", "MD", 39.29, -76.61),
+    ("Salisbury", "MD", 38.36, -75.60),
+    ("Bangor", "ME", 44.80, -68.78),
+    ("Portland", "ME", 43.66, -70.26),
+    ("Alpena", "MI", 45.06, -83.43),
+    ("Benton Harbor", "MI", 42.12, -86.45),
+    ("Detroit", "MI", 42.33, -83.05),
+    ("Flint", "MI", 43.01, -83.69),
+    ("Grand Rapids", "MI", 42.96, -85.67),
+    ("Houghton Lake", "MI", 44.31, -84.76),
+    ("Jackson", "MI", 42.25, -84.40),
+    ("Kalamazoo", "MI", 42.29, -85.59),
+    ("Lansing", "MI", 42.73, -84.56),
+    ("Marquette", "MI", 46.54, -87.40),
+    ("Muskegon", "MI", 43.23, -86.25),
+    ("Saginaw", "MI", 43.42, -83.95),
+    ("Traverse City", "MI", 44.76, -85.62),
+    ("Albert Lea", "MN", 43.65, -93.37),
+    ("Alexandria", "MN", 45.89, -95.38),
+    ("Bemidji", "MN", 47.47, -94.88),
+    ("Brainerd", "MN", 46.36, -94.20),
+    ("Duluth", "MN", 46.79, -92.10),
+    ("Fairmont", "MN", 43.65, -94.46),
+    ("Hibbing", "MN", 47.43, -92.94),
+    ("International Falls", "MN", 48.60, -93.41),
+    ("Mankato", "MN", 44.16, -94.00),
+    ("Minneapolis", "MN", 44.98, -93.27),
+    ("Redwood Falls", "MN", 44.54, -95.12),
+    ("Rochester", "MN", 44.02, -92.47),
+    ("St. Cloud", "MN", 45.56, -94.16),
+    ("Worthington", "MN", 43.62, -95.60),
+    ("Butler", "MO", 38.26, -94.33),
+    ("Cape Girardeau", "MO", 37.31, -89.52),
+    ("Chillicothe", "MO", 39.80, -93.55),
+    ("Columbia", "MO", 38.95, -92.33),
+    ("Farmington", "MO", 37.78, -90.42),
+    ("Fort Leonard Wood", "MO", 37.71, -92.15),
+    ("Jefferson City", "MO", 38.58, -92.17),
+    ("Joplin", "MO", 37.08, -94.51),
+    ("Kansas City", "MO", 39.10, -94.58),
+    ("Kirksville", "MO", 40.19, -92.58),
+    ("Malden", "MO", 36.56, -89.97),
+    ("Poplar Bluff", "MO", 36.76, -90.39),
+    ("Sedalia", "MO", 38.70, -93.23),
+    ("Springfield", "MO", 37.21, -93.29),
+    ("St. Joseph", "MO", 39.77, -94.85),
+    ("St. Louis", "MO", 38.63, -90.20),
+    ("Vichy", "MO", 38.13, -91.77),
+    ("West Plains", "MO", 36.73, -91.85),
+    ("Columbus", "MS", 33.50, -88.43),
+    ("Greenville", "MS", 33.41, -91.06),
+    ("Greenwood", "MS", 33.52, -90.18),
+    ("Gulfport", "MS", 30.37, -89.09),
+    ("Hattiesburg", "MS", 31.33, -89.29),
+    ("Jackson", "MS", 32.30, -90.18),
+    ("Laurel", "MS", 31.69, -89.13),
+    ("McComb", "MS", 31.24, -90.45),
+    ("Meridian", "MS", 32.36, -88.70),
+    ("Natchez", "MS", 31.56, -91.40),
+    ("Oxford", "MS", 34.37, -89.52),
+    ("Tupelo", "MS", 34.26, -88.70),
+    ("Baker", "MT", 46.37, -104.28),
+    ("Billings", "MT", 45.78, -108.50),
+    ("Broadus", "MT", 45.44, -105.41),
+    ("Glasgow", "MT", 48.20, -106.64),
+    ("Glendive", "MT", 47.11, -104.71),
+    ("Great Falls", "MT", 47.50, -111.30),
+    ("Havre", "MT", 48.55, -109.68),
+    ("Jordan", "MT", 47.32, -106.91),
+    ("Lewistown", "MT", 47.06, -109.43),
+    ("Miles City", "MT", 46.41, -105.84),
+    ("Sidney", "MT", 47.72, -104.16),
+    ("Wolf Point", "MT", 48.09, -105.64),
+    ("Asheville", "NC", 35.60, -82.55),
+    ("Cape Hatteras", "NC", 35.25, -75.53),
+    ("Charlotte", "NC", 35.23, -80.84),
+    ("Elizabeth City", "NC", 36.29, -76.25),
+    ("Fayetteville", "NC", 35.05, -78.88),
+    ("Greensboro", "NC", 36.07, -79.79),
+    ("Greenville", "NC", 35.61, -77.37),
+    ("Hickory", "NC", 35.73, -81.34),
+    ("New Bern", "NC", 35.11, -77.04),
+    ("Raleigh", "NC", 35.78, -78.64),
+    ("Rocky Mount", "NC", 35.94, -77.79),
+    ("Wilmington", "NC", 34.23, -77.94),
+    ("Bismarck", "ND", 46.81, -100.78),
+    ("Devils Lake", "ND", 48.11, -98.86),
+    ("Dickinson", "ND", 46.88, -102.79),
+    ("Fargo", "ND", 46.88, -96.79),
+    ("Grand Forks", "ND", 47.93, -97.03),
+    ("Jamestown", "ND", 46.91, -98.71),
+    ("Minot", "ND", 48.23, -101.30),
+    ("Williston", "ND", 48.15, -103.62),
+    ("Ainsworth", "NE", 42.55, -99.86),
+    ("Alliance", "NE", 42.10, -102.87),
+    ("Beatrice", "NE", 40.27, -96.75),
+    ("Broken Bow", "NE", 41.40, -99.64),
+    ("Chadron", "NE", 42.83, -103.00),
+    ("Columbus", "NE", 41.43, -97.37),
+    ("Falls City", "NE", 40.06, -95.60),
+    ("Grand Island", "NE", 40.92, -98.34),
+    ("Hastings", "NE", 40.59, -98.39),
+    ("Imperial", "NE", 40.52, -101.64),
+    ("Kearney", "NE", 40.70, -99.08),
+    ("Lincoln", "NE", 40.81, -96.68),
+    ("McCook", "NE", 40.20, -100.63),
+    ("Mullen", "NE", 42.04, -101.04),
+    ("Norfolk", "NE", 42.03, -97.42),
+    ("North Platte", "NE", 41.12, -100.77),
+    ("Omaha", "NE", 41.26, -95.94),
+    ("Ord", "NE", 41.60, -98.93),
+    ("Scottsbluff", "NE", 41.87, -103.67),
+    ("Sidney", "NE", 41.14, -102.98),
+    ("Valentine", "NE", 42.87, -100.55),
+    ("Concord", "NH", 43.21, -71.54),
+    ("Atlantic City", "NJ", 39.36, -74.42),
+    ("Newark", "NJ", 40.74, -74.17),
+    ("Albuquerque", "NM", 35.08, -106.65),
+    ("Carlsbad", "NM", 32.42, -104.23),
+    ("Clayton", "NM", 36.45, -103.18),
+    ("Clovis", "NM", 34.40, -103.21),
+    ("Hobbs", "NM", 32.70, -103.14),
+    ("Raton", "NM", 36.90, -104.44),
+    ("Roswell", "NM", 33.39, -104.52),
+    ("Tucumcari", "NM", 35.17, -103.72),
+    ("Albany", "NY", 42.65, -73.76),
+    ("Binghamton", "NY", 42.10, -75.91),
+    ("Buffalo", "NY", 42.89, -78.88),
+    ("Elmira", "NY", 42.09, -76.81),
+    ("Glens Falls", "NY", 43.31, -73.64),
+    ("New York", "NY", 40.71, -74.01),
+    ("Poughkeepsie", "NY", 41.70, -73.92),
+    ("Rochester", "NY", 43.16, -77.61),
+    ("Syracuse", "NY", 43.05, -76.15),
+    ("Watertown", "NY", 43.97, -75.91),
+    ("Akron", "OH", 41.08, -81.52),
+    ("Cincinnati", "OH", 39.10, -84.51),
+    ("Cleveland", "OH", 41.50, -81.69),
+    ("Columbus", "OH", 39.96, -83.00),
+    ("Dayton", "OH", 39.76, -84.19),
+    ("Findlay", "OH", 41.04, -83.65),
+    ("Lima", "OH", 40.74, -84.11),
+    ("Mansfield", "OH", 40.76, -82.52),
+    ("Toledo", "OH", 41.65, -83.54),
+    ("Wilmington", "OH", 39.45, -83.83),
+    ("Youngstown", "OH", 41.10, -80.65),
+    ("Zanesville", "OH", 39.94, -82.01),
+    ("Ada", "OK", 34.77, -96.68),
+    ("Altus", "OK", 34.64, -99.33),
+    ("Ardmore", "OK", 34.17, -97.14),
+    ("Bartlesville", "OK", 36.75, -95.98),
+    ("Boise City", "OK", 36.73, -102.51),
+    ("Clinton", "OK", 35.52, -98.97),
+    ("Durant", "OK", 33.99, -96.37),
+    ("Enid", "OK", 36.40, -97.88),
+    ("Gage", "OK", 36.30, -99.77),
+    ("Guymon", "OK", 36.68, -101.48),
+    ("Hobart", "OK", 35.03, -99.09),
+    ("Hugo", "OK", 34.01, -95.51),
+    ("Lawton", "OK", 34.60, -98.39),
+    ("McAlester", "OK", 34.93, -95.77),
+    ("Muskogee", "OK", 35.75, -95.37),
+    ("Oklahoma City", "OK", 35.47, -97.52),
+    ("Ponca City", "OK", 36.71, -97.09),
+    ("Stillwater", "OK", 36.12, -97.06),
+    ("Tulsa", "OK", 36.15, -95.99),
+    ("Woodward", "OK", 36.43, -99.39),
+    ("Allentown", "PA", 40.60, -75.49),
+    ("Altoona", "PA", 40.52, -78.39),
+    ("Bradford", "PA", 41.96, -78.64),
+    ("Du Bois", "PA", 41.12, -78.76),
+    ("Erie", "PA", 42.13, -80.09),
+    ("Harrisburg", "PA", 40.27, -76.88),
+    ("Johnstown", "PA", 40.33, -78.92),
+    ("Philadelphia", "PA", 39.95, -75.17),
+    ("Pittsburgh", "PA", 40.44, -80.00),
+    ("Scranton", "PA", 41.41, -75.66),
+    ("State College", "PA", 40.79, -77.86),
+    ("Williamsport", "PA", 41.24, -77.00),
+    ("Providence", "RI", 41.82, -71.41),
+    ("Anderson", "SC", 34.50, -82.65),
+    ("Charleston", "SC", 32.78, -79.93),
+    ("Columbia", "SC", 34.00, -81.03),
+    ("Florence", "SC", 34.20, -79.76),
+    ("Greenville", "SC", 34.85, -82.40),
+    ("Myrtle Beach", "SC", 33.69, -78.89),
+    ("Orangeburg", "SC", 33.49, -80.86),
+    ("Spartanburg", "SC", 34.95, -81.93),
+    ("Aberdeen", "SD", 45.46, -98.49),
+    ("Brookings", "SD", 44.31, -96.80),
+    ("Buffalo", "SD", 45.58, -103.55),
+    ("Chamberlain", "SD", 43.81, -99.33),
+    ("Huron", "SD", 44.36, -98.21),
+    ("Mitchell", "SD", 43.71, -98.03),
+    ("Mobridge", "SD", 45.54, -100.43),
+    ("Philip", "SD", 44.04, -101.67),
+    ("Pierre", "SD", 44.37, -100.35),
+    ("Rapid City", "SD", 44.08, -103.23),
+    ("Sioux Falls", "SD", 43.54, -96.73),
+    ("Watertown", "SD", 44.90, -97.12),
+    ("Winner", "SD", 43.38, -99.86),
+    ("Yankton", "SD", 42.87, -97.40),
+    ("Bristol", "TN", 36.60, -82.19),
+    ("Chattanooga", "TN", 35.05, -85.31),
+    ("Clarksville", "TN", 36.53, -87.36),
+    ("Columbia", "TN", 35.62, -87.04),
+    ("Crossville", "TN", 35.95, -85.03),
+    ("Dyersburg", "TN", 36.03, -89.39),
+    ("Jackson", "TN", 35.61, -88.81),
+    ("Knoxville", "TN", 35.96, -83.92),
+    ("Memphis", "TN", 35.15, -90.05),
+    ("Nashville", "TN", 36.16, -86.78),
+    ("Tullahoma", "TN", 35.36, -86.21),
+    ("Union City", "TN", 36.42, -89.06),
+    ("Abilene", "TX", 32.45, -99.73),
+    ("Alice", "TX", 27.75, -98.07),
+    ("Amarillo", "TX", 35.22, -101.83),
+    ("Austin", "TX", 30.27, -97.74),
+    ("Beaumont", "TX", 30.08, -94.10),
+    ("Big Spring", "TX", 32.25, -101.48),
+    ("Borger", "TX", 35.67, -101.40),
+    ("Brownsville", "TX", 25.90, -97.50),
+    ("Brownwood", "TX", 31.71, -98.99),
+    ("Childress", "TX", 34.43, -100.20),
+    ("College Station", "TX", 30.63, -96.33),
+    ("Corpus Christi", "TX", 27.80, -97.40),
+    ("Cotulla", "TX", 28.44, -99.23),
+    ("Dalhart", "TX", 36.06, -102.52),
+    ("Dallas", "TX", 32.78, -96.80),
+    ("Del Rio", "TX", 29.36, -100.90),
+    ("El Paso", "TX", 31.76, -106.49),
+    ("Fort Stockton", "TX", 30.89, -102.88),
+    ("Fort Worth", "TX", 32.76, -97.33),
+    ("Gainesville", "TX", 33.63, -97.13),
+    ("Galveston", "TX", 29.30, -94.80),
+    ("Houston", "TX", 29.76, -95.37),
+    ("Junction", "TX", 30.49, -99.77),
+    ("Lamesa", "TX", 32.74, -101.95),
+    ("Laredo", "TX", 27.51, -99.51),
+    ("Longview", "TX", 32.50, -94.74),
+    ("Lubbock", "TX", 33.58, -101.85),
+    ("Lufkin", "TX", 31.34, -94.73),
+    ("Marfa", "TX", 30.31, -104.02),
+    ("McAllen", "TX", 26.20, -98.23),
+    ("Midland", "TX", 32.00, -102.08),
+    ("Mineral Wells", "TX", 32.81, -98.11),
+    ("Palestine", "TX", 31.76, -95.63),
+    ("Paris", "TX", 33.66, -95.56),
+    ("Plainview", "TX", 34.18, -101.71),
+    ("San Angelo", "TX", 31.46, -100.44),
+    ("San Antonio", "TX", 29.42, -98.49),
+    ("Sherman", "TX", 33.64, -96.61),
+    ("Temple", "TX", 31.10, -97.34),
+    ("Texarkana", "TX", 33.43, -94.05),
+    ("Tyler", "TX", 32.35, -95.30),
+    ("Victoria", "TX", 28.81, -97.00),
+    ("Waco", "TX", 31.55, -97.15),
+    ("Wichita Falls", "TX", 33.91, -98.49),
+    ("Blacksburg", "VA", 37.23, -80.41),
+    ("Charlottesville", "VA", 38.03, -78.48),
+    ("Danville", "VA", 36.59, -79.40),
+    ("Lynchburg", "VA", 37.41, -79.14),
+    ("Norfolk", "VA", 36.85, -76.29),
+    ("Richmond", "VA", 37.54, -77.44),
+    ("Roanoke", "VA", 37.27, -79.94),
+    ("Wallops Island", "VA", 37.94, -75.47),
+    ("Burlington", "VT", 44.48, -73.21),
+    ("Eau Claire", "WI", 44.81, -91.50),
+    ("Green Bay", "WI", 44.51, -88.02),
+    ("La Crosse", "WI", 43.80, -91.24),
+    ("Lone Rock", "WI", 43.18, -90.20),
+    ("Madison", "WI", 43.07, -89.40),
+    ("Milwaukee", "WI", 43.04, -87.91),
+    ("Park Falls", "WI", 45.93, -90.45),
+    ("Rhinelander", "WI", 45.64, -89.41),
+    ("Wausau", "WI", 44.96, -89.63),
+    ("Beckley", "WV", 37.78, -81.19),
+    ("Bluefield", "WV", 37.27, -81.22),
+    ("Charleston", "WV", 38.35, -81.63),
+    ("Clarksburg", "WV", 39.28, -80.34),
+    ("Elkins", "WV", 38.93, -79.85),
+    ("Huntington", "WV", 38.42, -82.45),
+    ("Martinsburg", "WV", 39.46, -77.96),
+    ("Morgantown", "WV", 39.63, -79.96),
+    ("Parkersburg", "WV", 39.27, -81.56),
+    ("Casper", "WY", 42.87, -106.31),
+    ("Cheyenne", "WY", 41.14, -104.82),
+    ("Douglas", "WY", 42.76, -105.38),
+    ("Gillette", "WY", 44.29, -105.50),
+    ("Lusk", "WY", 42.76, -104.45),
+    ("Sheridan", "WY", 44.80, -106.96),
+    ("Torrington", "WY", 42.06, -104.18),
+];
+
+/**
+ * Looks up an anchor point by name and state. Names are matched ignoring case and periods, so
+ * "ST. LOUIS", "St Louis" and "Saint Louis" are the same place.
+ */
+pub fn get_anchor(name: &str, state: &str) -> Option<Coordinates> {
+    let name = normalize(name);
+    let state = state.to_uppercase();
+    ANCHORS
+        .iter()
+        .find(|(anchor_name, anchor_state, _, _)| {
+            *anchor_state == state && normalize(anchor_name) == name
+        })
+        .map(|(_, _, lat, lon)| Coordinates {
+            lat: *lat,
+            lon: *lon,
+        })
+}
+
+fn normalize(name: &str) -> String {
+    let name = name.to_uppercase().replace('.', "");
+    let name = name.split_whitespace().collect::<Vec<&str>>().join(" ");
+    if let Some(rest) = name.strip_prefix("SAINT ") {
+        format!("ST {}", rest)
+    } else if let Some(rest) = name.strip_prefix("FT ") {
+        format!("FORT {}", rest)
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_anchor_should_ignore_case_and_abbreviations() {
+        let expected = Some(Coordinates {
+            lat: 38.63,
+            lon: -90.2,
+        });
+        assert_eq!(get_anchor("St. Louis", "MO"), expected);
+        assert_eq!(get_anchor("SAINT LOUIS", "mo"), expected);
+        assert_eq!(get_anchor("Terre\n  Haute", "IN").unwrap().lat, 39.47);
+        assert_eq!(get_anchor("Ft Smith", "AR").unwrap().lon, -94.4);
+        assert_eq!(get_anchor("Columbus", "ZZ"), None);
+    }
+}
@@ -0,0 +1,126 @@
+use crate::nws_regexes::Regexes;
+use crate::parser_util::{day_time_to_ticks, get_storm_position};
+use domain::{Event, EventType, Location, Product, TropicalCyclone};
+use regex::{Regex, RegexBuilder};
+use util::safe_result;
+
+const HEADLINE_PATTERN: &str = r"\*\*(?P<headline>[^*]+?)\*\*";
+const INTENSITY_PATTERN: &str = r"storm\s+intensity\s+(?P<mph>\d+)\s+mph";
+const MOVEMENT_PATTERN: &str = r"movement\s+.*?(?P<deg>\d+)\s+degrees\s+at\s+(?P<mph>\d+)\s+mph";
+
+lazy_static! {
+    static ref HEADLINE_REGEX: Regex =
+        Regex::new(HEADLINE_PATTERN).expect("Unable to compile regex");
+    static ref INTENSITY_REGEX: Regex = RegexBuilder::new(INTENSITY_PATTERN)
+        .case_insensitive(true)
+        .build()
+        .expect("Unable to compile regex");
+    static ref MOVEMENT_REGEX: Regex = RegexBuilder::new(MOVEMENT_PATTERN)
+        .case_insensitive(true)
+        .build()
+        .expect("Unable to compile regex");
+}
+
+/**
+ * Parses an NWS Hurricane Local Statement (HLS). WFOs issue these with each NHC advisory, using
+ * the same storm ID and advisory number, and summarize the storm in the storm information bullet.
+ */
+pub fn parse(product: &Product) -> Option<Event> {
+    let regexes = Regexes::new();
+    let text = &product.product_text;
+    let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
+    let header = regexes.storm_header.captures(text)?;
+    let storm_id = match regexes.storm_id.captures(text) {
+        Some(storm_id) => storm_id["id"].to_string(),
+        None => {
+            warn!("HLS has no storm id: {}", text);
+            return None;
+        }
+    };
+
+    let expires_ts = match regexes.ugc_purge.captures(text) {
+        Some(purge) => Some(safe_result!(day_time_to_ticks(&purge["purge"], event_ts))),
+        None => None,
+    };
+
+    let wfo = product.issuing_office.to_string();
+    let name = header["name"].to_string();
+    let title = format!("{} Local Statement ({})", name, wfo);
+    let movement = MOVEMENT_REGEX.captures(text);
+    let headline = HEADLINE_REGEX.captures(text).map(|x| {
+        x["headline"]
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+    });
+
+    let tropical = TropicalCyclone {
+        storm_id,
+        name,
+        advisory: header["advisory"].to_uppercase(),
+        max_wind_mph: INTENSITY_REGEX
+            .captures(text)
+            .and_then(|x| x["mph"].parse().ok()),
+        pressure_mb: None,
+        motion_deg: movement.as_ref().and_then(|x| x["deg"].parse().ok()),
+        motion_mph: movement.as_ref().and_then(|x| x["mph"].parse().ok()),
+        headline,
+    };
+
+    let location = Some(Location {
+        wfo: Some(wfo),
+        point: get_storm_position(text, &regexes),
+        poly: None,
+        county: None,
+    });
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type: EventType::NwsHls,
+        expires_ts,
+        ext_uri: None,
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0,
+        location,
+        md: None,
+        outlook: None,
+        report: None,
+        source_id: None,
+        survey: None,
+        text: Some(text.to_string()),
+        title,
+        tropical: Some(tropical),
+        valid_ts: Some(event_ts),
+        warning: None,
+        watch: None,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::get_product_from_file;
+    use super::*;
+
+    #[test]
+    fn parse_hurricane_local_statement() {
+        let product = get_product_from_file("../data/products/hls-hurricane");
+        let result = parse(&product).unwrap();
+        assert_eq!(result.event_type, EventType::NwsHls);
+        assert_eq!(result.title, "Hurricane Ian Local Statement (KTBW)");
+        assert_eq!(result.expires_ts, Some(1664406900000000));
+
+        let serialized_result = serde_json::to_string(&result.location).unwrap();
+        let expected =
+            r#"{"wfo":"KTBW","point":{"lat":26.7,"lon":-82.2},"poly":null,"county":null}"#;
+        assert_eq!(expected, serialized_result);
+
+        let serialized_result = serde_json::to_string(&result.tropical).unwrap();
+        let expected = r#"{"storm_id":"AL092022","name":"Hurricane Ian","advisory":"23","max_wind_mph":155,"pressure_mb":null,"motion_deg":20,"motion_mph":9,"headline":"Extremely Dangerous Hurricane Ian Making Landfall Near Punta Gorda"}"#;
+        assert_eq!(expected, serialized_result);
+    }
+}
@@ -5,17 +5,34 @@ extern crate log;
 #[macro_use]
 extern crate serde_derive;
 
+pub mod alerts_parser;
+pub mod mping_parser;
 pub mod nws_parser;
+pub mod sn_api_parser;
 pub mod sn_parser;
+pub mod spc_reports_parser;
 
 mod afd_parser;
 mod ffw_parser;
+mod fwd_parser;
+mod gazetteer;
+mod hls_parser;
 mod lsr_parser;
+mod mpd_parser;
 mod nws_regexes;
 mod parser_util;
+mod pns_parser;
+mod rfw_parser;
 mod sel_parser;
+mod smw_parser;
+mod sps_parser;
 mod svr_parser;
 mod svs_parser;
 mod swo_parser;
+mod tcp_parser;
 mod test_util;
 mod tor_parser;
+mod wsw_parser;
+mod wwp_parser;
+
+pub use parser_util::hour_min_to_ticks;
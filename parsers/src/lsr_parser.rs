@@ -82,6 +82,7 @@ pub fn parse(product: &Product) -> Option<Event> {
         hazard,
         magnitude,
         report_ts: Some(report_ts),
+        lsr_source_id: None,
         reporter,
         units,
         was_measured,
@@ -254,7 +255,7 @@ mod tests {
         let product = get_product_from_file("../data/products/lsr-tornado");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1522524900000000,"event_type":"NwsLsr","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":{"wfo":"KMFL","point":{"lat":26.8,"lon":-80.64},"poly":null,"county":"PALM BEACH"},"md":null,"outlook":null,"report":{"reporter":"TRAINED SPOTTER","hazard":"Tornado","magnitude":null,"units":null,"was_measured":null,"report_ts":1522522800000000},"text":"\n158 \nNWUS52 KMFL 311935\nLSRMFL\n\nPRELIMINARY LOCAL STORM REPORT\nNATIONAL WEATHER SERVICE MIAMI FL\n335 PM EDT SAT MAR 31 2018\n\n..TIME...   ...EVENT...      ...CITY LOCATION...     ...LAT.LON...\n..DATE...   ....MAG....      ..COUNTY LOCATION..ST.. ...SOURCE....\n            ..REMARKS..\n\n0300 PM     TORNADO          2 SE PAHOKEE            26.80N  80.64W\n03/31/2018                   PALM BEACH         FL   TRAINED SPOTTER \n\n            TRAINED SKYWARN SPOTTER OBSERVED FROM PAHOKEE A FUNNEL \n            CLOUD APPROXIMATELY 3 MILES SOUTHEAST OF PAHOKEE, \n            PARTIALLY RAIN-WRAPPED AND NEARLY STATIONARY. THE FUNNEL \n            EXTENDED TO NEARLY HALFWAY TO THE GROUND BEFORE LIFTING. \n            LOCATION RADAR-ESTIMATED/ADJUSTED. VIDEO RECEIVED OF \n            FUNNEL REACHING THE GROUND WITH DUST BEING KICKED UP. \n            RECLASSIFIED AS A TORNADO. \n\n\n&&\nEVENT...FATALITIES...INJURIES...REMARKS\n\nEVENT NUMBER MFL1800020\n\n$$\n\nSI\n\n\n\n","title":"Report:  Tornado (KMFL)","valid_ts":null,"warning":null,"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1522524900000000,"event_type":"NwsLsr","expires_ts":null,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KMFL","point":{"lat":26.8,"lon":-80.64},"poly":null,"county":"PALM BEACH"},"md":null,"outlook":null,"report":{"reporter":"TRAINED SPOTTER","hazard":"Tornado","magnitude":null,"units":null,"was_measured":null,"report_ts":1522522800000000,"lsr_source_id":null},"source_id":null,"survey":null,"text":"\n158 \nNWUS52 KMFL 311935\nLSRMFL\n\nPRELIMINARY LOCAL STORM REPORT\nNATIONAL WEATHER SERVICE MIAMI FL\n335 PM EDT SAT MAR 31 2018\n\n..TIME...   ...EVENT...      ...CITY LOCATION...     ...LAT.LON...\n..DATE...   ....MAG....      ..COUNTY LOCATION..ST.. ...SOURCE....\n            ..REMARKS..\n\n0300 PM     TORNADO          2 SE PAHOKEE            26.80N  80.64W\n03/31/2018                   PALM BEACH         FL   TRAINED SPOTTER \n\n            TRAINED SKYWARN SPOTTER OBSERVED FROM PAHOKEE A FUNNEL \n            CLOUD APPROXIMATELY 3 MILES SOUTHEAST OF PAHOKEE, \n            PARTIALLY RAIN-WRAPPED AND NEARLY STATIONARY. THE FUNNEL \n            EXTENDED TO NEARLY HALFWAY TO THE GROUND BEFORE LIFTING. \n            LOCATION RADAR-ESTIMATED/ADJUSTED. VIDEO RECEIVED OF \n            FUNNEL REACHING THE GROUND WITH DUST BEING KICKED UP. \n            RECLASSIFIED AS A TORNADO. \n\n\n&&\nEVENT...FATALITIES...INJURIES...REMARKS\n\nEVENT NUMBER MFL1800020\n\n$$\n\nSI\n\n\n\n","title":"Report:  Tornado (KMFL)","tropical":null,"valid_ts":null,"warning":null,"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -270,7 +271,7 @@ mod tests {
         let product = get_product_from_file("../data/products/lsr-tstm-wind");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1555316100000000,"event_type":"NwsLsr","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":{"wfo":"KMHX","point":{"lat":35.07,"lon":-77.04},"poly":null,"county":"CRAVEN"},"md":null,"outlook":null,"report":{"reporter":"ASOS","hazard":"Wind","magnitude":61.0,"units":"Mph","was_measured":true,"report_ts":1555315080000000},"text":"\n000\nNWUS52 KMHX 150815\nLSRMHX\n\nPRELIMINARY LOCAL STORM REPORT\nNATIONAL WEATHER SERVICE NEWPORT/MOREHEAD CITY NC\n415 AM EDT MON APR 15 2019\n\n..TIME...   ...EVENT...      ...CITY LOCATION...     ...LAT.LON...\n..DATE...   ....MAG....      ..COUNTY LOCATION..ST.. ...SOURCE....\n            ..REMARKS..\n\n0358 AM     TSTM WND GST     COASTAL CAROLINA REGION 35.07N 77.04W\n04/15/2019  M61 MPH          CRAVEN             NC   ASOS             \n\n            NEW BERN/CRAVEN COUNTY ASOS (EWN) REPORTS \n            GUST OF 61 MPH. \n\n\n&&\n\n$$\n\nML\n\n","title":"Report:  61mph Wind (KMHX)","valid_ts":null,"warning":null,"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1555316100000000,"event_type":"NwsLsr","expires_ts":null,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KMHX","point":{"lat":35.07,"lon":-77.04},"poly":null,"county":"CRAVEN"},"md":null,"outlook":null,"report":{"reporter":"ASOS","hazard":"Wind","magnitude":61.0,"units":"Mph","was_measured":true,"report_ts":1555315080000000,"lsr_source_id":null},"source_id":null,"survey":null,"text":"\n000\nNWUS52 KMHX 150815\nLSRMHX\n\nPRELIMINARY LOCAL STORM REPORT\nNATIONAL WEATHER SERVICE NEWPORT/MOREHEAD CITY NC\n415 AM EDT MON APR 15 2019\n\n..TIME...   ...EVENT...      ...CITY LOCATION...     ...LAT.LON...\n..DATE...   ....MAG....      ..COUNTY LOCATION..ST.. ...SOURCE....\n            ..REMARKS..\n\n0358 AM     TSTM WND GST     COASTAL CAROLINA REGION 35.07N 77.04W\n04/15/2019  M61 MPH          CRAVEN             NC   ASOS             \n\n            NEW BERN/CRAVEN COUNTY ASOS (EWN) REPORTS \n            GUST OF 61 MPH. \n\n\n&&\n\n$$\n\nML\n\n","title":"Report:  61mph Wind (KMHX)","tropical":null,"valid_ts":null,"warning":null,"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -279,7 +280,7 @@ mod tests {
         let product = get_product_from_file("../data/products/lsr-hail-remarks");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1522113360000000,"event_type":"NwsLsr","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":{"wfo":"KSJT","point":{"lat":32.07,"lon":-100.66},"poly":null,"county":"COKE"},"md":null,"outlook":null,"report":{"reporter":"STORM CHASER","hazard":"Hail","magnitude":1.25,"units":"Inches","was_measured":false,"report_ts":1522112100000000},"text":"\n106 \nNWUS54 KSJT 270116\nLSRSJT\n\nPRELIMINARY LOCAL STORM REPORT\nNational Weather Service San Angelo Tx\n816 PM CDT MON MAR 26 2018\n\n..TIME...   ...EVENT...      ...CITY LOCATION...     ...LAT.LON...\n..DATE...   ....MAG....      ..COUNTY LOCATION..ST.. ...SOURCE....\n            ..REMARKS..\n\n0755 PM     HAIL             1 E SILVER              32.07N 100.66W\n03/26/2018  E1.25 INCH       COKE               TX   STORM CHASER    \n\n            1.25 HAIL ON HWY 208 NEAR SILVER \n\n\n&&\n\nEVENT NUMBER SJT1800032\n\n$$\n\nSJT\n\n","title":"Report:  1.25\" Hail (KSJT)","valid_ts":null,"warning":null,"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1522113360000000,"event_type":"NwsLsr","expires_ts":null,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KSJT","point":{"lat":32.07,"lon":-100.66},"poly":null,"county":"COKE"},"md":null,"outlook":null,"report":{"reporter":"STORM CHASER","hazard":"Hail","magnitude":1.25,"units":"Inches","was_measured":false,"report_ts":1522112100000000,"lsr_source_id":null},"source_id":null,"survey":null,"text":"\n106 \nNWUS54 KSJT 270116\nLSRSJT\n\nPRELIMINARY LOCAL STORM REPORT\nNational Weather Service San Angelo Tx\n816 PM CDT MON MAR 26 2018\n\n..TIME...   ...EVENT...      ...CITY LOCATION...     ...LAT.LON...\n..DATE...   ....MAG....      ..COUNTY LOCATION..ST.. ...SOURCE....\n            ..REMARKS..\n\n0755 PM     HAIL             1 E SILVER              32.07N 100.66W\n03/26/2018  E1.25 INCH       COKE               TX   STORM CHASER    \n\n            1.25 HAIL ON HWY 208 NEAR SILVER \n\n\n&&\n\nEVENT NUMBER SJT1800032\n\n$$\n\nSJT\n\n","title":"Report:  1.25\" Hail (KSJT)","tropical":null,"valid_ts":null,"warning":null,"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -0,0 +1,186 @@
+use crate::nws_regexes::Regexes;
+use crate::parser_util::{day_time_to_ticks, get_condensed_polygon};
+use domain::{Event, EventType, Location, MdConcerning, MesoscaleDiscussion, Product};
+use regex::Regex;
+use util::safe_result;
+
+const MPD_NUMBER_PATTERN: &str = r"Mesoscale Precipitation Discussion (\d{4})";
+
+lazy_static! {
+    static ref MPD_NUMBER_REGEX: Regex =
+        Regex::new(MPD_NUMBER_PATTERN).expect("Unable to compile regex");
+}
+
+/**
+ * Parses a WPC Mesoscale Precipitation Discussion (MPD). MPDs are laid out like SPC MDs, so they
+ * share the MD model, but they're about flash flooding instead of watches. They're sent as FFGs,
+ * along with the RFCs' flash flood guidance, which is skipped.
+ */
+pub fn parse(product: &Product) -> Option<Event> {
+    let regexes = Regexes::new();
+    let text = &product.product_text;
+    let id = safe_result!(MPD_NUMBER_REGEX.captures(text)?[1].parse::<u16>());
+    let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
+    let poly = get_condensed_polygon(text, &regexes);
+
+    let affected = regexes
+        .affected
+        .captures(text)
+        .map(|x| x[1].replace('\n', " "))
+        .unwrap_or_default();
+    let wfos: Vec<String> = regexes
+        .wfos
+        .captures(text)
+        .map(|x| {
+            x[1].split("...")
+                .map(ToString::to_string)
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    let summary = regexes
+        .md_summary
+        .captures(text)
+        .map(|x| x[1].trim().replace('\n', " "));
+    let concerning = regexes
+        .md_concerning
+        .captures(text)
+        .map(|x| get_mpd_concerning(&x[1]))
+        .unwrap_or(MdConcerning::Unknown);
+
+    let (valid_ts, expires_ts) = match regexes.md_valid.captures(text) {
+        Some(valid) => (
+            day_time_to_ticks(&valid[1], event_ts).ok(),
+            day_time_to_ticks(&valid[2], event_ts).ok(),
+        ),
+        None => (None, None),
+    };
+
+    let title = match concerning {
+        MdConcerning::FlashFloodingLikely => "WPC MPD: Flash Flooding Likely",
+        MdConcerning::FlashFloodingPossible => "WPC MPD: Flash Flooding Possible",
+        MdConcerning::FlashFloodingUnlikely => "WPC MPD: Flash Flooding Unlikely",
+        _ => "WPC Mesoscale Precipitation Discussion",
+    }
+    .to_string();
+
+    let md = MesoscaleDiscussion {
+        id,
+        affected,
+        concerning,
+        watch_issuance_probability: None,
+        wfos,
+        summary,
+        watch_ids: vec![],
+    };
+
+    let location = Some(Location {
+        wfo: None,
+        point: None,
+        poly: Some(poly),
+        county: None,
+    });
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type: EventType::NwsMpd,
+        expires_ts,
+        ext_uri: None,
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0,
+        location,
+        md: Some(md),
+        outlook: None,
+        report: None,
+        source_id: None,
+        survey: None,
+        text: Some(text.to_string()),
+        title,
+        tropical: None,
+        valid_ts,
+        warning: None,
+        watch: None,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+/**
+ * Maps the text after "Concerning...", ie. "Heavy rainfall...Flash flooding likely".
+ */
+fn get_mpd_concerning(text: &str) -> MdConcerning {
+    let text = text.to_lowercase();
+
+    if text.contains("flash flooding unlikely") {
+        MdConcerning::FlashFloodingUnlikely
+    } else if text.contains("flash flooding likely") {
+        MdConcerning::FlashFloodingLikely
+    } else if text.contains("flash flooding possible") {
+        MdConcerning::FlashFloodingPossible
+    } else if text.contains("heavy rain") {
+        MdConcerning::HeavyRain
+    } else {
+        MdConcerning::Unknown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::get_product_from_file;
+    use super::*;
+
+    #[test]
+    fn parse_mpd_flash_flooding_likely() {
+        let product = get_product_from_file("../data/products/ffg-mpd");
+        let result = parse(&product).unwrap();
+        assert_eq!(result.event_type, EventType::NwsMpd);
+        assert_eq!(result.title, "WPC MPD: Flash Flooding Likely");
+        assert_eq!(result.valid_ts, Some(1558491300000000));
+        assert_eq!(result.expires_ts, Some(1558512000000000));
+
+        let serialized_result = serde_json::to_string(&result.md).unwrap();
+        let expected = r#"{"id":215,"affected":"Southern Oklahoma...North Texas","concerning":"FlashFloodingLikely","watch_issuance_probability":null,"wfos":["FWD","OUN","TSA"],"summary":"Training thunderstorms will continue to produce rainfall rates of 2-3 inches per hour through the early morning hours, with additional totals of 3-5 inches possible. Flash flooding is likely.","watch_ids":[]}"#;
+        assert_eq!(expected, serialized_result);
+
+        let poly = result.location.unwrap().poly.unwrap();
+        assert_eq!(poly.len(), 8);
+        assert_eq!(poly[0].lat, 34.92);
+        assert_eq!(poly[0].lon, -96.49);
+    }
+
+    #[test]
+    fn parse_should_skip_flash_flood_guidance() {
+        let mut product = get_product_from_file("../data/products/ffg-mpd");
+        product.product_text =
+            "\nFLASH FLOOD GUIDANCE\nNWS ARKANSAS-RED BASIN RFC TULSA OK\n\nOKZ004 2.1 2.6 3.0\n"
+                .to_string();
+        assert_eq!(parse(&product), None);
+    }
+
+    #[test]
+    fn get_mpd_concerning_should_map_likelihood() {
+        let tests = vec![
+            (
+                "Heavy rainfall...Flash flooding likely",
+                MdConcerning::FlashFloodingLikely,
+            ),
+            (
+                "Heavy rainfall...Flash flooding possible",
+                MdConcerning::FlashFloodingPossible,
+            ),
+            (
+                "HEAVY RAINFALL...FLASH FLOODING UNLIKELY",
+                MdConcerning::FlashFloodingUnlikely,
+            ),
+            ("Heavy rainfall", MdConcerning::HeavyRain),
+            ("Snow", MdConcerning::Unknown),
+        ];
+
+        for (text, expected) in tests {
+            assert_eq!(get_mpd_concerning(text), expected, "{}", text);
+        }
+    }
+}
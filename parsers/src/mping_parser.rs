@@ -0,0 +1,160 @@
+use domain::{Coordinates, Event, EventType, HazardType, Location, MpingReport, Report, Units};
+use regex::Regex;
+use util;
+use util::safe_result;
+
+const HAIL_SIZE_PATTERN: &str = r"\((?P<size>\d+\.\d+) in\.\)";
+
+lazy_static! {
+    static ref HAIL_SIZE_REGEX: Regex =
+        Regex::new(HAIL_SIZE_PATTERN).expect("Unable to compile regex");
+}
+
+/**
+ * Parses a report from the mPING API. Reports are anonymous, and only severe and winter
+ * precipitation categories are kept since plain rain reports are far too common to be useful.
+ */
+pub fn parse(mping_report: &MpingReport) -> Option<Event> {
+    let category = mping_report.category.as_str();
+    let description = mping_report.description.as_str();
+    let hazard = get_hazard_type(category, description)?;
+
+    let mut report = Report {
+        hazard,
+        lsr_source_id: None,
+        magnitude: None,
+        report_ts: None,
+        reporter: "mPING".to_string(),
+        units: None,
+        was_measured: None,
+    };
+
+    let mut title = format!("Report: {}", category);
+
+    if report.hazard == HazardType::Hail {
+        if let Some(size) = HAIL_SIZE_REGEX
+            .captures(description)
+            .and_then(|x| x["size"].parse::<f32>().ok())
+        {
+            title = format!("Report: {}\" Hail", size);
+            report.magnitude = Some(size);
+            report.units = Some(Units::Inches);
+        }
+    }
+
+    let coordinates = &mping_report.geom.coordinates;
+    if coordinates.len() < 2 {
+        warn!("mPING report {} has no point", mping_report.id);
+        return None;
+    }
+
+    let location = Some(Location {
+        county: None,
+        wfo: None,
+        point: Some(Coordinates {
+            lat: coordinates[1],
+            lon: coordinates[0],
+        }),
+        poly: None,
+    });
+
+    let event_ts = safe_result!(util::rfc3339_to_ticks(&mping_report.obtime));
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type: EventType::MpingReport,
+        expires_ts: None,
+        ext_uri: None,
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0, // set when storing
+        location,
+        md: None,
+        outlook: None,
+        report: Some(report),
+        source_id: Some(format!("mping:{}", mping_report.id)),
+        survey: None,
+        text: Some(format!("{} reported via mPING: {}", category, description)),
+        title,
+        tropical: None,
+        valid_ts: None,
+        warning: None,
+        watch: None,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+fn get_hazard_type(category: &str, description: &str) -> Option<HazardType> {
+    let hazard = match category {
+        "Hail" => HazardType::Hail,
+        "Wind Damage" => HazardType::Wind,
+        "Tornado" if description.contains("Water spout") => HazardType::Waterspout,
+        "Tornado" => HazardType::Tornado,
+        "Flood" => HazardType::Flood,
+        "Snow and/or Graupel" => HazardType::Snow,
+        "Freezing Rain" | "Freezing Drizzle" => HazardType::FreezingRain,
+        // Ice pellets and mixed precipitation types
+        x if x.contains("Ice Pellets") || x.starts_with("Mixed") => HazardType::Other,
+        _ => return None,
+    };
+
+    Some(hazard)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::MpingReportsResult;
+    use std::fs::File;
+    use std::io::Read;
+
+    fn get_reports() -> Vec<MpingReport> {
+        let mut f = File::open("../data/mping-reports.json").expect("reports file not found");
+        let mut contents = String::new();
+        f.read_to_string(&mut contents)
+            .expect("something went wrong reading the file");
+        let result: MpingReportsResult = serde_json::from_str(&contents).unwrap();
+        result.results
+    }
+
+    #[test]
+    fn parse_hail_report_happy_path() {
+        let reports = get_reports();
+        let result = parse(&reports[0]).unwrap();
+        let serialized_result = serde_json::to_string(&result).unwrap();
+        let expected = r#"{"afd":null,"event_ts":1586727720000000,"event_type":"MpingReport","expires_ts":null,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":null,"point":{"lat":32.5171,"lon":-92.1123},"poly":null,"county":null},"md":null,"outlook":null,"report":{"reporter":"mPING","hazard":"Hail","magnitude":1.0,"units":"Inches","was_measured":null,"report_ts":null,"lsr_source_id":null},"source_id":"mping:4452141","survey":null,"text":"Hail reported via mPING: Quarter (1.00 in.)","title":"Report: 1\" Hail","tropical":null,"valid_ts":null,"warning":null,"watch":null,"winter":null}"#;
+        assert_eq!(expected, serialized_result);
+    }
+
+    #[test]
+    fn parse_should_map_categories() {
+        let reports = get_reports();
+        let hazards: Vec<Option<HazardType>> = reports
+            .iter()
+            .map(|x| parse(x).and_then(|event| event.report).map(|x| x.hazard))
+            .collect();
+        let expected = vec![
+            Some(HazardType::Hail),
+            Some(HazardType::Wind),
+            Some(HazardType::Tornado),
+            None,
+            Some(HazardType::Snow),
+            Some(HazardType::Flood),
+        ];
+        assert_eq!(hazards, expected);
+    }
+
+    #[test]
+    fn get_hazard_type_should_handle_winter_and_waterspouts() {
+        let result = get_hazard_type("Freezing Drizzle", "Freezing Drizzle");
+        assert_eq!(result, Some(HazardType::FreezingRain));
+        let result = get_hazard_type("Ice Pellets/Sleet", "Ice Pellets/Sleet");
+        assert_eq!(result, Some(HazardType::Other));
+        let result = get_hazard_type("Tornado", "Water spout");
+        assert_eq!(result, Some(HazardType::Waterspout));
+        assert_eq!(get_hazard_type("Test", "Test"), None);
+    }
+}
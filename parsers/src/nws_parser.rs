@@ -1,31 +1,88 @@
 use crate::{
-    afd_parser, ffw_parser, lsr_parser, sel_parser, svr_parser, svs_parser, swo_parser, tor_parser,
+    afd_parser, ffw_parser, fwd_parser, hls_parser, lsr_parser, mpd_parser, pns_parser, rfw_parser,
+    sel_parser, smw_parser, sps_parser, svr_parser, svs_parser, swo_parser, tcp_parser, tor_parser,
+    wsw_parser, wwp_parser,
 };
 use domain::{Event, Product};
 use std::panic;
 
 /**
- * Determines which product gets which parser.
+ * Determines which product gets which parser. Most products parse to a single event, but segmented
+ * products like WSWs, RFWs and PNSs parse to an event per segment. Skipped segments are still
+ * counted, since the segment index feeds `get_source_id`.
  * NOTE: We're catching panics here - not ideal, but processing threads can't die.
  */
-pub fn parse(product: &Product) -> Option<Event> {
+pub fn parse(product: &Product) -> Vec<Event> {
     let result = panic::catch_unwind(|| match product.product_code.as_ref() {
-        "AFD" => afd_parser::parse(&product),
-        "FFW" => ffw_parser::parse(&product),
-        "LSR" => lsr_parser::parse(&product),
-        "SEL" => sel_parser::parse(&product),
-        "SVR" => svr_parser::parse(&product),
-        "SVS" => svs_parser::parse(&product),
-        "SWO" => swo_parser::parse(&product),
-        "TOR" => tor_parser::parse(&product),
+        "AFD" => vec![afd_parser::parse(&product)],
+        "FFG" => vec![mpd_parser::parse(product)],
+        "FFW" => vec![ffw_parser::parse(&product)],
+        "FWD" => vec![fwd_parser::parse(product)],
+        "HLS" => vec![hls_parser::parse(product)],
+        "LSR" => vec![lsr_parser::parse(&product)],
+        "PNS" => pns_parser::parse(product),
+        "RFW" => rfw_parser::parse(product),
+        "SEL" => vec![sel_parser::parse(&product)],
+        "SMW" => vec![smw_parser::parse(product)],
+        "SPS" => vec![sps_parser::parse(product)],
+        "SVR" => vec![svr_parser::parse(&product)],
+        "SVS" => vec![svs_parser::parse(&product)],
+        "SWO" => vec![swo_parser::parse(&product)],
+        "TCP" => vec![tcp_parser::parse(product)],
+        "TOR" => vec![tor_parser::parse(&product)],
+        "WSW" => wsw_parser::parse(product),
+        "WWP" => vec![wwp_parser::parse(product)],
         _ => {
             error!("unknown product code: {}", &product.product_code);
-            None
+            vec![]
         }
     });
 
-    result.unwrap_or_else(|_| {
+    let segments = result.unwrap_or_else(|_| {
         error!("recovered from panic on product: {}", product.id);
-        None
-    })
+        vec![]
+    });
+
+    segments
+        .into_iter()
+        .enumerate()
+        .filter_map(|(segment, event)| {
+            let mut event = event?;
+            event.source_id = Some(get_source_id(product, segment));
+            Some(event)
+        })
+        .collect()
+}
+
+/**
+ * Identifies an event by the product it was parsed from, and the segment of the product for
+ * products that parse to more than one event.
+ */
+pub fn get_source_id(product: &Product, segment: usize) -> String {
+    format!("nws:{}:{}", product.id, segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::get_product_from_file;
+    use super::*;
+
+    #[test]
+    fn parse_should_set_source_id() {
+        let product = get_product_from_file("../data/products/tor-normal");
+        let events = parse(&product);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].source_id, Some(format!("nws:{}:0", product.id)));
+    }
+
+    #[test]
+    fn parse_should_set_segment_source_ids() {
+        let product = get_product_from_file("../data/products/wsw-mpx");
+        let source_ids: Vec<String> = parse(&product)
+            .into_iter()
+            .filter_map(|x| x.source_id)
+            .collect();
+        let expected: Vec<String> = (0..3).map(|x| get_source_id(&product, x)).collect();
+        assert_eq!(source_ids, expected);
+    }
 }
@@ -9,16 +9,29 @@ pub struct Regexes {
     pub probability: Regex,
     pub wfos: Regex,
     pub md_number: Regex,
+    pub md_concerning: Regex,
+    pub md_summary: Regex,
+    pub md_valid: Regex,
     pub watch_id: Regex,
     pub poly: Regex,
     pub warning_for: Regex,
     pub watch_for: Regex,
+    pub vtec: Regex,
+    pub lat_lon: Regex,
+    pub hail_tag: Regex,
+    pub wind_tag: Regex,
+    pub waterspout_tag: Regex,
+    pub ugc_purge: Regex,
+    pub storm_motion: Regex,
+    pub storm_header: Regex,
+    pub storm_id: Regex,
+    pub storm_position: Regex,
 }
 
 impl Regexes {
     pub fn new() -> Regexes {
-        let movement_pattern = r"\ntime...mot...loc\s(?P<time>\d{4}z)\s(?P<deg>\d+)\D{3}\s(?P<kt>\d+)kt\s(?P<lat>\d{4})\s(?P<lon>\d{4,5})";
-        let source_pattern = r"\n{2}\s{2}source...(?P<src>[\s|\S]*?)\.";
+        let movement_pattern = r"\ntime...mot...loc\s(?P<time>\d{4})z\s(?P<deg>\d+)\D{3}\s(?P<kt>\d+)kt(?P<loc>(\s+\d{4}\s\d{4,5})+)";
+        let source_pattern = r"\n{2}\s{0,2}source...(?P<src>[\s|\S]*?)\.";
         let valid_pattern = r"(\d{6}t\d{4}z)-(\d{6}t\d{4}z)";
         let affected_pattern = r"Areas affected\.{3}([\S|\s]*?)\n\n";
         let probability_pattern = r"Probability of Watch Issuance...(\d{1,3}) percent";
@@ -26,9 +39,23 @@ impl Regexes {
         let poly_pattern = r"(\d{4}\s\d{4,5})+";
         let poly_condensed_pattern = r"(\d{8})\s";
         let md_number_pattern = r"Mesoscale Discussion (\d{4})";
+        let md_concerning_pattern = r"Concerning\.{3}(.+)";
+        let md_summary_pattern = r"SUMMARY\.{3}([\s|\S]*?)\n\n";
+        let md_valid_pattern = r"Valid (\d{6})Z - (\d{6})Z";
         let watch_id_pattern = r"Watch Number (\d{1,3})";
         let warning_for_pattern = r"Warning for...([\s|\S]+?)\n\n";
         let watch_for_pattern = r"Watch for portions of\s\n([\s|\S]+?)\n\n";
+        let vtec_pattern = r"/[OTEX]\.(?P<action>[A-Z]{3})\.(?P<office>[A-Z]{4})\.(?P<phenomena>[A-Z]{2})\.(?P<significance>[A-Z])\.(?P<etn>\d{4})\.(?P<begin>\d{6}T\d{4}Z)-(?P<end>\d{6}T\d{4}Z)/";
+        let lat_lon_pattern = r"LAT\.{3}LON(?P<coords>[\d\s]+)";
+        let hail_tag_pattern = r"\nHAIL\.{3}[<>]?(?P<size>\d*\.?\d+)IN";
+        let wind_tag_pattern = r"\nWIND\.{3}(?P<gust>[<>]?\d+(KTS|MPH))";
+        let waterspout_tag_pattern = r"\nWATERSPOUT\.{3}(?P<waterspout>[A-Z]+)";
+        let ugc_purge_pattern = r"(?m)^[A-Z]{2}[CZ]\d{3}[\s\S]*?(?P<purge>\d{6})-$";
+        let storm_motion_pattern = r"storm\s+motion\s+vector\D*(?P<deg>\d{3})(?P<kt>\d{2,3})";
+        let storm_header_pattern = r"(?m)^(?P<name>[a-z][a-z\- ]*?)\s+(local\s+statement\s+)?(intermediate\s+)?advisory\s+number\s+(?P<advisory>\d+[a-z]?)\s*$";
+        let storm_id_pattern = r"(?m)\s(?P<id>(AL|EP|CP)\d{6})\s*$";
+        let storm_position_pattern =
+            r"(?P<lat>\d{1,2}\.\d)(?P<ns>[NS])\s+(?P<lon>\d{1,3}\.\d)(?P<ew>[EW])";
 
         Regexes {
             movement: RegexBuilder::new(movement_pattern)
@@ -67,6 +94,18 @@ impl Regexes {
                 .case_insensitive(true)
                 .build()
                 .unwrap(),
+            md_concerning: RegexBuilder::new(md_concerning_pattern)
+                .case_insensitive(true)
+                .build()
+                .unwrap(),
+            md_summary: RegexBuilder::new(md_summary_pattern)
+                .case_insensitive(true)
+                .build()
+                .unwrap(),
+            md_valid: RegexBuilder::new(md_valid_pattern)
+                .case_insensitive(true)
+                .build()
+                .unwrap(),
             watch_id: RegexBuilder::new(watch_id_pattern)
                 .case_insensitive(true)
                 .build()
@@ -79,6 +118,22 @@ impl Regexes {
                 .case_insensitive(true)
                 .build()
                 .unwrap(),
+            vtec: Regex::new(vtec_pattern).unwrap(),
+            lat_lon: Regex::new(lat_lon_pattern).unwrap(),
+            hail_tag: Regex::new(hail_tag_pattern).unwrap(),
+            wind_tag: Regex::new(wind_tag_pattern).unwrap(),
+            waterspout_tag: Regex::new(waterspout_tag_pattern).unwrap(),
+            ugc_purge: Regex::new(ugc_purge_pattern).unwrap(),
+            storm_motion: RegexBuilder::new(storm_motion_pattern)
+                .case_insensitive(true)
+                .build()
+                .unwrap(),
+            storm_header: RegexBuilder::new(storm_header_pattern)
+                .case_insensitive(true)
+                .build()
+                .unwrap(),
+            storm_id: Regex::new(storm_id_pattern).unwrap(),
+            storm_position: Regex::new(storm_position_pattern).unwrap(),
         }
     }
 }
@@ -1,5 +1,18 @@
+use crate::nws_regexes::Regexes;
 use chrono::prelude::*;
+use domain::Coordinates;
 use regex::Match;
+use util::safe_result;
+
+const EARTH_RADIUS_MI: f64 = 3958.8;
+const HALF_DAY_MICROS: u64 = 12 * 3600 * 1_000_000;
+
+pub struct StormMotion {
+    pub time: u64,
+    pub deg: u16,
+    pub kt: u16,
+    pub points: Vec<Coordinates>,
+}
 
 pub fn short_time_to_ticks(input: &str) -> Result<u64, ()> {
     match Utc.datetime_from_str(input, "%y%m%dT%H%MZ") {
@@ -11,10 +24,293 @@ pub fn short_time_to_ticks(input: &str) -> Result<u64, ()> {
     }
 }
 
+/**
+ * Converts a DDHHMM time, ie. the 031641 in an MD's "Valid 031641Z", to ticks. The month and year
+ * come from the product's issuance time, and a day far from the issuance day is taken to be in the
+ * previous or next month.
+ */
+pub fn day_time_to_ticks(input: &str, issued_ts: u64) -> Result<u64, ()> {
+    let issued = Utc
+        .timestamp_opt((issued_ts / 1_000_000) as i64, 0)
+        .single();
+    let parsed = input
+        .get(0..2)
+        .and_then(|x| x.parse::<u32>().ok())
+        .and_then(|day| {
+            let hour = input.get(2..4)?.parse::<u32>().ok()?;
+            let minute = input.get(4..6)?.parse::<u32>().ok()?;
+            Some((day, hour, minute))
+        });
+
+    let (issued, (day, hour, minute)) = match (issued, parsed) {
+        (Some(issued), Some(parsed)) => (issued, parsed),
+        _ => {
+            warn!("Unable to parse day time {}", input);
+            return Err(());
+        }
+    };
+
+    let (year, month) = if day + 15 < issued.day() {
+        if issued.month() == 12 {
+            (issued.year() + 1, 1)
+        } else {
+            (issued.year(), issued.month() + 1)
+        }
+    } else if day > issued.day() + 15 {
+        if issued.month() == 1 {
+            (issued.year() - 1, 12)
+        } else {
+            (issued.year(), issued.month() - 1)
+        }
+    } else {
+        (issued.year(), issued.month())
+    };
+
+    match Utc
+        .ymd_opt(year, month, day)
+        .single()
+        .and_then(|x| x.and_hms_opt(hour, minute, 0))
+    {
+        Some(dt) => Ok(dt.timestamp() as u64 * 1_000_000),
+        None => {
+            warn!("Unable to parse day time {}", input);
+            Err(())
+        }
+    }
+}
+
+/**
+ * Converts an HHMM time, ie. the 0100 in "TIME...MOT...LOC 0100Z", to ticks. The date comes from
+ * the product's issuance time, and a time more than 12 hours from it is taken to be on the previous
+ * or next day.
+ */
+pub fn hour_min_to_ticks(input: &str, issued_ts: u64) -> Result<u64, ()> {
+    let hour = input.get(0..2).and_then(|x| x.parse::<u32>().ok());
+    let minute = input.get(2..4).and_then(|x| x.parse::<u32>().ok());
+    let issued = Utc
+        .timestamp_opt((issued_ts / 1_000_000) as i64, 0)
+        .single();
+
+    let time = match (issued, hour, minute) {
+        (Some(issued), Some(hour), Some(minute)) => issued
+            .naive_utc()
+            .date()
+            .and_hms_opt(hour, minute, 0)
+            .map(|x| x.timestamp() as u64 * 1_000_000),
+        _ => None,
+    };
+
+    match time {
+        Some(time) if time > issued_ts + HALF_DAY_MICROS => Ok(time - 2 * HALF_DAY_MICROS),
+        Some(time) if time + HALF_DAY_MICROS < issued_ts => Ok(time + 2 * HALF_DAY_MICROS),
+        Some(time) => Ok(time),
+        None => {
+            warn!("Unable to parse hour minute {}", input);
+            Err(())
+        }
+    }
+}
+
+/**
+ * Returns the offset from UTC of a US time zone abbreviation, ie. -5 for CDT.
+ */
+pub fn get_utc_offset_hours(zone: &str) -> Option<i64> {
+    match zone.to_uppercase().as_str() {
+        "EDT" => Some(-4),
+        "EST" | "CDT" => Some(-5),
+        "CST" | "MDT" => Some(-6),
+        "MST" | "PDT" => Some(-7),
+        "PST" | "AKDT" => Some(-8),
+        "AKST" => Some(-9),
+        "HST" => Some(-10),
+        "UTC" | "GMT" | "Z" => Some(0),
+        _ => {
+            warn!("Unknown time zone {}", zone);
+            None
+        }
+    }
+}
+
 pub fn cap(m: Option<Match>) -> &str {
     m.unwrap().as_str()
 }
 
+/**
+ * Returns the VTEC event tracking number, ie. KTOP.TO.W.0009.2018, which identifies the same
+ * warning across products and sources. ETNs reset every year, so the year is included. Products
+ * in effect "until further notice" have a zeroed begin time, so those use the end time's year.
+ */
+pub fn get_vtec_id(text: &str, regexes: &Regexes) -> Option<String> {
+    let captures = regexes.vtec.captures(text)?;
+    let begin = cap(captures.name("begin"));
+    let year = if begin.starts_with("000000") {
+        &cap(captures.name("end"))[0..2]
+    } else {
+        &begin[0..2]
+    };
+
+    Some(format!(
+        "{}.{}.{}.{}.20{}",
+        cap(captures.name("office")),
+        cap(captures.name("phenomena")),
+        cap(captures.name("significance")),
+        cap(captures.name("etn")),
+        year
+    ))
+}
+
+/**
+ * Returns every point in the LAT...LON block, which can wrap over several lines. Only the block is
+ * searched, since the TIME...MOT...LOC points that follow it look the same.
+ */
+pub fn get_polygon(text: &str, regexes: &Regexes) -> Vec<Coordinates> {
+    let lat_lon = match regexes.lat_lon.captures(text) {
+        Some(lat_lon) => lat_lon,
+        None => return vec![],
+    };
+
+    regexes
+        .poly
+        .captures_iter(&lat_lon["coords"])
+        .map(|latlon| {
+            let splits: Vec<&str> = latlon[0].split(' ').collect();
+            Coordinates {
+                lat: str_to_latlon(splits[0], false),
+                lon: str_to_latlon(splits[1], true),
+            }
+        })
+        .collect()
+}
+
+/**
+ * Returns the storm's motion and location from the TIME...MOT...LOC line of a warning or
+ * statement, ie. "TIME...MOT...LOC 0100Z 245DEG 24KT 3952 9728". A line of storms has a point for
+ * each end of the line, or more, and the points can wrap onto the next line.
+ */
+pub fn get_storm_motion(text: &str, issued_ts: u64, regexes: &Regexes) -> Option<StormMotion> {
+    let movement = regexes.movement.captures(text)?;
+    let time = safe_result!(hour_min_to_ticks(&movement["time"], issued_ts));
+    let deg = safe_result!(movement["deg"].parse::<u16>());
+    let kt = safe_result!(movement["kt"].parse::<u16>());
+    let points = regexes
+        .poly
+        .captures_iter(&movement["loc"])
+        .map(|latlon| {
+            let splits: Vec<&str> = latlon[0].split(' ').collect();
+            Coordinates {
+                lat: str_to_latlon(splits[0], false),
+                lon: str_to_latlon(splits[1], true),
+            }
+        })
+        .collect();
+
+    Some(StormMotion {
+        time,
+        deg,
+        kt,
+        points,
+    })
+}
+
+/**
+ * Returns the points of a condensed LAT...LON block, ie. "37828769 38538776", which SPC and WPC
+ * discussions use instead of the space separated pairs in warnings.
+ */
+pub fn get_condensed_polygon(text: &str, regexes: &Regexes) -> Vec<Coordinates> {
+    let lat_lon = match regexes.lat_lon.captures(text) {
+        Some(lat_lon) => lat_lon,
+        None => return vec![],
+    };
+
+    regexes
+        .poly_condensed
+        .captures_iter(&lat_lon["coords"])
+        .map(|latlon| Coordinates {
+            lat: str_to_latlon(&latlon[1][0..4], false),
+            lon: str_to_latlon(&latlon[1][4..8], true),
+        })
+        .collect()
+}
+
+/**
+ * Returns the first tropical cyclone position in the text, ie. "26.7N 82.2W".
+ */
+pub fn get_storm_position(text: &str, regexes: &Regexes) -> Option<Coordinates> {
+    let position = regexes.storm_position.captures(text)?;
+    let lat = position["lat"].parse::<f32>().ok()?;
+    let lon = position["lon"].parse::<f32>().ok()?;
+
+    Some(Coordinates {
+        lat: if &position["ns"] == "S" { -lat } else { lat },
+        lon: if &position["ew"] == "W" { -lon } else { lon },
+    })
+}
+
+/**
+ * Returns the point a distance in statute miles along a bearing from a starting point, treating
+ * the earth as a sphere.
+ */
+pub fn get_destination(start: &Coordinates, bearing_deg: f64, miles: f64) -> Coordinates {
+    let distance = miles / EARTH_RADIUS_MI;
+    let bearing = bearing_deg.to_radians();
+    let lat1 = f64::from(start.lat).to_radians();
+    let lon1 = f64::from(start.lon).to_radians();
+
+    let lat2 = (lat1.sin() * distance.cos() + lat1.cos() * distance.sin() * bearing.cos()).asin();
+    let lon2 = lon1
+        + (bearing.sin() * distance.sin() * lat1.cos())
+            .atan2(distance.cos() - lat1.sin() * lat2.sin());
+
+    Coordinates {
+        lat: lat2.to_degrees() as f32,
+        lon: lon2.to_degrees() as f32,
+    }
+}
+
+/**
+ * Returns the initial bearing in degrees from one point to another.
+ */
+pub fn get_bearing(start: &Coordinates, end: &Coordinates) -> f64 {
+    let lat1 = f64::from(start.lat).to_radians();
+    let lat2 = f64::from(end.lat).to_radians();
+    let delta_lon = f64::from(end.lon - start.lon).to_radians();
+    let y = delta_lon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * delta_lon.cos();
+    (y.atan2(x).to_degrees() + 360.0) % 360.0
+}
+
+/**
+ * Expands a UGC line, ie. "MNZ060-062-063-WIZ014>016-280600-", into its zones. Zones without a
+ * state and type prefix share the previous one, and ranges are inclusive. The trailing purge time
+ * is dropped.
+ */
+pub fn get_zones(ugc: &str) -> Vec<String> {
+    let mut zones = vec![];
+    let mut prefix = "";
+
+    let ugc: String = ugc.split_whitespace().collect();
+    for part in ugc.split('-').filter(|x| !x.is_empty()) {
+        let part = if part.len() > 3 && part.as_bytes()[0].is_ascii_alphabetic() {
+            prefix = &part[0..3];
+            &part[3..]
+        } else if part.len() == 6 {
+            continue; // purge time
+        } else {
+            part
+        };
+
+        let mut range = part.split('>').filter_map(|x| x.parse::<u16>().ok());
+        let (start, end) = match (range.next(), range.next()) {
+            (Some(start), Some(end)) => (start, end),
+            (Some(start), None) => (start, start),
+            _ => continue,
+        };
+        (start..=end).for_each(|x| zones.push(format!("{}{:03}", prefix, x)));
+    }
+
+    zones
+}
+
 pub fn str_to_latlon(input: &str, invert: bool) -> f32 {
     let sign = if invert { -1.0 } else { 1.0 };
     let mut value = input.parse::<f32>().unwrap();
@@ -47,10 +343,101 @@ mod tests {
         });
     }
 
+    #[test]
+    fn get_vtec_id_should_include_year() {
+        let regexes = Regexes::new();
+        let text = "KSC027-161-201-020145-\n/O.NEW.KTOP.TO.W.0009.180502T0101Z-180502T0145Z/\n";
+        let result = get_vtec_id(text, &regexes);
+        assert_eq!(result, Some("KTOP.TO.W.0009.2018".to_string()));
+
+        let text = "/O.EXT.KDMX.FL.W.0031.000000T0000Z-190401T1200Z/";
+        let result = get_vtec_id(text, &regexes);
+        assert_eq!(result, Some("KDMX.FL.W.0031.2019".to_string()));
+
+        let result = get_vtec_id("no vtec here", &regexes);
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn hour_min_to_ticks_should_handle_day_rollover() {
+        // 2018-05-02T01:01:00Z
+        let issued_ts = 1525222860000000;
+        assert_eq!(hour_min_to_ticks("0100", issued_ts), Ok(1525222800000000));
+        assert_eq!(hour_min_to_ticks("2359", issued_ts), Ok(1525219140000000));
+        assert_eq!(hour_min_to_ticks("01", issued_ts), Err(()));
+    }
+
+    #[test]
+    fn get_storm_motion_should_get_every_point() {
+        let regexes = Regexes::new();
+        let text = "LAT...LON 3977 9697 3950 9680\nTIME...MOT...LOC 0100Z 245DEG 24KT 3952 9728 3940 9740\n      3931 9752 \n\nHAIL...1.00IN";
+        let result = get_storm_motion(text, 1525222860000000, &regexes).unwrap();
+        assert_eq!(result.time, 1525222800000000);
+        assert_eq!(result.deg, 245);
+        assert_eq!(result.kt, 24);
+        assert_eq!(
+            result.points,
+            vec![
+                Coordinates {
+                    lat: 39.52,
+                    lon: -97.28
+                },
+                Coordinates {
+                    lat: 39.4,
+                    lon: -97.4
+                },
+                Coordinates {
+                    lat: 39.31,
+                    lon: -97.52
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn day_time_to_ticks_should_handle_month_rollover() {
+        // 2019-05-31T23:50:00Z
+        let issued_ts = 1559346600000000;
+        assert_eq!(day_time_to_ticks("312349", issued_ts), Ok(1559346540000000));
+        assert_eq!(day_time_to_ticks("010145", issued_ts), Ok(1559353500000000));
+        // 2019-01-01T00:30:00Z
+        assert_eq!(
+            day_time_to_ticks("312330", 1546302600000000),
+            Ok(1546299000000000)
+        );
+        assert!(day_time_to_ticks("31", issued_ts).is_err());
+    }
+
     #[test]
     fn short_time_to_ticks_should_return_correct_ticks() {
         let short_time = "190522T2100Z";
         let result = short_time_to_ticks(short_time).unwrap();
         assert_eq!(result, 1558558800000000);
     }
+
+    #[test]
+    fn get_destination_should_follow_bearing() {
+        let start = Coordinates {
+            lat: 40.0,
+            lon: -90.0,
+        };
+        // a degree of latitude is about 69.1 statute miles
+        let north = get_destination(&start, 0.0, 69.09);
+        assert!((north.lat - 41.0).abs() < 0.01);
+        assert!((north.lon + 90.0).abs() < 0.01);
+
+        let east = get_destination(&start, 90.0, 100.0);
+        assert!((east.lon + 88.12).abs() < 0.01);
+        assert!((get_bearing(&start, &east) - 90.0).abs() < 1.0);
+        assert!((get_bearing(&start, &north) - 0.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn get_zones_should_expand_ranges() {
+        let result = get_zones("MNZ049>053-058-\n059-061-280600-");
+        assert_eq!(
+            result,
+            vec!["MNZ049", "MNZ050", "MNZ051", "MNZ052", "MNZ053", "MNZ058", "MNZ059", "MNZ061"]
+        );
+    }
 }
@@ -0,0 +1,233 @@
+use crate::parser_util::get_utc_offset_hours;
+use chrono::prelude::*;
+use chrono::Duration;
+use domain::{Coordinates, Event, EventType, Location, Product, TornadoSurvey};
+use regex::Regex;
+use std::collections::HashMap;
+
+const SECTION_PATTERN: &str = r"(?m)^\.(?P<name>[^.\n][^\n]*?)\.{3}[ \t]*$";
+const FIELD_PATTERN: &str = r"(?m)^(?P<label>[A-Za-z][A-Za-z /]*?):[ \t]+(?P<value>\S.*?)[ \t]*$";
+const SUMMARY_PATTERN: &str = r"Survey Summary:\s*(?P<summary>[\s\S]*?)(\n\s*\n|$)";
+const RATING_PATTERN: &str = r"^EF[0-5U]$";
+
+lazy_static! {
+    static ref SECTION_REGEX: Regex = Regex::new(SECTION_PATTERN).expect("Unable to compile regex");
+    static ref FIELD_REGEX: Regex = Regex::new(FIELD_PATTERN).expect("Unable to compile regex");
+    static ref SUMMARY_REGEX: Regex = Regex::new(SUMMARY_PATTERN).expect("Unable to compile regex");
+    static ref RATING_REGEX: Regex = Regex::new(RATING_PATTERN).expect("Unable to compile regex");
+}
+
+/**
+ * Parses the tornadoes out of an NWS damage survey, which is sent as a Public Information Statement
+ * (PNS). Each tornado has its own section, which parses to its own event. PNSs are used for all
+ * sorts of announcements, so sections without an EF rating are skipped.
+ */
+pub fn parse(product: &Product) -> Vec<Option<Event>> {
+    let event_ts = match util::ts_to_ticks(&product.issuance_time) {
+        Ok(event_ts) => event_ts,
+        Err(_) => return vec![],
+    };
+    let text = &product.product_text;
+
+    // The survey ends at the EF scale legend and notes that follow the tornadoes
+    let text = match text.find("\n&&") {
+        Some(end) => &text[..end],
+        None => text,
+    };
+
+    let headers: Vec<(usize, usize, &str)> = SECTION_REGEX
+        .captures_iter(text)
+        .filter_map(|x| {
+            let header = x.get(0)?;
+            Some((header.start(), header.end(), x.name("name")?.as_str()))
+        })
+        .collect();
+
+    headers
+        .iter()
+        .enumerate()
+        .map(|(i, (start, end, name))| {
+            let section_end = match headers.get(i + 1) {
+                Some((next_start, _, _)) => *next_start,
+                None => text.len(),
+            };
+            parse_section(product, name, &text[*end..section_end], event_ts).map(|mut event| {
+                event.text = Some(text[*start..section_end].trim().to_string());
+                event
+            })
+        })
+        .collect()
+}
+
+fn parse_section(product: &Product, name: &str, text: &str, event_ts: u64) -> Option<Event> {
+    let fields: HashMap<&str, &str> = FIELD_REGEX
+        .captures_iter(text)
+        .filter_map(|x| Some((x.name("label")?.as_str(), x.name("value")?.as_str())))
+        .collect();
+
+    let rating = fields.get("Rating")?.to_uppercase();
+    if !RATING_REGEX.is_match(&rating) {
+        return None;
+    }
+
+    let start = fields.get("Start Lat/Lon").and_then(|x| get_coordinates(x));
+    let end = fields.get("End Lat/Lon").and_then(|x| get_coordinates(x));
+    let start_ts = get_ts(&fields, "Start");
+    let path: Vec<Coordinates> = match (start, end) {
+        (Some(start), Some(end)) => vec![start, end],
+        (Some(start), None) => vec![start],
+        _ => vec![],
+    };
+    let point = path.first().map(|x| Coordinates {
+        lat: x.lat,
+        lon: x.lon,
+    });
+
+    let summary = SUMMARY_REGEX.captures(text).and_then(|x| {
+        let summary = x["summary"]
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        if summary.is_empty() {
+            None
+        } else {
+            Some(summary)
+        }
+    });
+
+    let wfo = product.issuing_office.to_string();
+    let name = name.trim().to_string();
+    let title = format!("{} {} ({})", rating, name, wfo);
+
+    let survey = TornadoSurvey {
+        name,
+        rating,
+        peak_wind_mph: get_number(&fields, "Estimated Peak Wind"),
+        path_length_mi: get_number(&fields, "Path Length /statute/"),
+        path_width_yd: get_number(&fields, "Path Width /maximum/"),
+        fatalities: get_number(&fields, "Fatalities"),
+        injuries: get_number(&fields, "Injuries"),
+        start_ts,
+        end_ts: get_ts(&fields, "End"),
+        path,
+        summary,
+    };
+
+    let location = Some(Location {
+        wfo: Some(wfo),
+        point,
+        poly: None,
+        county: None,
+    });
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type: EventType::NwsTornadoSurvey,
+        expires_ts: None,
+        ext_uri: Some(product._id.to_string()),
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0,
+        location,
+        md: None,
+        outlook: None,
+        report: None,
+        source_id: None,
+        survey: Some(survey),
+        text: None,
+        title,
+        tropical: None,
+        valid_ts: start_ts,
+        warning: None,
+        watch: None,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+/**
+ * Parses the leading number of a field, ie. the 115 in "115 mph".
+ */
+fn get_number<T: std::str::FromStr>(fields: &HashMap<&str, &str>, label: &str) -> Option<T> {
+    fields
+        .get(label)?
+        .split_whitespace()
+        .next()?
+        .trim_start_matches('<')
+        .parse()
+        .ok()
+}
+
+fn get_coordinates(value: &str) -> Option<Coordinates> {
+    let mut splits = value.split('/').map(|x| x.trim().parse::<f32>());
+    match (splits.next(), splits.next()) {
+        (Some(Ok(lat)), Some(Ok(lon))) => Some(Coordinates { lat, lon }),
+        _ => None,
+    }
+}
+
+/**
+ * Survey times are local, ie. "Start Date: 05/20/2019" and "Start Time: 5:33 PM CDT".
+ */
+fn get_ts(fields: &HashMap<&str, &str>, prefix: &str) -> Option<u64> {
+    let date = fields.get(format!("{} Date", prefix).as_str())?;
+    let time = fields.get(format!("{} Time", prefix).as_str())?;
+    let (time, zone) = time.trim().rsplit_once(' ')?;
+    let offset_hours = get_utc_offset_hours(zone)?;
+
+    let local = format!("{} {}", date.trim(), time.to_uppercase());
+    let local = match NaiveDateTime::parse_from_str(&local, "%m/%d/%Y %I:%M %p") {
+        Ok(local) => local,
+        Err(_) => {
+            warn!("Unable to parse survey time: {}", local);
+            return None;
+        }
+    };
+
+    let utc = Utc.from_utc_datetime(&(local - Duration::hours(offset_hours)));
+    Some(utc.timestamp() as u64 * 1_000_000)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::get_product_from_file;
+    use super::*;
+
+    #[test]
+    fn parse_damage_survey() {
+        let product = get_product_from_file("../data/products/pns-damage-survey");
+        let result = parse(&product);
+        assert_eq!(result.len(), 2);
+
+        let event = result[0].as_ref().unwrap();
+        assert_eq!(event.event_type, EventType::NwsTornadoSurvey);
+        assert_eq!(event.title, "EF2 Mangum Tornado (KOUN)");
+        assert_eq!(event.valid_ts, Some(1558391580000000));
+        assert!(event
+            .text
+            .as_ref()
+            .unwrap()
+            .starts_with(".Mangum Tornado..."));
+
+        let serialized_result = serde_json::to_string(&event.survey).unwrap();
+        let expected = r#"{"name":"Mangum Tornado","rating":"EF2","peak_wind_mph":115,"path_length_mi":11.63,"path_width_yd":500,"fatalities":0,"injuries":1,"start_ts":1558391580000000,"end_ts":1558392900000000,"path":[{"lat":34.8428,"lon":-99.5522},{"lat":34.9862,"lon":-99.4997}],"summary":"The tornado developed southwest of Mangum and moved north across US-283, destroying an outbuilding and snapping numerous power poles. A home lost much of its roof north of town."}"#;
+        assert_eq!(expected, serialized_result);
+
+        let survey = result[1].as_ref().unwrap().survey.as_ref().unwrap();
+        assert_eq!(survey.rating, "EF0");
+        assert_eq!(survey.path_length_mi, Some(0.8));
+        assert_eq!(
+            survey.summary,
+            Some("A brief tornado damaged trees and a fence south of Willow.".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_should_skip_non_survey_pns() {
+        let product = get_product_from_file("../data/products/pns-spotter-training");
+        let result = parse(&product);
+        assert!(result.iter().all(Option::is_none));
+    }
+}
@@ -0,0 +1,176 @@
+use crate::nws_regexes::Regexes;
+use crate::parser_util::{cap, get_vtec_id, get_zones, short_time_to_ticks};
+use domain::{Event, EventType, FireHazard, FireHazardType, Location, Product};
+use regex::Regex;
+use util::safe_result;
+
+const BULLET_PATTERN: &str = r"\* (?P<name>AFFECTED AREA|WINDS?|(RELATIVE )?HUMIDITY|IMPACTS)\.{3}(?P<text>[\s\S]*?)(\n\n|$)";
+
+lazy_static! {
+    static ref BULLET_REGEX: Regex = Regex::new(BULLET_PATTERN).expect("Unable to compile regex");
+}
+
+/**
+ * Parses an NWS Red Flag Warning (RFW) product, which carries both Red Flag Warnings and Fire
+ * Weather Watches. Like WSWs, each segment covers one set of fire weather zones and parses to its
+ * own event.
+ */
+pub fn parse(product: &Product) -> Vec<Option<Event>> {
+    let event_ts = match util::ts_to_ticks(&product.issuance_time) {
+        Ok(event_ts) => event_ts,
+        Err(_) => return vec![],
+    };
+    let regexes = Regexes::new();
+
+    product
+        .product_text
+        .split("$$")
+        .map(|segment| parse_segment(product, segment, event_ts, &regexes))
+        .collect()
+}
+
+fn parse_segment(
+    product: &Product,
+    segment: &str,
+    event_ts: u64,
+    regexes: &Regexes,
+) -> Option<Event> {
+    // Watches are usually upgraded to warnings in the same segment, so prefer the line for the
+    // hazard that is in effect
+    let vtec = regexes
+        .vtec
+        .captures_iter(segment)
+        .filter_map(|x| Some((get_hazard_type(&x["phenomena"], &x["significance"])?, x)))
+        .min_by_key(|(_, x)| matches!(&x["action"], "UPG" | "CAN" | "EXP"));
+    let (hazard_type, vtec) = vtec?;
+
+    let action = cap(vtec.name("action")).to_string();
+    let begin = cap(vtec.name("begin"));
+    let valid_ts = if begin.starts_with("000000") {
+        None // already in effect
+    } else {
+        Some(safe_result!(short_time_to_ticks(begin)))
+    };
+    let expires_ts = Some(safe_result!(short_time_to_ticks(cap(vtec.name("end")))));
+    let vtec_id = get_vtec_id(&vtec[0], regexes)?;
+
+    let zones = regexes
+        .ugc_purge
+        .find(segment)
+        .map(|x| get_zones(x.as_str()))
+        .unwrap_or_default();
+
+    let mut affected_area = None;
+    let mut wind = None;
+    let mut humidity = None;
+    let mut impacts = None;
+    for bullet in BULLET_REGEX.captures_iter(segment) {
+        let text = Some(
+            bullet["text"]
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" "),
+        );
+        match &bullet["name"] {
+            "AFFECTED AREA" => affected_area = text,
+            "WIND" | "WINDS" => wind = text,
+            "IMPACTS" => impacts = text,
+            _ => humidity = text,
+        }
+    }
+
+    let wfo = product.issuing_office.to_string();
+    let title = format!("{} ({})", get_hazard_name(&hazard_type), wfo);
+
+    let fire = FireHazard {
+        hazard_type,
+        action,
+        vtec: vtec_id,
+        zones,
+        affected_area,
+        wind,
+        humidity,
+        impacts,
+    };
+
+    let location = Some(Location {
+        wfo: Some(wfo),
+        point: None,
+        poly: None,
+        county: None,
+    });
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type: EventType::NwsRfw,
+        expires_ts,
+        ext_uri: None,
+        fire: Some(fire),
+        fire_outlook: None,
+        ingest_ts: 0,
+        location,
+        md: None,
+        outlook: None,
+        report: None,
+        source_id: None,
+        survey: None,
+        text: Some(segment.trim().to_string()),
+        title,
+        tropical: None,
+        valid_ts,
+        warning: None,
+        watch: None,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+fn get_hazard_type(phenomena: &str, significance: &str) -> Option<FireHazardType> {
+    match (phenomena, significance) {
+        ("FW", "W") => Some(FireHazardType::RedFlagWarning),
+        ("FW", "A") => Some(FireHazardType::FireWeatherWatch),
+        _ => None,
+    }
+}
+
+fn get_hazard_name(hazard_type: &FireHazardType) -> &'static str {
+    match hazard_type {
+        FireHazardType::RedFlagWarning => "Red Flag Warning",
+        FireHazardType::FireWeatherWatch => "Fire Weather Watch",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::get_product_from_file;
+    use super::*;
+
+    #[test]
+    fn parse_red_flag_warning_and_fire_weather_watch() {
+        let product = get_product_from_file("../data/products/rfw-red-flag");
+        let result = parse(&product);
+        assert_eq!(result.len(), 3);
+
+        // The watch was upgraded in the same segment, so the warning wins
+        let event = result[0].as_ref().unwrap();
+        assert_eq!(event.event_type, EventType::NwsRfw);
+        assert_eq!(event.title, "Red Flag Warning (KABQ)");
+        assert_eq!(event.valid_ts, Some(1555952400000000));
+        assert_eq!(event.expires_ts, Some(1555984800000000));
+
+        let serialized_result = serde_json::to_string(&event.fire).unwrap();
+        let expected = r#"{"hazard_type":"RedFlagWarning","action":"NEW","vtec":"KABQ.FW.W.0005.2019","zones":["NMZ105","NMZ106","NMZ107","NMZ108"],"affected_area":"Fire weather zones 105, 106, 107 and 108.","wind":"Southwest 20 to 30 mph with gusts up to 50 mph.","humidity":"As low as 6 percent.","impacts":"Any fires that develop will likely spread rapidly. Outdoor burning is not recommended."}"#;
+        assert_eq!(expected, serialized_result);
+
+        let event = result[1].as_ref().unwrap();
+        assert_eq!(event.title, "Fire Weather Watch (KABQ)");
+        let serialized_result = serde_json::to_string(&event.fire).unwrap();
+        let expected = r#"{"hazard_type":"FireWeatherWatch","action":"NEW","vtec":"KABQ.FW.A.0004.2019","zones":["NMZ109","NMZ110"],"affected_area":"Fire weather zones 109 and 110.","wind":"West 20 to 25 mph with gusts up to 40 mph.","humidity":"As low as 8 percent.","impacts":null}"#;
+        assert_eq!(expected, serialized_result);
+
+        // The trailing signature is skipped
+        assert!(result[2].is_none());
+    }
+}
@@ -1,8 +1,36 @@
+use crate::gazetteer::get_anchor;
 use crate::nws_regexes::Regexes;
-use domain::{Event, EventType, Product, Watch, WatchStatus, WatchType};
+use crate::parser_util::{day_time_to_ticks, get_bearing, get_destination, get_utc_offset_hours};
+use chrono::prelude::*;
+use chrono::Duration;
+use domain::{Coordinates, Event, EventType, Location, Product, Watch, WatchStatus, WatchType};
+use regex::{Regex, RegexBuilder};
 use util;
 use util::safe_result;
 
+const EFFECTIVE_PATTERN: &str = r"from\s+(?P<hour>\d{1,2})(?P<minute>\d{2})\s+(?P<meridiem>[AP]M)\s+until\s+\d{3,4}\s+[AP]M\s+(?P<zone>[A-Z]{3,4})";
+const HAIL_PATTERN: &str = r"hail\s+surface\s+and\s+aloft\s+to\s+(?P<size>\d+(\.\d+)?)\s+inch";
+const GUST_PATTERN: &str = r"surface\s+wind\s+gusts\s+to\s+(?P<kt>\d+)\s+knots";
+const WATCH_AREA_PATTERN: &str = r"along\s+and\s+(?P<width>\d+)\s+statute\s+miles\s+(?P<sides>north\s+and\s+south|east\s+and\s+west|either\s+side)\s+of\s+a\s+line\s+from\s+(?P<line>[\s\S]+?)\.\s+for\s+a\s+complete";
+const LINE_SPLIT_PATTERN: &str = r"\s+to\s+";
+const ANCHOR_POINT_PATTERN: &str = r"^((?P<miles>\d+)\s+miles\s+(?P<direction>[a-z\s]+?)\s+of\s+)?(?P<name>[\s\S]+?)\s+(?P<state>[a-z]{2})$";
+const REPLACES_PATTERN: &str = r"replaces\s+(tornado|severe\s+thunderstorm)\s+watch\s+numbers?\s+(?P<ids>\d+((\s*(\.{3}|,|and)\s*)\d+)*)";
+
+lazy_static! {
+    static ref EFFECTIVE_REGEX: Regex = build_regex(EFFECTIVE_PATTERN);
+    static ref HAIL_REGEX: Regex = build_regex(HAIL_PATTERN);
+    static ref GUST_REGEX: Regex = build_regex(GUST_PATTERN);
+    static ref REPLACES_REGEX: Regex = build_regex(REPLACES_PATTERN);
+    static ref WATCH_AREA_REGEX: Regex = build_regex(WATCH_AREA_PATTERN);
+    static ref LINE_SPLIT_REGEX: Regex = build_regex(LINE_SPLIT_PATTERN);
+    static ref ANCHOR_POINT_REGEX: Regex = build_regex(ANCHOR_POINT_PATTERN);
+}
+
+/**
+ * Parses an SPC Severe Local Storm Watch (SEL). The hail size, wind gusts and storm motion come
+ * from the aviation section. Watches expire when the UGC line is purged, and the watch polygon is
+ * drawn from the parallelogram SPC describes relative to its anchor points.
+ */
 pub fn parse(product: &Product) -> Option<Event> {
     let regexes = Regexes::new();
     let text = &product.product_text;
@@ -48,34 +76,216 @@ pub fn parse(product: &Product) -> Option<Event> {
     };
     let title = format!("SPC {}{}{}Watch {}", verb, pds_text, watch_type_text, id);
 
+    let (valid_ts, expires_ts) = match status {
+        WatchStatus::Issued => (
+            get_valid_ts(text, event_ts),
+            regexes
+                .ugc_purge
+                .captures(text)
+                .and_then(|x| day_time_to_ticks(&x["purge"], event_ts).ok()),
+        ),
+        _ => (None, None),
+    };
+
+    let motion = regexes.storm_motion.captures(text);
+    let replaces_ids = match REPLACES_REGEX.captures(text) {
+        Some(replaces) => replaces["ids"]
+            .split(|x: char| !x.is_ascii_digit())
+            .filter_map(|x| x.parse().ok())
+            .collect(),
+        None => vec![],
+    };
+
+    let location = match status {
+        WatchStatus::Issued => get_watch_polygon(text).map(|poly| Location {
+            wfo: Some(product.issuing_office.to_string()),
+            point: None,
+            poly: Some(poly),
+            county: None,
+        }),
+        _ => None,
+    };
+
     let watch = Some(Watch {
         is_pds,
         id,
         issued_for,
         watch_type,
         status,
+        max_hail_size: HAIL_REGEX
+            .captures(text)
+            .and_then(|x| x["size"].parse().ok()),
+        max_wind_gust_kt: GUST_REGEX.captures(text).and_then(|x| x["kt"].parse().ok()),
+        motion_deg: motion.as_ref().and_then(|x| x["deg"].parse().ok()),
+        motion_kt: motion.as_ref().and_then(|x| x["kt"].parse().ok()),
+        replaces_ids,
+        probabilities: None,
     });
 
     let event = Event {
+        afd: None,
         event_ts,
         event_type: EventType::NwsSel,
-        expires_ts: None,
+        expires_ts,
         ext_uri: None,
+        fire: None,
+        fire_outlook: None,
         ingest_ts: 0,
-        location: None,
+        location,
         md: None,
         outlook: None,
         report: None,
+        source_id: None,
+        survey: None,
         text: Some(text.to_string()),
         title,
-        valid_ts: None,
+        tropical: None,
+        valid_ts,
         warning: None,
         watch,
+        winter: None,
     };
 
     Some(event)
 }
 
+/**
+ * The start of the watch is only given in local time, ie. "from 115 PM until 800 PM EDT", so it's
+ * taken to be on the local day the watch was issued.
+ */
+fn get_valid_ts(text: &str, event_ts: u64) -> Option<u64> {
+    let effective = EFFECTIVE_REGEX.captures(text)?;
+    let offset_hours = get_utc_offset_hours(&effective["zone"])?;
+    let hour = effective["hour"].parse::<u32>().ok()? % 12;
+    let hour = if effective["meridiem"].eq_ignore_ascii_case("PM") {
+        hour + 12
+    } else {
+        hour
+    };
+    let minute = effective["minute"].parse::<u32>().ok()?;
+
+    let issued = Utc
+        .timestamp_opt((event_ts / 1_000_000) as i64, 0)
+        .single()?;
+    let local_date = (issued + Duration::hours(offset_hours)).naive_utc().date();
+    let local = local_date.and_hms_opt(hour, minute, 0)?;
+    let mut valid = Utc.from_utc_datetime(&(local - Duration::hours(offset_hours)));
+
+    // ie. issued at 1155 PM for a watch starting at 1200 AM
+    if valid < issued - Duration::hours(12) {
+        valid = valid + Duration::days(1);
+    }
+
+    Some(valid.timestamp() as u64 * 1_000_000)
+}
+
+fn build_regex(pattern: &str) -> Regex {
+    RegexBuilder::new(pattern)
+        .case_insensitive(true)
+        .build()
+        .expect("Unable to compile regex")
+}
+
+/**
+ * Draws the watch outline from its description, ie. "along and 70 statute miles north and south of
+ * a line from 40 miles south southwest of Terre Haute IN to 20 miles south southeast of Columbus
+ * OH". The line is offset to either side, giving a parallelogram. Returns None if the description
+ * is missing or an anchor point isn't in the gazetteer.
+ */
+fn get_watch_polygon(text: &str) -> Option<Vec<Coordinates>> {
+    let area = WATCH_AREA_REGEX.captures(text)?;
+    let width = area["width"].parse::<f64>().ok()?;
+    let line = LINE_SPLIT_REGEX
+        .split(&area["line"])
+        .map(get_anchor_point)
+        .collect::<Option<Vec<Coordinates>>>()?;
+    if line.len() < 2 {
+        return None;
+    }
+
+    let sides = area["sides"].to_lowercase();
+    let bearing = if sides.starts_with("north") {
+        0.0
+    } else if sides.starts_with("east") {
+        90.0
+    } else {
+        get_bearing(&line[0], &line[line.len() - 1]) - 90.0
+    };
+
+    let side = line.iter().map(|x| get_destination(x, bearing, width));
+    let other_side = line
+        .iter()
+        .rev()
+        .map(|x| get_destination(x, bearing + 180.0, width));
+
+    Some(
+        side.chain(other_side)
+            .map(|x| Coordinates {
+                lat: round(x.lat),
+                lon: round(x.lon),
+            })
+            .collect(),
+    )
+}
+
+/**
+ * Parses a point relative to an anchor, ie. "40 miles south southwest of Terre Haute IN".
+ */
+fn get_anchor_point(text: &str) -> Option<Coordinates> {
+    let point = ANCHOR_POINT_REGEX.captures(text.trim())?;
+    let anchor = match get_anchor(&point["name"], &point["state"]) {
+        Some(anchor) => anchor,
+        None => {
+            warn!("Unknown watch anchor point: {}", text);
+            return None;
+        }
+    };
+
+    match point.name("miles") {
+        Some(miles) => {
+            let bearing = get_compass_bearing(&point["direction"])?;
+            Some(get_destination(
+                &anchor,
+                bearing,
+                miles.as_str().parse().ok()?,
+            ))
+        }
+        None => Some(anchor),
+    }
+}
+
+/**
+ * Converts a 16-point compass direction, ie. "south southwest", to a bearing in degrees.
+ */
+fn get_compass_bearing(direction: &str) -> Option<f64> {
+    let abbreviation: String = direction
+        .to_lowercase()
+        .split_whitespace()
+        .map(|word| {
+            word.replace("north", "N")
+                .replace("south", "S")
+                .replace("east", "E")
+                .replace("west", "W")
+        })
+        .collect();
+
+    let points = [
+        "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW",
+        "NW", "NNW",
+    ];
+    match points.iter().position(|x| *x == abbreviation) {
+        Some(index) => Some(index as f64 * 22.5),
+        None => {
+            warn!("Unknown compass direction: {}", direction);
+            None
+        }
+    }
+}
+
+fn round(value: f32) -> f32 {
+    (value * 100.0).round() / 100.0
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::test_util::get_product_from_file;
@@ -86,7 +296,7 @@ mod tests {
         let product = get_product_from_file("../data/products/sel-tor-watch-issued");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1522775580000000,"event_type":"NwsSel","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":null,"md":null,"outlook":null,"report":null,"text":"\n281 \nWWUS20 KWNS 031713\nSEL6  \nSPC WW 031713\nINZ000-KYZ000-OHZ000-040000-\n\nURGENT - IMMEDIATE BROADCAST REQUESTED\nTornado Watch Number 26\nNWS Storm Prediction Center Norman OK\n115 PM EDT Tue Apr 3 2018\n\nThe NWS Storm Prediction Center has issued a\n\n* Tornado Watch for portions of \n  Southern and Central Indiana\n  Northern Kentucky\n  Western and Central Ohio\n\n* Effective this Tuesday afternoon and evening from 115 PM until\n  800 PM EDT.\n\n* Primary threats include...\n  A few tornadoes likely with a couple intense tornadoes possible\n  Scattered damaging wind gusts to 70 mph likely\n  Scattered large hail and isolated very large hail events to 2\n    inches in diameter possible\n\nSUMMARY...Thunderstorms are intensifying along the IL/IN border, and\nwill track eastward across the watch area through the afternoon. \nConditions appear favorable for supercell storms capable of large\nhail, damaging winds, and perhaps a strong tornado or two.\n\nThe tornado watch area is approximately along and 70 statute miles\nnorth and south of a line from 40 miles south southwest of Terre\nHaute IN to 20 miles south southeast of Columbus OH. For a complete\ndepiction of the watch see the associated watch outline update\n(WOUS64 KWNS WOU6).\n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nREMEMBER...A Tornado Watch means conditions are favorable for\ntornadoes and severe thunderstorms in and close to the watch\narea. Persons in these areas should be on the lookout for\nthreatening weather conditions and listen for later statements\nand possible warnings.\n\n&&\n\nOTHER WATCH INFORMATION...CONTINUE...WW 25...\n\nAVIATION...Tornadoes and a few severe thunderstorms with hail\nsurface and aloft to 2 inches. Extreme turbulence and surface wind\ngusts to 60 knots. A few cumulonimbi with maximum tops to 450. Mean\nstorm motion vector 24035.\n\n...Hart\n\n","title":"SPC issues Tor Watch 26","valid_ts":null,"warning":null,"watch":{"is_pds":false,"id":26,"watch_type":"Tornado","status":"Issued","issued_for":"Southern and Central Indiana, Northern Kentucky, Western and Central Ohio"}}"#;
+        let expected = r#"{"afd":null,"event_ts":1522775580000000,"event_type":"NwsSel","expires_ts":1522800000000000,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KWNS","point":null,"poly":[{"lat":39.95,"lon":-87.69},{"lat":40.71,"lon":-82.86},{"lat":38.68,"lon":-82.86},{"lat":37.92,"lon":-87.69}],"county":null},"md":null,"outlook":null,"report":null,"source_id":null,"survey":null,"text":"\n281 \nWWUS20 KWNS 031713\nSEL6  \nSPC WW 031713\nINZ000-KYZ000-OHZ000-040000-\n\nURGENT - IMMEDIATE BROADCAST REQUESTED\nTornado Watch Number 26\nNWS Storm Prediction Center Norman OK\n115 PM EDT Tue Apr 3 2018\n\nThe NWS Storm Prediction Center has issued a\n\n* Tornado Watch for portions of \n  Southern and Central Indiana\n  Northern Kentucky\n  Western and Central Ohio\n\n* Effective this Tuesday afternoon and evening from 115 PM until\n  800 PM EDT.\n\n* Primary threats include...\n  A few tornadoes likely with a couple intense tornadoes possible\n  Scattered damaging wind gusts to 70 mph likely\n  Scattered large hail and isolated very large hail events to 2\n    inches in diameter possible\n\nSUMMARY...Thunderstorms are intensifying along the IL/IN border, and\nwill track eastward across the watch area through the afternoon. \nConditions appear favorable for supercell storms capable of large\nhail, damaging winds, and perhaps a strong tornado or two.\n\nThe tornado watch area is approximately along and 70 statute miles\nnorth and south of a line from 40 miles south southwest of Terre\nHaute IN to 20 miles south southeast of Columbus OH. For a complete\ndepiction of the watch see the associated watch outline update\n(WOUS64 KWNS WOU6).\n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nREMEMBER...A Tornado Watch means conditions are favorable for\ntornadoes and severe thunderstorms in and close to the watch\narea. Persons in these areas should be on the lookout for\nthreatening weather conditions and listen for later statements\nand possible warnings.\n\n&&\n\nOTHER WATCH INFORMATION...CONTINUE...WW 25...\n\nAVIATION...Tornadoes and a few severe thunderstorms with hail\nsurface and aloft to 2 inches. Extreme turbulence and surface wind\ngusts to 60 knots. A few cumulonimbi with maximum tops to 450. Mean\nstorm motion vector 24035.\n\n...Hart\n\n","title":"SPC issues Tor Watch 26","tropical":null,"valid_ts":1522775700000000,"warning":null,"watch":{"is_pds":false,"id":26,"watch_type":"Tornado","status":"Issued","issued_for":"Southern and Central Indiana, Northern Kentucky, Western and Central Ohio","max_hail_size":2.0,"max_wind_gust_kt":60,"motion_deg":240,"motion_kt":35,"replaces_ids":[],"probabilities":null},"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -95,7 +305,7 @@ mod tests {
         let product = get_product_from_file("../data/products/sel-tor-pds-watch");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1523645220000000,"event_type":"NwsSel","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":null,"md":null,"outlook":null,"report":null,"text":"\n136 \nWWUS20 KWNS 131847\nSEL0  \nSPC WW 131847\nARZ000-LAZ000-OKZ000-TXZ000-140300-\n\nURGENT - IMMEDIATE BROADCAST REQUESTED\nTornado Watch Number 40\nNWS Storm Prediction Center Norman OK\n150 PM CDT Fri Apr 13 2018\n\nThe NWS Storm Prediction Center has issued a\n\n* Tornado Watch for portions of \n  Much of Arkansas\n  Northwest Louisiana\n  Southeast Oklahoma\n  Northeast Texas\n\n* Effective this Friday afternoon and evening from 150 PM until\n  1000 PM CDT.\n\n...THIS IS A PARTICULARLY DANGEROUS SITUATION...\n\n* Primary threats include...\n  Numerous tornadoes expected with a few intense tornadoes likely\n  Widespread large hail and isolated very large hail events to 2.5\n    inches in diameter likely\n  Widespread damaging wind gusts to 70 mph likely\n\nSUMMARY...Intense thunderstorms are expected to track across the\nwatch area this afternoon and early evening, posing a risk of\ntornadoes, large hail and damaging winds.  Strong tornadoes are\npossible.  Multiple rounds of severe storms are expected across this\nregion.\n\nThe tornado watch area is approximately along and 70 statute miles\neast and west of a line from 70 miles south of Longview TX to 20\nmiles northeast of Flippin AR. For a complete depiction of the watch\nsee the associated watch outline update (WOUS64 KWNS WOU0).\n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nREMEMBER...A Tornado Watch means conditions are favorable for\ntornadoes and severe thunderstorms in and close to the watch\narea. Persons in these areas should be on the lookout for\nthreatening weather conditions and listen for later statements\nand possible warnings.\n\n&&\n\nOTHER WATCH INFORMATION...CONTINUE...WW 39...\n\nAVIATION...Tornadoes and a few severe thunderstorms with hail\nsurface and aloft to 2.5 inches. Extreme turbulence and surface wind\ngusts to 60 knots. A few cumulonimbi with maximum tops to 500. Mean\nstorm motion vector 24035.\n\n...Hart\n\n","title":"SPC issues PDS Tor Watch 40","valid_ts":null,"warning":null,"watch":{"is_pds":true,"id":40,"watch_type":"Tornado","status":"Issued","issued_for":"Much of Arkansas, Northwest Louisiana, Southeast Oklahoma, Northeast Texas"}}"#;
+        let expected = r#"{"afd":null,"event_ts":1523645220000000,"event_type":"NwsSel","expires_ts":1523674800000000,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KWNS","point":null,"poly":[{"lat":31.48,"lon":-93.55},{"lat":36.48,"lon":-91.09},{"lat":36.48,"lon":-93.61},{"lat":31.48,"lon":-95.93}],"county":null},"md":null,"outlook":null,"report":null,"source_id":null,"survey":null,"text":"\n136 \nWWUS20 KWNS 131847\nSEL0  \nSPC WW 131847\nARZ000-LAZ000-OKZ000-TXZ000-140300-\n\nURGENT - IMMEDIATE BROADCAST REQUESTED\nTornado Watch Number 40\nNWS Storm Prediction Center Norman OK\n150 PM CDT Fri Apr 13 2018\n\nThe NWS Storm Prediction Center has issued a\n\n* Tornado Watch for portions of \n  Much of Arkansas\n  Northwest Louisiana\n  Southeast Oklahoma\n  Northeast Texas\n\n* Effective this Friday afternoon and evening from 150 PM until\n  1000 PM CDT.\n\n...THIS IS A PARTICULARLY DANGEROUS SITUATION...\n\n* Primary threats include...\n  Numerous tornadoes expected with a few intense tornadoes likely\n  Widespread large hail and isolated very large hail events to 2.5\n    inches in diameter likely\n  Widespread damaging wind gusts to 70 mph likely\n\nSUMMARY...Intense thunderstorms are expected to track across the\nwatch area this afternoon and early evening, posing a risk of\ntornadoes, large hail and damaging winds.  Strong tornadoes are\npossible.  Multiple rounds of severe storms are expected across this\nregion.\n\nThe tornado watch area is approximately along and 70 statute miles\neast and west of a line from 70 miles south of Longview TX to 20\nmiles northeast of Flippin AR. For a complete depiction of the watch\nsee the associated watch outline update (WOUS64 KWNS WOU0).\n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nREMEMBER...A Tornado Watch means conditions are favorable for\ntornadoes and severe thunderstorms in and close to the watch\narea. Persons in these areas should be on the lookout for\nthreatening weather conditions and listen for later statements\nand possible warnings.\n\n&&\n\nOTHER WATCH INFORMATION...CONTINUE...WW 39...\n\nAVIATION...Tornadoes and a few severe thunderstorms with hail\nsurface and aloft to 2.5 inches. Extreme turbulence and surface wind\ngusts to 60 knots. A few cumulonimbi with maximum tops to 500. Mean\nstorm motion vector 24035.\n\n...Hart\n\n","title":"SPC issues PDS Tor Watch 40","tropical":null,"valid_ts":1523645400000000,"warning":null,"watch":{"is_pds":true,"id":40,"watch_type":"Tornado","status":"Issued","issued_for":"Much of Arkansas, Northwest Louisiana, Southeast Oklahoma, Northeast Texas","max_hail_size":2.5,"max_wind_gust_kt":60,"motion_deg":240,"motion_kt":35,"replaces_ids":[],"probabilities":null},"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -104,7 +314,7 @@ mod tests {
         let product = get_product_from_file("../data/products/sel-svr-watch");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1522768980000000,"event_type":"NwsSel","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":null,"md":null,"outlook":null,"report":null,"text":"\n727 \nWWUS20 KWNS 031523\nSEL5  \nSPC WW 031523\nARZ000-LAZ000-OKZ000-TXZ000-032300-\n\nURGENT - IMMEDIATE BROADCAST REQUESTED\nSevere Thunderstorm Watch Number 25\nNWS Storm Prediction Center Norman OK\n1025 AM CDT Tue Apr 3 2018\n\nThe NWS Storm Prediction Center has issued a\n\n* Severe Thunderstorm Watch for portions of \n  Southwest Arkansas\n  Northwest Louisiana\n  Southeast Oklahoma\n  Central and Northeast Texas\n\n* Effective this Tuesday morning and evening from 1025 AM until\n  600 PM CDT.\n\n* Primary threats include...\n  Scattered large hail likely with isolated very large hail events\n    to 2.5 inches in diameter possible\n  Scattered damaging wind gusts to 70 mph possible\n\nSUMMARY...Thunderstorms are intensifying over central Texas, and\nwill spread northeastward across the watch area through the\nafternoon.  Other storms will form along an approaching cold front. \nLarge hail and damaging winds will be possible in the strongest\ncells.\n\nThe severe thunderstorm watch area is approximately along and 75\nstatute miles north and south of a line from 50 miles west of Temple\nTX to 40 miles northeast of Shreveport LA. For a complete depiction\nof the watch see the associated watch outline update (WOUS64 KWNS\nWOU5).\n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nREMEMBER...A Severe Thunderstorm Watch means conditions are\nfavorable for severe thunderstorms in and close to the watch area.\nPersons in these areas should be on the lookout for threatening\nweather conditions and listen for later statements and possible\nwarnings. Severe thunderstorms can and occasionally do produce\ntornadoes.\n\n&&\n\nAVIATION...A few severe thunderstorms with hail surface and aloft to\n2.5 inches. Extreme turbulence and surface wind gusts to 60 knots. A\nfew cumulonimbi with maximum tops to 500. Mean storm motion vector\n26030.\n\n...Hart\n\n","title":"SPC issues Tstm Watch 25","valid_ts":null,"warning":null,"watch":{"is_pds":false,"id":25,"watch_type":"SevereThunderstorm","status":"Issued","issued_for":"Southwest Arkansas, Northwest Louisiana, Southeast Oklahoma, Central and Northeast Texas"}}"#;
+        let expected = r#"{"afd":null,"event_ts":1522768980000000,"event_type":"NwsSel","expires_ts":1522796400000000,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KWNS","point":null,"poly":[{"lat":32.18,"lon":-98.19},{"lat":34.02,"lon":-93.26},{"lat":31.85,"lon":-93.26},{"lat":30.01,"lon":-98.19}],"county":null},"md":null,"outlook":null,"report":null,"source_id":null,"survey":null,"text":"\n727 \nWWUS20 KWNS 031523\nSEL5  \nSPC WW 031523\nARZ000-LAZ000-OKZ000-TXZ000-032300-\n\nURGENT - IMMEDIATE BROADCAST REQUESTED\nSevere Thunderstorm Watch Number 25\nNWS Storm Prediction Center Norman OK\n1025 AM CDT Tue Apr 3 2018\n\nThe NWS Storm Prediction Center has issued a\n\n* Severe Thunderstorm Watch for portions of \n  Southwest Arkansas\n  Northwest Louisiana\n  Southeast Oklahoma\n  Central and Northeast Texas\n\n* Effective this Tuesday morning and evening from 1025 AM until\n  600 PM CDT.\n\n* Primary threats include...\n  Scattered large hail likely with isolated very large hail events\n    to 2.5 inches in diameter possible\n  Scattered damaging wind gusts to 70 mph possible\n\nSUMMARY...Thunderstorms are intensifying over central Texas, and\nwill spread northeastward across the watch area through the\nafternoon.  Other storms will form along an approaching cold front. \nLarge hail and damaging winds will be possible in the strongest\ncells.\n\nThe severe thunderstorm watch area is approximately along and 75\nstatute miles north and south of a line from 50 miles west of Temple\nTX to 40 miles northeast of Shreveport LA. For a complete depiction\nof the watch see the associated watch outline update (WOUS64 KWNS\nWOU5).\n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nREMEMBER...A Severe Thunderstorm Watch means conditions are\nfavorable for severe thunderstorms in and close to the watch area.\nPersons in these areas should be on the lookout for threatening\nweather conditions and listen for later statements and possible\nwarnings. Severe thunderstorms can and occasionally do produce\ntornadoes.\n\n&&\n\nAVIATION...A few severe thunderstorms with hail surface and aloft to\n2.5 inches. Extreme turbulence and surface wind gusts to 60 knots. A\nfew cumulonimbi with maximum tops to 500. Mean storm motion vector\n26030.\n\n...Hart\n\n","title":"SPC issues Tstm Watch 25","tropical":null,"valid_ts":1522769100000000,"warning":null,"watch":{"is_pds":false,"id":25,"watch_type":"SevereThunderstorm","status":"Issued","issued_for":"Southwest Arkansas, Northwest Louisiana, Southeast Oklahoma, Central and Northeast Texas","max_hail_size":2.5,"max_wind_gust_kt":60,"motion_deg":260,"motion_kt":30,"replaces_ids":[],"probabilities":null},"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -113,7 +323,36 @@ mod tests {
         let product = get_product_from_file("../data/products/sel-svr-watch-cancelled");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1556002980000000,"event_type":"NwsSel","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":null,"md":null,"outlook":null,"report":null,"text":"\n000\nWWUS20 KWNS 230703\nSEL4  \nSPC WW 230703\nOKZ000-TXZ000-230700-\n\nURGENT - IMMEDIATE BROADCAST REQUESTED\nSEVERE THUNDERSTORM WATCH - NUMBER 94 \nNWS STORM PREDICTION CENTER NORMAN OK \n203 AM CDT TUE APR 23 2019\n\nTHE NWS STORM PREDICTION CENTER HAS CANCELLED \nSEVERE THUNDERSTORM WATCH NUMBER 94 ISSUED AT 635 PM CDT FOR PORTIONS OF\n\n         OKLAHOMA\n         TEXAS\n\n","title":"SPC cancels Tstm Watch 94","valid_ts":null,"warning":null,"watch":{"is_pds":false,"id":94,"watch_type":"SevereThunderstorm","status":"Cancelled","issued_for":null}}"#;
+        let expected = r#"{"afd":null,"event_ts":1556002980000000,"event_type":"NwsSel","expires_ts":null,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":null,"md":null,"outlook":null,"report":null,"source_id":null,"survey":null,"text":"\n000\nWWUS20 KWNS 230703\nSEL4  \nSPC WW 230703\nOKZ000-TXZ000-230700-\n\nURGENT - IMMEDIATE BROADCAST REQUESTED\nSEVERE THUNDERSTORM WATCH - NUMBER 94 \nNWS STORM PREDICTION CENTER NORMAN OK \n203 AM CDT TUE APR 23 2019\n\nTHE NWS STORM PREDICTION CENTER HAS CANCELLED \nSEVERE THUNDERSTORM WATCH NUMBER 94 ISSUED AT 635 PM CDT FOR PORTIONS OF\n\n         OKLAHOMA\n         TEXAS\n\n","title":"SPC cancels Tstm Watch 94","tropical":null,"valid_ts":null,"warning":null,"watch":{"is_pds":false,"id":94,"watch_type":"SevereThunderstorm","status":"Cancelled","issued_for":null,"max_hail_size":null,"max_wind_gust_kt":null,"motion_deg":null,"motion_kt":null,"replaces_ids":[],"probabilities":null},"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
+
+    #[test]
+    fn parse_should_get_replaced_watches() {
+        let product = get_product_from_file("../data/products/sel-tor-watch-replaces");
+        let watch = parse(&product).unwrap().watch.unwrap();
+        assert_eq!(watch.replaces_ids, vec![23, 24]);
+    }
+
+    #[test]
+    fn get_valid_ts_should_roll_over_midnight() {
+        // 2018-04-04T04:55:00Z, or 1155 PM CDT
+        let event_ts = 1522817700000000;
+        let text = "* Effective this Tuesday night from 1200 AM until\n  600 AM CDT.";
+        assert_eq!(get_valid_ts(text, event_ts), Some(1522818000000000));
+        let text = "* Effective this Tuesday night from 1155 PM until\n  600 AM CDT.";
+        assert_eq!(get_valid_ts(text, event_ts), Some(event_ts));
+    }
+
+    #[test]
+    fn get_watch_polygon_should_need_known_anchors() {
+        assert_eq!(get_compass_bearing("south southwest"), Some(202.5));
+        assert_eq!(get_compass_bearing("NORTHEAST"), Some(45.0));
+        assert_eq!(get_compass_bearing("up"), None);
+
+        let text = "along and 50 statute miles either side of a line from 10 miles west of\nNowhere KS to Wichita KS. For a complete depiction";
+        assert_eq!(get_watch_polygon(text), None);
+        let text = "along and 50 statute miles either side of a line from 10 miles west of\nSalina KS to Wichita KS. For a complete depiction";
+        assert_eq!(get_watch_polygon(text).unwrap().len(), 4);
+    }
 }
@@ -0,0 +1,152 @@
+use crate::nws_regexes::Regexes;
+use crate::parser_util::{get_polygon, get_storm_motion, get_vtec_id, short_time_to_ticks};
+use domain::{Event, EventType, Location, Product, Warning};
+use regex::Regex;
+use util::safe_result;
+
+const HAZARD_PATTERN: &str = r"HAZARD\.{3}([\s\S]*?)\n\n";
+
+lazy_static! {
+    static ref HAZARD_REGEX: Regex = Regex::new(HAZARD_PATTERN).expect("Unable to compile regex");
+}
+
+/**
+ * Parses an NWS Special Marine Warning (SMW). These are the marine equivalent of SVRs, issued for
+ * coastal waters and the Great Lakes. The HAIL, WIND and WATERSPOUT tags at the end of the product
+ * are kept on the warning.
+ */
+pub fn parse(product: &Product) -> Option<Event> {
+    let regexes = Regexes::new();
+    let text = &product.product_text;
+    let valid_range = regexes.valid.captures(text)?;
+    let issued_for = regexes.warning_for.captures(text)?[1]
+        .replace("\n", "")
+        .replace("...", ",")
+        .replace("  ", " ");
+    let issued_for = issued_for.trim().trim_end_matches(',').to_string();
+
+    let wfo = product.issuing_office.to_string();
+    let valid_ts = Some(safe_result!(short_time_to_ticks(&valid_range[1])));
+    let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
+    let motion = get_storm_motion(text, event_ts, &regexes);
+    let expires_ts = Some(safe_result!(short_time_to_ticks(&valid_range[2])));
+    let title = format!("Special Marine Warning ({})", wfo);
+
+    let location = Some(Location {
+        wfo: Some(wfo),
+        point: motion.as_ref().and_then(|x| x.points.first().copied()),
+        poly: Some(get_polygon(text, &regexes)),
+        county: None,
+    });
+
+    let lower_case_text = text.to_lowercase();
+
+    let warning = Some(Warning {
+        is_pds: lower_case_text.contains("particularly dangerous situation"),
+        was_observed: None,
+        is_tor_emergency: None,
+        motion_deg: motion.as_ref().map(|x| x.deg),
+        motion_kt: motion.as_ref().map(|x| x.kt),
+        source: regexes.source.captures(text).map(|x| x["src"].to_string()),
+        issued_for,
+        time: motion.as_ref().map(|x| x.time),
+        vtec: get_vtec_id(text, &regexes),
+        hail_size: regexes
+            .hail_tag
+            .captures(text)
+            .and_then(|x| x["size"].parse().ok()),
+        wind_gust: regexes
+            .wind_tag
+            .captures(text)
+            .map(|x| x["gust"].to_string()),
+        waterspout: get_waterspout(text, &regexes),
+        mpd_source_id: None,
+        storm_points: motion.map(|x| x.points).unwrap_or_default(),
+        severity: None,
+    });
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type: EventType::NwsSmw,
+        expires_ts,
+        ext_uri: None,
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0,
+        location,
+        md: None,
+        outlook: None,
+        report: None,
+        source_id: None,
+        survey: None,
+        text: Some(text.to_string()),
+        title,
+        tropical: None,
+        valid_ts,
+        warning,
+        watch: None,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+/**
+ * Older SMWs have no WATERSPOUT tag, so fall back to the hazard line. The rest of the text can't
+ * be used since the call to action mentions waterspouts in most SMWs.
+ */
+fn get_waterspout(text: &str, regexes: &Regexes) -> Option<String> {
+    if let Some(tag) = regexes.waterspout_tag.captures(text) {
+        return Some(tag["waterspout"].to_string());
+    }
+
+    let hazard = HAZARD_REGEX.captures(text)?;
+    if hazard[1].to_lowercase().contains("waterspout") {
+        Some("POSSIBLE".to_string())
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::get_product_from_file;
+    use super::*;
+    use domain::Coordinates;
+
+    #[test]
+    fn parse_smw_product_happy_path() {
+        let product = get_product_from_file("../data/products/smw");
+        let result = parse(&product).unwrap();
+        assert_eq!(result.event_type, EventType::NwsSmw);
+        assert_eq!(result.title, "Special Marine Warning (KMKX)");
+        assert_eq!(result.valid_ts, Some(1563226560000000));
+        assert_eq!(result.expires_ts, Some(1563229800000000));
+
+        let location = result.location.unwrap();
+        assert_eq!(location.poly.unwrap().len(), 8);
+        assert_eq!(
+            location.point,
+            Some(Coordinates {
+                lat: 43.72,
+                lon: -87.57
+            })
+        );
+
+        let serialized_result = serde_json::to_string(&result.warning).unwrap();
+        let expected = r#"{"is_pds":false,"is_tor_emergency":null,"was_observed":null,"issued_for":"Sheboygan to Port Washington WI, Port Washington to North Point Light WI, North Point Light to Wind Point WI, Wind Point WI to Winthrop Harbor IL","motion_deg":270,"motion_kt":25,"source":"Radar indicated","time":1563226560000000,"vtec":"KMKX.MA.W.0012.2019","hail_size":0.75,"wind_gust":">34KTS","waterspout":"POSSIBLE","mpd_source_id":null,"storm_points":[{"lat":43.72,"lon":-87.57},{"lat":42.58,"lon":-87.71}],"severity":null}"#;
+        assert_eq!(expected, serialized_result);
+    }
+
+    #[test]
+    fn get_waterspout_should_fall_back_to_hazard() {
+        let regexes = Regexes::new();
+        let text = "  HAZARD...Waterspouts and wind gusts 34 knots or greater.\n\n  SOURCE...";
+        assert_eq!(get_waterspout(text, &regexes), Some("POSSIBLE".to_string()));
+
+        let text =
+            "  HAZARD...Wind gusts 34 knots or greater.\n\nThunderstorms can produce waterspouts.";
+        assert_eq!(get_waterspout(text, &regexes), None);
+    }
+}
@@ -0,0 +1,177 @@
+use crate::sn_parser::Hazard;
+use domain::{Coordinates, Event, EventType, Location, Report, SnReport, Units};
+use util;
+use util::safe_result;
+
+/**
+ * Parses a report from the SpotterNetwork JSON API into the same event the placefile parser
+ * creates, so clients can't tell which source a report came from.
+ * NOTE: The `SnReport` schema is unverified. It hasn't been checked against real responses from
+ * https://www.spotternetwork.org/api/reports, which is why the placefile is still the default.
+ */
+pub fn parse(sn_report: &SnReport) -> Option<Event> {
+    let hazard = Hazard::get_by_code(&sn_report.report_type.to_string());
+    let notes = sn_report
+        .notes
+        .as_ref()
+        .map(|x| x.trim())
+        .filter(|x| !x.is_empty() && *x != "None");
+
+    // Skip Other/None reports since they're essentially worthless
+    if hazard == Hazard::Other && notes.is_none() {
+        return None;
+    }
+
+    let mut report = Report {
+        hazard: hazard.to_hazard_type(),
+        lsr_source_id: None,
+        magnitude: None,
+        report_ts: None, // not set for SN reports
+        reporter: sn_report.reporter.to_string(),
+        units: None,
+        was_measured: None,
+    };
+
+    let mut title = format!("Report: {}", hazard);
+
+    if let Some(mph) = sn_report.wind_speed {
+        title = format!("Report: {}mph {}", mph, hazard);
+        report.magnitude = Some(f32::from(mph));
+        report.units = Some(Units::Mph);
+        if sn_report.wind_measured {
+            report.was_measured = Some(true);
+        }
+    } else if let Some(size) = sn_report.hail_size {
+        title = format!("Report: {}\" {}", size, hazard);
+        report.magnitude = Some(size);
+        report.units = Some(Units::Inches);
+    }
+
+    let location = Some(Location {
+        county: None,
+        wfo: None,
+        point: Some(Coordinates {
+            lat: sn_report.lat,
+            lon: sn_report.lon,
+        }),
+        poly: None,
+    });
+
+    let event_ts = safe_result!(util::rfc3339_to_ticks(&sn_report.report_time));
+
+    let text = match notes {
+        Some(notes) => format!("{} reported by {}. {}", hazard, sn_report.reporter, notes),
+        None => format!("{} reported by {}", hazard, sn_report.reporter),
+    };
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type: EventType::SnReport,
+        expires_ts: None,
+        ext_uri: None,
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0, // set when storing
+        location,
+        md: None,
+        outlook: None,
+        report: Some(report),
+        source_id: Some(format!("sn-api:{}", sn_report.id)),
+        survey: None,
+        text: Some(text),
+        title,
+        tropical: None,
+        valid_ts: None,
+        warning: None,
+        watch: None,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::{HazardType, SnReportsResult};
+    use std::fs::File;
+    use std::io::Read;
+
+    fn get_reports() -> Vec<SnReport> {
+        let mut f = File::open("../data/sn-reports.json").expect("reports file not found");
+        let mut contents = String::new();
+        f.read_to_string(&mut contents)
+            .expect("something went wrong reading the file");
+        let result: SnReportsResult = serde_json::from_str(&contents).unwrap();
+        result.reports
+    }
+
+    #[test]
+    fn parse_should_return_an_event_with_all_required_fields() {
+        let reports = get_reports();
+        let event = parse(&reports[1]).unwrap();
+
+        assert_eq!(
+            event,
+            Event {
+                afd: None,
+                event_ts: 1537490040000000,
+                event_type: EventType::SnReport,
+                expires_ts: None,
+                ext_uri: None,
+                fire: None,
+                fire_outlook: None,
+                ingest_ts: 0,
+                location: Some(Location {
+                    county: None,
+                    wfo: None,
+                    point: Some(Coordinates {
+                        lat: 41.338715,
+                        lon: -96.05956
+                    }),
+                    poly: None
+                }),
+                md: None,
+                outlook: None,
+                report: Some(Report {
+                    reporter: "Test User".to_string(),
+                    hazard: HazardType::Wind,
+                    magnitude: Some(60.0),
+                    units: Some(Units::Mph),
+                    was_measured: Some(true),
+                    report_ts: None,
+                    lsr_source_id: None
+                }),
+                source_id: Some("sn-api:1181043".to_string()),
+                survey: None,
+                text: Some("Wind reported by Test User. Wind gusting to 63mph".to_string()),
+                title: "Report: 60mph Wind".to_string(),
+                tropical: None,
+                valid_ts: None,
+                warning: None,
+                watch: None,
+                winter: None,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_should_match_placefile_parser() {
+        let reports = get_reports();
+        let placefile_report = r#"Icon: 47.617706,-111.215248,000,4,4,"Reported By: Test User\nHail\nTime: 2018-09-20 22:49:29 UTC\nSize: 0.75" (Penny)\nNotes: None""#;
+        let expected = crate::sn_parser::parse(placefile_report);
+        let mut result = parse(&reports[0]);
+        if let Some(event) = result.as_mut() {
+            assert_eq!(event.source_id, Some("sn-api:1181042".to_string()));
+            event.source_id = None;
+        }
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn parse_should_skip_empty_other_reports() {
+        let reports = get_reports();
+        assert!(parse(&reports[2]).is_none());
+    }
+}
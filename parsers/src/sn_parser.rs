@@ -29,6 +29,7 @@ pub fn parse(report: &str) -> Option<Event> {
 
     let mut report = Report {
         hazard: hazard.to_hazard_type(),
+        lsr_source_id: None,
         magnitude: None,
         report_ts: None, // not set for SN reports
         reporter: reporter.to_string(),
@@ -89,20 +90,27 @@ pub fn parse(report: &str) -> Option<Event> {
     };
 
     let event = Event {
+        afd: None,
         event_ts,
         event_type: EventType::SnReport,
         expires_ts: None,
         ext_uri: None,
+        fire: None,
+        fire_outlook: None,
         ingest_ts: 0, // set when storing
         location,
         md: None,
         outlook: None,
         report: Some(report),
+        source_id: None,
+        survey: None,
         text: Some(text),
         title,
+        tropical: None,
         valid_ts: None,
         warning: None,
         watch: None,
+        winter: None,
     };
 
     Some(event)
@@ -142,7 +150,7 @@ impl Hazard {
         }
     }
 
-    fn to_hazard_type(&self) -> HazardType {
+    pub(crate) fn to_hazard_type(&self) -> HazardType {
         match self {
             Hazard::Tornado => HazardType::Tornado,
             Hazard::Funnel => HazardType::Funnel,
@@ -206,10 +214,13 @@ mod tests {
         assert_eq!(
             event,
             Event {
+                afd: None,
                 event_ts: 1537483920000000,
                 event_type: EventType::SnReport,
                 expires_ts: None,
                 ext_uri: None,
+                fire: None,
+                fire_outlook: None,
                 ingest_ts: 0,
                 location: Some(Location {
                     county: None,
@@ -228,16 +239,21 @@ mod tests {
                     magnitude: Some(60.0),
                     units: Some(Units::Mph),
                     was_measured: Some(true),
-                    report_ts: None
+                    report_ts: None,
+                    lsr_source_id: None
                 }),
+                source_id: None,
+                survey: None,
                 text: Some(
                     "Wind reported by Test Human. Strong winds measured at 60mph with anemometer"
                         .to_string()
                 ),
                 title: "Report: 60mph Wind".to_string(),
+                tropical: None,
                 valid_ts: None,
                 warning: None,
-                watch: None
+                watch: None,
+                winter: None,
             }
         );
     }
@@ -0,0 +1,243 @@
+use chrono::{Duration, NaiveDate, NaiveTime, TimeZone, Timelike, Utc};
+use domain::{Coordinates, Event, EventType, HazardType, Location, Report, Units};
+use regex::Regex;
+use util;
+
+const COLUMN_COUNT: usize = 8;
+const WFO_PATTERN: &str = r"\((?P<wfo>[A-Z]{3})\)\s*$";
+
+lazy_static! {
+    static ref WFO_REGEX: Regex = Regex::new(WFO_PATTERN).expect("Unable to compile regex");
+}
+
+/**
+ * Parses an SPC storm reports CSV, ie. today_filtered.csv or an archived YYMMDD_rpts_filtered.csv.
+ * The file holds tornado, wind and hail sections, each starting with its own header. Times are
+ * UTC without a date; `date` is the convective day the file covers, which runs 12Z to 12Z, so
+ * times before 1200 fall on the following calendar day.
+ */
+pub fn parse(csv: &str, date: NaiveDate) -> Vec<Event> {
+    let mut section = None;
+
+    csv.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            if line.starts_with("Time,") {
+                section = line.split(',').nth(1);
+                return None;
+            }
+
+            match section {
+                Some(section) => parse_row(line, section, date),
+                None => {
+                    warn!("SPC report outside of a section: {}", line);
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+fn parse_row(line: &str, section: &str, date: NaiveDate) -> Option<Event> {
+    let columns: Vec<&str> = line.splitn(COLUMN_COUNT, ',').map(|x| x.trim()).collect();
+    if columns.len() != COLUMN_COUNT {
+        warn!("Unexpected SPC report row: {}", line);
+        return None;
+    }
+
+    let (time, magnitude, city, county, state) =
+        (columns[0], columns[1], columns[2], columns[3], columns[4]);
+    let comments = columns[7];
+
+    let report_ts = match get_report_ts(time, date) {
+        Some(ts) => ts,
+        None => {
+            warn!("Unable to parse SPC report time: {}", line);
+            return None;
+        }
+    };
+
+    let point = match (columns[5].parse(), columns[6].parse()) {
+        (Ok(lat), Ok(lon)) => Coordinates { lat, lon },
+        _ => {
+            warn!("Unable to parse SPC report location: {}", line);
+            return None;
+        }
+    };
+
+    let f_scale = get_f_scale(magnitude);
+    // Units are only set along with a magnitude, since UNK speeds have neither
+    let (hazard, magnitude, units, kind) = match section {
+        "F_Scale" => (HazardType::Tornado, f_scale.map(|x| x.1), None, "torn"),
+        "Speed" => {
+            let speed = magnitude.parse::<f32>().ok();
+            (HazardType::Wind, speed, speed.map(|_| Units::Mph), "wind")
+        }
+        "Size" => {
+            let size = magnitude.parse::<f32>().ok().map(|x| x / 100.0);
+            (HazardType::Hail, size, size.map(|_| Units::Inches), "hail")
+        }
+        _ => {
+            warn!("Unknown SPC report section: {}", section);
+            return None;
+        }
+    };
+
+    let title = match (&hazard, f_scale, magnitude) {
+        (HazardType::Tornado, Some((scale, rating)), _) => {
+            format!("Report: {}{} Tornado", scale, rating)
+        }
+        (HazardType::Tornado, None, _) => "Report: Tornado".to_string(),
+        (HazardType::Wind, _, Some(speed)) => format!("Report: {}mph Wind", speed),
+        (HazardType::Wind, _, None) => "Report: Wind".to_string(),
+        (_, _, Some(size)) => format!("Report: {}\" Hail", size),
+        (_, _, None) => "Report: Hail".to_string(),
+    };
+
+    let wfo = WFO_REGEX
+        .captures(comments)
+        .map(|x| format!("K{}", &x["wfo"]));
+
+    let location = Some(Location {
+        county: Some(county.to_uppercase()),
+        wfo,
+        point: Some(point),
+        poly: None,
+    });
+
+    let report = Report {
+        hazard,
+        lsr_source_id: None, // set when storing
+        magnitude,
+        report_ts: Some(report_ts),
+        reporter: "SPC".to_string(),
+        units,
+        was_measured: None,
+    };
+
+    let event = Event {
+        afd: None,
+        event_ts: report_ts,
+        event_type: EventType::SpcReport,
+        expires_ts: None,
+        ext_uri: None,
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0, // set when storing
+        location,
+        md: None,
+        outlook: None,
+        report: Some(report),
+        source_id: Some(format!(
+            "spc:{}:{}:{}:{}:{}",
+            date.format("%y%m%d"),
+            kind,
+            time,
+            columns[5],
+            columns[6]
+        )),
+        survey: None,
+        text: Some(format!("{}, {} {}: {}", city, county, state, comments)),
+        title,
+        tropical: None,
+        valid_ts: None,
+        warning: None,
+        watch: None,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+/**
+ * Ratings come through as EF#, F# or UNK. The scale is returned with the rating, so older F-scale
+ * ratings aren't relabeled as EF.
+ */
+fn get_f_scale(rating: &str) -> Option<(&'static str, f32)> {
+    let (scale, rating) = match rating.strip_prefix("EF") {
+        Some(rating) => ("EF", rating),
+        None => ("F", rating.strip_prefix('F')?),
+    };
+    Some((scale, rating.parse().ok()?))
+}
+
+fn get_report_ts(time: &str, date: NaiveDate) -> Option<u64> {
+    let time = NaiveTime::parse_from_str(time, "%H%M").ok()?;
+    let date = if time.hour() < 12 {
+        date + Duration::days(1)
+    } else {
+        date
+    };
+    let datetime = Utc.from_utc_datetime(&date.and_time(time));
+    util::rfc3339_to_ticks(&datetime.to_rfc3339()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::Read;
+
+    fn get_reports() -> Vec<Event> {
+        let mut f = File::open("../data/spc-reports.csv").expect("reports file not found");
+        let mut contents = String::new();
+        f.read_to_string(&mut contents)
+            .expect("something went wrong reading the file");
+        parse(&contents, NaiveDate::from_ymd_opt(2018, 3, 26).unwrap())
+    }
+
+    #[test]
+    fn parse_hail_report_happy_path() {
+        let reports = get_reports();
+        assert_eq!(reports.len(), 6);
+        let serialized_result = serde_json::to_string(&reports[4]).unwrap();
+        let expected = r#"{"afd":null,"event_ts":1522112100000000,"event_type":"SpcReport","expires_ts":null,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KSJT","point":{"lat":32.07,"lon":-100.66},"poly":null,"county":"COKE"},"md":null,"outlook":null,"report":{"reporter":"SPC","hazard":"Hail","magnitude":1.25,"units":"Inches","was_measured":null,"report_ts":1522112100000000,"lsr_source_id":null},"source_id":"spc:180326:hail:0055:32.07:-100.66","survey":null,"text":"1 E Silver, Coke TX: 1.25 hail on Hwy 208 near Silver. (SJT)","title":"Report: 1.25\" Hail","tropical":null,"valid_ts":null,"warning":null,"watch":null,"winter":null}"#;
+        assert_eq!(expected, serialized_result);
+    }
+
+    #[test]
+    fn parse_should_map_magnitudes() {
+        let reports = get_reports();
+        let titles: Vec<&str> = reports.iter().map(|x| x.title.as_str()).collect();
+        let expected = vec![
+            "Report: EF1 Tornado",
+            "Report: Tornado",
+            "Report: Wind",
+            "Report: 65mph Wind",
+            "Report: 1.25\" Hail",
+            "Report: 1.75\" Hail",
+        ];
+        assert_eq!(titles, expected);
+
+        let wind = reports[3].report.as_ref().unwrap();
+        assert_eq!(wind.magnitude, Some(65.0));
+        assert_eq!(wind.units, Some(Units::Mph));
+        let wind = reports[2].report.as_ref().unwrap();
+        assert_eq!(wind.magnitude, None);
+        assert_eq!(wind.units, None);
+        let tornado = reports[1].report.as_ref().unwrap();
+        assert_eq!(tornado.magnitude, None);
+        assert_eq!(tornado.units, None);
+    }
+
+    #[test]
+    fn parse_should_roll_early_times_to_next_day() {
+        let reports = get_reports();
+        // 2140Z on the 26th and 0112Z on the 27th
+        assert_eq!(reports[0].event_ts, 1522100400000000);
+        assert_eq!(reports[1].event_ts, 1522113120000000);
+    }
+
+    #[test]
+    fn get_f_scale_should_handle_old_and_unknown_ratings() {
+        assert_eq!(get_f_scale("EF3"), Some(("EF", 3.0)));
+        assert_eq!(get_f_scale("F2"), Some(("F", 2.0)));
+        assert_eq!(get_f_scale("UNK"), None);
+        assert_eq!(get_f_scale("65"), None);
+
+        let csv = "Time,F_Scale,Location,County,State,Lat,Lon,Comments\n\
+                   2140,F2,2 NW Tiffin,Johnson,IA,41.73,-91.69,Old report. (DVN)";
+        let reports = parse(csv, NaiveDate::from_ymd_opt(1990, 3, 26).unwrap());
+        assert_eq!(reports[0].title, "Report: F2 Tornado");
+    }
+}
@@ -0,0 +1,135 @@
+use crate::nws_regexes::Regexes;
+use crate::parser_util::{day_time_to_ticks, get_polygon, get_storm_motion};
+use domain::{Event, EventType, Location, Product, Warning};
+use regex::Regex;
+use util::safe_result;
+
+const HEADLINE_PATTERN: &str = r"\n\.{3}(?P<headline>[\s\S]+?)\.{3}\n";
+
+lazy_static! {
+    static ref HEADLINE_REGEX: Regex =
+        Regex::new(HEADLINE_PATTERN).expect("Unable to compile regex");
+}
+
+/**
+ * Parses an NWS Special Weather Statement (SPS). Offices use these for strong storms that are below
+ * warning criteria, and they often come a few minutes before a SVR or TOR. SPSs are also used for
+ * fog, fire danger and the like, so only those tracking a storm with a polygon are parsed.
+ */
+pub fn parse(product: &Product) -> Option<Event> {
+    let regexes = Regexes::new();
+    let text = &product.product_text;
+    let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
+    let motion = get_storm_motion(text, event_ts, &regexes)?;
+    let poly = get_polygon(text, &regexes);
+    if poly.is_empty() {
+        return None;
+    }
+
+    let wfo = product.issuing_office.to_string();
+
+    // SPSs have no VTEC, so they expire when the segment is purged
+    let expires_ts = match regexes.ugc_purge.captures(text) {
+        Some(purge) => Some(safe_result!(day_time_to_ticks(&purge["purge"], event_ts))),
+        None => None,
+    };
+
+    let issued_for = HEADLINE_REGEX
+        .captures(text)
+        .map(|x| {
+            x["headline"]
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" ")
+        })
+        .unwrap_or_default();
+
+    let location = Some(Location {
+        wfo: Some(wfo.to_string()),
+        point: motion.points.first().copied(),
+        poly: Some(poly),
+        county: None,
+    });
+
+    let warning = Some(Warning {
+        is_pds: false,
+        was_observed: None,
+        is_tor_emergency: None,
+        motion_deg: Some(motion.deg),
+        motion_kt: Some(motion.kt),
+        source: regexes.source.captures(text).map(|x| x["src"].to_string()),
+        issued_for,
+        time: Some(motion.time),
+        vtec: None,
+        hail_size: regexes
+            .hail_tag
+            .captures(text)
+            .and_then(|x| x["size"].parse().ok()),
+        wind_gust: regexes
+            .wind_tag
+            .captures(text)
+            .map(|x| x["gust"].to_string()),
+        waterspout: regexes
+            .waterspout_tag
+            .captures(text)
+            .map(|x| x["waterspout"].to_string()),
+        mpd_source_id: None,
+        storm_points: motion.points,
+        severity: None,
+    });
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type: EventType::NwsSps,
+        expires_ts,
+        ext_uri: None,
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0,
+        location,
+        md: None,
+        outlook: None,
+        report: None,
+        source_id: None,
+        survey: None,
+        text: Some(text.to_string()),
+        title: format!("Special Weather Statement ({})", wfo),
+        tropical: None,
+        valid_ts: Some(event_ts),
+        warning,
+        watch: None,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::get_product_from_file;
+    use super::*;
+
+    #[test]
+    fn parse_strong_storm_sps() {
+        let product = get_product_from_file("../data/products/sps-strong-storm");
+        let result = parse(&product).unwrap();
+        assert_eq!(result.title, "Special Weather Statement (KDMX)");
+        assert_eq!(result.valid_ts, Some(1558478880000000));
+        assert_eq!(result.expires_ts, Some(1558481400000000));
+
+        let serialized_result = serde_json::to_string(&result.location).unwrap();
+        let expected = r#"{"wfo":"KDMX","point":{"lat":42.04,"lon":-93.87},"poly":[{"lat":41.99,"lon":-93.98},{"lat":42.15,"lon":-93.75},{"lat":42.18,"lon":-93.46},{"lat":42.0,"lon":-93.4},{"lat":41.9,"lon":-93.89}],"county":null}"#;
+        assert_eq!(expected, serialized_result);
+
+        let serialized_result = serde_json::to_string(&result.warning).unwrap();
+        let expected = r#"{"is_pds":false,"is_tor_emergency":null,"was_observed":null,"issued_for":"A STRONG THUNDERSTORM WILL IMPACT PORTIONS OF NORTHEASTERN BOONE AND NORTHWESTERN STORY COUNTIES THROUGH 630 PM CDT","motion_deg":225,"motion_kt":26,"source":"Radar indicated","time":1558478880000000,"vtec":null,"hail_size":0.88,"wind_gust":"50MPH","waterspout":null,"mpd_source_id":null,"storm_points":[{"lat":42.04,"lon":-93.87}],"severity":null}"#;
+        assert_eq!(expected, serialized_result);
+    }
+
+    #[test]
+    fn parse_should_skip_non_convective_sps() {
+        let product = get_product_from_file("../data/products/sps-dense-fog");
+        assert_eq!(parse(&product), None);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::nws_regexes::Regexes;
+use crate::parser_util::{get_polygon, get_storm_motion};
 use domain::{Event, EventType, Location, Product};
 use util;
 use util::safe_result;
@@ -30,28 +32,40 @@ pub fn parse(product: &Product) -> Option<Event> {
     let title = format!("{} SVS: {}", wfo, title_fragment);
     let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
 
+    // The statement's polygon is what's left of the warning, and can be smaller than what was issued
+    let regexes = Regexes::new();
+    let poly = get_polygon(text, &regexes);
+    let point = get_storm_motion(text, event_ts, &regexes).and_then(|x| x.points.first().copied());
+
     let location = Some(Location {
-        point: None,
-        poly: None,
+        point,
+        poly: if poly.is_empty() { None } else { Some(poly) },
         wfo: Some(wfo),
         county: None,
     });
 
     let event = Event {
+        afd: None,
         event_ts,
         event_type: EventType::NwsSvs,
         expires_ts: None,
         ext_uri: None,
+        fire: None,
+        fire_outlook: None,
         ingest_ts: 0,
         location,
         md: None,
         outlook: None,
         report: None,
+        source_id: None,
+        survey: None,
         text: Some(text.to_string()),
         title,
+        tropical: None,
         valid_ts: None,
         warning: None,
         watch: None,
+        winter: None,
     };
 
     Some(event)
@@ -74,7 +88,7 @@ mod tests {
         let product = get_product_from_file("../data/products/svs-pds-tor");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1525223280000000,"event_type":"NwsSvs","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":{"wfo":"KTOP","point":null,"poly":null,"county":null},"md":null,"outlook":null,"report":null,"text":"\n211 \nWWUS53 KTOP 020108\nSVSTOP\n\nSevere Weather Statement\nNational Weather Service Topeka KS\n808 PM CDT TUE MAY 1 2018\n\nKSC143-020130-\n/O.CON.KTOP.TO.W.0008.000000T0000Z-180502T0130Z/\nOttawa-\n808 PM CDT TUE MAY 1 2018\n\n...A TORNADO WARNING REMAINS IN EFFECT UNTIL 830 PM CDT FOR\nSOUTHEASTERN OTTAWA COUNTY...\n    \nAt 807 PM CDT, a confirmed extremely dangerous tornado was located 4 \nmiles south of Minneapolis, moving northeast at 30 mph. An \nadditional tornado may be forming 5 miles NW of Bennington.\n\nThis is a PARTICULARLY DANGEROUS SITUATION. TAKE COVER NOW!\n\nHAZARD...Damaging tornado. \n\nSOURCE...Law enforcement confirmed tornado. \n\nIMPACT...You are in a life-threatening situation. Flying debris may \n         be deadly to those caught without shelter. Mobile homes \n         will be destroyed. Considerable damage to homes, \n         businesses, and vehicles is likely and complete destruction \n         is possible. \n\nThe tornado will be near...\n  Bennington around 815 PM CDT. \n  Wells around 825 PM CDT. \n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nHeavy rainfall may hide this tornado. Do not wait to see or hear the\ntornado. TAKE COVER NOW!\n\nTornadoes are extremely difficult to see and confirm at night. Do not\nwait to see or hear the tornado. TAKE COVER NOW!\n\n&&\n\nLAT...LON 3926 9748 3897 9738 3897 9773 3910 9783\nTIME...MOT...LOC 0107Z 244DEG 27KT 3906 9769 \n\nTORNADO...OBSERVED\nTORNADO DAMAGE THREAT...CONSIDERABLE\nHAIL...2.00IN\n\n$$\n\nSkow\n\n","title":"KTOP SVS: PDS Tornado","valid_ts":null,"warning":null,"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1525223280000000,"event_type":"NwsSvs","expires_ts":null,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KTOP","point":{"lat":39.06,"lon":-97.69},"poly":[{"lat":39.26,"lon":-97.48},{"lat":38.97,"lon":-97.38},{"lat":38.97,"lon":-97.73},{"lat":39.1,"lon":-97.83}],"county":null},"md":null,"outlook":null,"report":null,"source_id":null,"survey":null,"text":"\n211 \nWWUS53 KTOP 020108\nSVSTOP\n\nSevere Weather Statement\nNational Weather Service Topeka KS\n808 PM CDT TUE MAY 1 2018\n\nKSC143-020130-\n/O.CON.KTOP.TO.W.0008.000000T0000Z-180502T0130Z/\nOttawa-\n808 PM CDT TUE MAY 1 2018\n\n...A TORNADO WARNING REMAINS IN EFFECT UNTIL 830 PM CDT FOR\nSOUTHEASTERN OTTAWA COUNTY...\n    \nAt 807 PM CDT, a confirmed extremely dangerous tornado was located 4 \nmiles south of Minneapolis, moving northeast at 30 mph. An \nadditional tornado may be forming 5 miles NW of Bennington.\n\nThis is a PARTICULARLY DANGEROUS SITUATION. TAKE COVER NOW!\n\nHAZARD...Damaging tornado. \n\nSOURCE...Law enforcement confirmed tornado. \n\nIMPACT...You are in a life-threatening situation. Flying debris may \n         be deadly to those caught without shelter. Mobile homes \n         will be destroyed. Considerable damage to homes, \n         businesses, and vehicles is likely and complete destruction \n         is possible. \n\nThe tornado will be near...\n  Bennington around 815 PM CDT. \n  Wells around 825 PM CDT. \n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nHeavy rainfall may hide this tornado. Do not wait to see or hear the\ntornado. TAKE COVER NOW!\n\nTornadoes are extremely difficult to see and confirm at night. Do not\nwait to see or hear the tornado. TAKE COVER NOW!\n\n&&\n\nLAT...LON 3926 9748 3897 9738 3897 9773 3910 9783\nTIME...MOT...LOC 0107Z 244DEG 27KT 3906 9769 \n\nTORNADO...OBSERVED\nTORNADO DAMAGE THREAT...CONSIDERABLE\nHAIL...2.00IN\n\n$$\n\nSkow\n\n","title":"KTOP SVS: PDS Tornado","tropical":null,"valid_ts":null,"warning":null,"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -83,7 +97,7 @@ mod tests {
         let product = get_product_from_file("../data/products/svs-tor-emergency");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1525223280000000,"event_type":"NwsSvs","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":{"wfo":"KTOP","point":null,"poly":null,"county":null},"md":null,"outlook":null,"report":null,"text":"\n211 \nWWUS53 KTOP 020108\nSVSTOP\n\nSevere Weather Statement\nNational Weather Service Topeka KS\n808 PM CDT TUE MAY 1 2018\n\nKSC143-020130-\n/O.CON.KTOP.TO.W.0008.000000T0000Z-180502T0130Z/\nOttawa-\n808 PM CDT TUE MAY 1 2018\n\n...TORNADO EMERGENCY IN TOPEKA METRO AREA...\n    \nAt 807 PM CDT, a confirmed extremely dangerous tornado was located 4 \nmiles south of Minneapolis, moving northeast at 30 mph. An \nadditional tornado may be forming 5 miles NW of Bennington.\n\n TAKE COVER NOW!\n\nHAZARD...Damaging tornado. \n\nSOURCE...Law enforcement confirmed tornado. \n\nIMPACT...You are in a life-threatening situation. Flying debris may \n         be deadly to those caught without shelter. Mobile homes \n         will be destroyed. Considerable damage to homes, \n         businesses, and vehicles is likely and complete destruction \n         is possible. \n\nThe tornado will be near...\n  Bennington around 815 PM CDT. \n  Wells around 825 PM CDT. \n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nHeavy rainfall may hide this tornado. Do not wait to see or hear the\ntornado. TAKE COVER NOW!\n\nTornadoes are extremely difficult to see and confirm at night. Do not\nwait to see or hear the tornado. TAKE COVER NOW!\n\n&&\n\nLAT...LON 3926 9748 3897 9738 3897 9773 3910 9783\nTIME...MOT...LOC 0107Z 244DEG 27KT 3906 9769 \n\nTORNADO...OBSERVED\nTORNADO DAMAGE THREAT...CONSIDERABLE\nHAIL...2.00IN\n\n$$\n\nSkow\n\n","title":"KTOP SVS: Tornado Emergency","valid_ts":null,"warning":null,"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1525223280000000,"event_type":"NwsSvs","expires_ts":null,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KTOP","point":{"lat":39.06,"lon":-97.69},"poly":[{"lat":39.26,"lon":-97.48},{"lat":38.97,"lon":-97.38},{"lat":38.97,"lon":-97.73},{"lat":39.1,"lon":-97.83}],"county":null},"md":null,"outlook":null,"report":null,"source_id":null,"survey":null,"text":"\n211 \nWWUS53 KTOP 020108\nSVSTOP\n\nSevere Weather Statement\nNational Weather Service Topeka KS\n808 PM CDT TUE MAY 1 2018\n\nKSC143-020130-\n/O.CON.KTOP.TO.W.0008.000000T0000Z-180502T0130Z/\nOttawa-\n808 PM CDT TUE MAY 1 2018\n\n...TORNADO EMERGENCY IN TOPEKA METRO AREA...\n    \nAt 807 PM CDT, a confirmed extremely dangerous tornado was located 4 \nmiles south of Minneapolis, moving northeast at 30 mph. An \nadditional tornado may be forming 5 miles NW of Bennington.\n\n TAKE COVER NOW!\n\nHAZARD...Damaging tornado. \n\nSOURCE...Law enforcement confirmed tornado. \n\nIMPACT...You are in a life-threatening situation. Flying debris may \n         be deadly to those caught without shelter. Mobile homes \n         will be destroyed. Considerable damage to homes, \n         businesses, and vehicles is likely and complete destruction \n         is possible. \n\nThe tornado will be near...\n  Bennington around 815 PM CDT. \n  Wells around 825 PM CDT. \n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nHeavy rainfall may hide this tornado. Do not wait to see or hear the\ntornado. TAKE COVER NOW!\n\nTornadoes are extremely difficult to see and confirm at night. Do not\nwait to see or hear the tornado. TAKE COVER NOW!\n\n&&\n\nLAT...LON 3926 9748 3897 9738 3897 9773 3910 9783\nTIME...MOT...LOC 0107Z 244DEG 27KT 3906 9769 \n\nTORNADO...OBSERVED\nTORNADO DAMAGE THREAT...CONSIDERABLE\nHAIL...2.00IN\n\n$$\n\nSkow\n\n","title":"KTOP SVS: Tornado Emergency","tropical":null,"valid_ts":null,"warning":null,"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -92,7 +106,7 @@ mod tests {
         let product = get_product_from_file("../data/products/svs-pds-tor-emergency");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1525223280000000,"event_type":"NwsSvs","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":{"wfo":"KTOP","point":null,"poly":null,"county":null},"md":null,"outlook":null,"report":null,"text":"\n211 \nWWUS53 KTOP 020108\nSVSTOP\n\nSevere Weather Statement\nNational Weather Service Topeka KS\n808 PM CDT TUE MAY 1 2018\n\nKSC143-020130-\n/O.CON.KTOP.TO.W.0008.000000T0000Z-180502T0130Z/\nOttawa-\n808 PM CDT TUE MAY 1 2018\n\n...A TORNADO EMERGENCY REMAINS IN EFFECT UNTIL 830 PM CDT FOR\nSOUTHEASTERN OTTAWA COUNTY...\n    \nAt 807 PM CDT, a confirmed extremely dangerous tornado was located 4 \nmiles south of Minneapolis, moving northeast at 30 mph. An \nadditional tornado may be forming 5 miles NW of Bennington.\n\nThis is a PARTICULARLY DANGEROUS SITUATION. TAKE COVER NOW!\n\nHAZARD...Damaging tornado. \n\nSOURCE...Law enforcement confirmed tornado. \n\nIMPACT...You are in a life-threatening situation. Flying debris may \n         be deadly to those caught without shelter. Mobile homes \n         will be destroyed. Considerable damage to homes, \n         businesses, and vehicles is likely and complete destruction \n         is possible. \n\nThe tornado will be near...\n  Bennington around 815 PM CDT. \n  Wells around 825 PM CDT. \n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nHeavy rainfall may hide this tornado. Do not wait to see or hear the\ntornado. TAKE COVER NOW!\n\nTornadoes are extremely difficult to see and confirm at night. Do not\nwait to see or hear the tornado. TAKE COVER NOW!\n\n&&\n\nLAT...LON 3926 9748 3897 9738 3897 9773 3910 9783\nTIME...MOT...LOC 0107Z 244DEG 27KT 3906 9769 \n\nTORNADO...OBSERVED\nTORNADO DAMAGE THREAT...CONSIDERABLE\nHAIL...2.00IN\n\n$$\n\nSkow\n\n","title":"KTOP SVS: PDS Tor Emergency","valid_ts":null,"warning":null,"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1525223280000000,"event_type":"NwsSvs","expires_ts":null,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KTOP","point":{"lat":39.06,"lon":-97.69},"poly":[{"lat":39.26,"lon":-97.48},{"lat":38.97,"lon":-97.38},{"lat":38.97,"lon":-97.73},{"lat":39.1,"lon":-97.83}],"county":null},"md":null,"outlook":null,"report":null,"source_id":null,"survey":null,"text":"\n211 \nWWUS53 KTOP 020108\nSVSTOP\n\nSevere Weather Statement\nNational Weather Service Topeka KS\n808 PM CDT TUE MAY 1 2018\n\nKSC143-020130-\n/O.CON.KTOP.TO.W.0008.000000T0000Z-180502T0130Z/\nOttawa-\n808 PM CDT TUE MAY 1 2018\n\n...A TORNADO EMERGENCY REMAINS IN EFFECT UNTIL 830 PM CDT FOR\nSOUTHEASTERN OTTAWA COUNTY...\n    \nAt 807 PM CDT, a confirmed extremely dangerous tornado was located 4 \nmiles south of Minneapolis, moving northeast at 30 mph. An \nadditional tornado may be forming 5 miles NW of Bennington.\n\nThis is a PARTICULARLY DANGEROUS SITUATION. TAKE COVER NOW!\n\nHAZARD...Damaging tornado. \n\nSOURCE...Law enforcement confirmed tornado. \n\nIMPACT...You are in a life-threatening situation. Flying debris may \n         be deadly to those caught without shelter. Mobile homes \n         will be destroyed. Considerable damage to homes, \n         businesses, and vehicles is likely and complete destruction \n         is possible. \n\nThe tornado will be near...\n  Bennington around 815 PM CDT. \n  Wells around 825 PM CDT. \n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nHeavy rainfall may hide this tornado. Do not wait to see or hear the\ntornado. TAKE COVER NOW!\n\nTornadoes are extremely difficult to see and confirm at night. Do not\nwait to see or hear the tornado. TAKE COVER NOW!\n\n&&\n\nLAT...LON 3926 9748 3897 9738 3897 9773 3910 9783\nTIME...MOT...LOC 0107Z 244DEG 27KT 3906 9769 \n\nTORNADO...OBSERVED\nTORNADO DAMAGE THREAT...CONSIDERABLE\nHAIL...2.00IN\n\n$$\n\nSkow\n\n","title":"KTOP SVS: PDS Tor Emergency","tropical":null,"valid_ts":null,"warning":null,"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 }
@@ -1,8 +1,8 @@
 use crate::nws_regexes::Regexes;
-use crate::parser_util::str_to_latlon;
+use crate::parser_util;
 use domain::{
-    Coordinates, Event, EventType, Location, MdConcerning, MesoscaleDiscussion, Outlook,
-    OutlookRisk, Product, SwoType,
+    Event, EventType, Location, MdConcerning, MesoscaleDiscussion, Outlook, OutlookRisk, Product,
+    SwoType,
 };
 use util;
 use util::safe_result;
@@ -53,89 +53,112 @@ fn parse_outlook(product: &Product, swo_type: SwoType) -> Option<Event> {
     };
 
     let event = Event {
+        afd: None,
         event_ts,
         event_type: EventType::NwsSwo,
         expires_ts: None,
         ext_uri: None,
+        fire: None,
+        fire_outlook: None,
         ingest_ts: 0,
         location: None,
         md: None,
         outlook: Some(outlook),
         report: None,
+        source_id: None,
+        survey: None,
         text: Some(product.product_text.to_string()),
         title,
+        tropical: None,
         valid_ts: None,
         warning: None,
         watch: None,
+        winter: None,
     };
 
     Some(event)
 }
 
+/**
+ * Parses a Mesoscale Discussion. MDs about a possible new watch carry the probability of watch
+ * issuance, and MDs about existing watches list the watch numbers in the concerning line, ie.
+ * "Concerning...Tornado Watch 100...101...".
+ */
 fn parse_md(product: &Product) -> Option<Event> {
     let regexes = Regexes::new();
     let text = &product.product_text;
-    let id = regexes.md_number.captures(&text).unwrap();
-    let affected = regexes.affected.captures(&text).unwrap();
-    let wfos = regexes.wfos.captures(&text).unwrap();
-    let poly_captures = regexes.poly_condensed.captures_iter(&text);
-
-    let mut poly: Vec<Coordinates> = vec![];
-    for latlon in poly_captures {
-        poly.push(Coordinates {
-            lat: str_to_latlon(&latlon[0][0..4], false),
-            lon: str_to_latlon(&latlon[0][4..8], true),
-        });
-    }
+    let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
 
-    let watch_issuance_probability =
-        if let Some(watch_issuance_probability) = regexes.probability.captures(&text) {
-            Some(safe_result!(watch_issuance_probability[1].parse::<u16>()))
-        } else {
-            None
-        };
-
-    let id = safe_result!(id[1].parse::<u16>());
-    let mut concerning = MdConcerning::Unknown;
-    let affected = affected[1].to_string().replace('\n', " ");
-    let wfos: Vec<String> = wfos[1]
-        .split("...")
-        .map(ToString::to_string)
-        .filter(|s| s != "")
-        .collect();
-
-    let title = if text.contains("Concerning...Severe potential...Watch") {
-        concerning = MdConcerning::NewSvrWatch;
-        format!(
-            "SPC MD: Tstm Watch {:?}%",
-            watch_issuance_probability.unwrap()
-        )
-    } else if text.contains("Concerning...Severe potential...Tornado Watch") {
-        concerning = MdConcerning::NewTorWatch;
-        format!(
-            "SPC MD: Tornado Watch {:?}%",
-            watch_issuance_probability.unwrap()
-        )
-    } else if text.contains("Concerning...Severe Thunderstorm Watch") {
-        concerning = MdConcerning::ExistingSvrWatch;
-        "SPC MD: Existing Tstm Watch".to_string()
-    } else if text.contains("Concerning...Tornado Watch") {
-        concerning = MdConcerning::ExistingTorWatch;
-        "SPC MD: Existing Tornado Watch".to_string()
-    } else {
-        "SPC Mesoscale Discussion".to_string()
+    let id = match regexes.md_number.captures(text) {
+        Some(id) => safe_result!(id[1].parse::<u16>()),
+        None => {
+            warn!("MD has no number: {}", text);
+            return None;
+        }
+    };
+
+    let poly = parser_util::get_condensed_polygon(text, &regexes);
+
+    let watch_issuance_probability = regexes
+        .probability
+        .captures(text)
+        .and_then(|x| x[1].parse::<u16>().ok());
+
+    let affected = regexes
+        .affected
+        .captures(text)
+        .map(|x| x[1].replace('\n', " "))
+        .unwrap_or_default();
+    let wfos: Vec<String> = regexes
+        .wfos
+        .captures(text)
+        .map(|x| {
+            x[1].split("...")
+                .map(ToString::to_string)
+                .filter(|s| !s.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+    let summary = regexes
+        .md_summary
+        .captures(text)
+        .map(|x| x[1].trim().replace('\n', " "));
+
+    let concerning_text = regexes
+        .md_concerning
+        .captures(text)
+        .map(|x| x[1].trim().to_string())
+        .unwrap_or_default();
+    let concerning = get_md_concerning(&concerning_text);
+    // Only existing watches are numbered, other concerns can have figures like "1-2 in/hr"
+    let watch_ids: Vec<u16> = match concerning {
+        MdConcerning::ExistingTorWatch | MdConcerning::ExistingSvrWatch => concerning_text
+            .split(|c: char| !c.is_ascii_digit())
+            .filter_map(|x| x.parse().ok())
+            .collect(),
+        _ => vec![],
+    };
+
+    let (valid_ts, expires_ts) = match regexes.md_valid.captures(text) {
+        Some(valid) => (
+            parser_util::day_time_to_ticks(&valid[1], event_ts).ok(),
+            parser_util::day_time_to_ticks(&valid[2], event_ts).ok(),
+        ),
+        None => (None, None),
     };
 
+    let title = get_md_title(&concerning, watch_issuance_probability);
+
     let md = MesoscaleDiscussion {
         id,
         affected,
         concerning,
         watch_issuance_probability,
         wfos,
+        summary,
+        watch_ids,
     };
 
-    let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
-
     let location = Some(Location {
         wfo: None,
         point: None,
@@ -144,25 +167,98 @@ fn parse_md(product: &Product) -> Option<Event> {
     });
 
     let event = Event {
+        afd: None,
         event_ts,
         event_type: EventType::NwsSwo,
-        expires_ts: None,
+        expires_ts,
         ext_uri: None,
+        fire: None,
+        fire_outlook: None,
         ingest_ts: 0,
         location,
         md: Some(md),
         outlook: None,
         report: None,
+        source_id: None,
+        survey: None,
         text: Some(text.to_string()),
         title,
-        valid_ts: None,
+        tropical: None,
+        valid_ts,
         warning: None,
         watch: None,
+        winter: None,
     };
 
     Some(event)
 }
 
+/**
+ * Maps the text after "Concerning...", ie. "Severe potential...Tornado Watch likely". Winter MDs
+ * can list more than one concern, so the most impactful is used.
+ */
+fn get_md_concerning(text: &str) -> MdConcerning {
+    let text = text.to_lowercase();
+
+    if text.starts_with("severe potential") {
+        if text.contains("unlikely") {
+            MdConcerning::SevereWatchUnlikely
+        } else if text.contains("tornado watch") {
+            MdConcerning::NewTorWatch
+        } else if text.contains("watch") {
+            MdConcerning::NewSvrWatch
+        } else {
+            MdConcerning::Unknown
+        }
+    } else if text.starts_with("tornado watch") || text.starts_with("severe thunderstorm watch") {
+        if text.contains("tornado watch") {
+            MdConcerning::ExistingTorWatch
+        } else {
+            MdConcerning::ExistingSvrWatch
+        }
+    } else if text.contains("blizzard") {
+        MdConcerning::Blizzard
+    } else if text.contains("snow squall") {
+        MdConcerning::SnowSquall
+    } else if text.contains("heavy snow") {
+        MdConcerning::HeavySnow
+    } else if text.contains("freezing rain") {
+        MdConcerning::FreezingRain
+    } else if text.contains("winter mixed precipitation") {
+        MdConcerning::WinterMixedPrecipitation
+    } else if text.contains("heavy rain") {
+        MdConcerning::HeavyRain
+    } else {
+        MdConcerning::Unknown
+    }
+}
+
+fn get_md_title(concerning: &MdConcerning, watch_issuance_probability: Option<u16>) -> String {
+    let probability = match watch_issuance_probability {
+        Some(probability) => format!(" {}%", probability),
+        None => String::new(),
+    };
+
+    match concerning {
+        MdConcerning::NewTorWatch => format!("SPC MD: Tornado Watch{}", probability),
+        MdConcerning::NewSvrWatch => format!("SPC MD: Tstm Watch{}", probability),
+        MdConcerning::SevereWatchUnlikely => format!("SPC MD: Watch Unlikely{}", probability),
+        MdConcerning::ExistingTorWatch => "SPC MD: Existing Tornado Watch".to_string(),
+        MdConcerning::ExistingSvrWatch => "SPC MD: Existing Tstm Watch".to_string(),
+        MdConcerning::HeavyRain => "SPC MD: Heavy Rain".to_string(),
+        MdConcerning::HeavySnow => "SPC MD: Heavy Snow".to_string(),
+        MdConcerning::Blizzard => "SPC MD: Blizzard".to_string(),
+        MdConcerning::SnowSquall => "SPC MD: Snow Squall".to_string(),
+        MdConcerning::FreezingRain => "SPC MD: Freezing Rain".to_string(),
+        MdConcerning::WinterMixedPrecipitation => "SPC MD: Winter Mixed Precip".to_string(),
+        // flash flooding is only a concern of WPC MPDs
+        MdConcerning::Unknown
+        | MdConcerning::FlashFloodingLikely
+        | MdConcerning::FlashFloodingPossible
+        | MdConcerning::FlashFloodingUnlikely => "SPC Mesoscale Discussion".to_string(),
+    }
+}
+
 /**
  * All risks are listed in a text, so we need to exit early when we find the first
  * match by severity descending.
@@ -193,7 +289,7 @@ mod tests {
         let product = get_product_from_file("../data/products/swo-md-tor-watch-likely");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1522773660000000,"event_type":"NwsSwo","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":{"wfo":null,"point":null,"poly":[{"lat":37.82,"lon":-87.69},{"lat":38.53,"lon":-87.76},{"lat":39.73,"lon":-87.06},{"lat":40.62,"lon":-85.25},{"lat":40.46,"lon":-83.56},{"lat":40.36,"lon":-83.1},{"lat":40.12,"lon":-82.74},{"lat":39.65,"lon":-82.75},{"lat":39.24,"lon":-83.39},{"lat":38.8,"lon":-84.23},{"lat":38.2,"lon":-85.03},{"lat":37.81,"lon":-85.97},{"lat":37.73,"lon":-86.3},{"lat":37.64,"lon":-87.21},{"lat":37.82,"lon":-87.69}],"county":null},"md":{"id":205,"affected":"Central and southern Indiana...far northern Kentucky...western Ohio","concerning":"NewTorWatch","watch_issuance_probability":95,"wfos":["ILN","LMK","IWX","IND","PAH","ILX"]},"outlook":null,"report":null,"text":"\n504 \nACUS11 KWNS 031641\nSWOMCD\nSPC MCD 031641 \nOHZ000-KYZ000-INZ000-ILZ000-031915-\n\nMesoscale Discussion 0205\nNWS Storm Prediction Center Norman OK\n1141 AM CDT Tue Apr 03 2018\n\nAreas affected...Central and southern Indiana...far northern\nKentucky...western Ohio\n\nConcerning...Severe potential...Tornado Watch likely \n\nValid 031641Z - 031915Z\n\nProbability of Watch Issuance...95 percent\n\nSUMMARY...Storms are expected to increase in intensity this\nafternoon with a few tornadoes possible along with large hail.\nAdditional severe storms are likely later this evening.\n\nDISCUSSION...Scattered storms persist from southern IL across\ncentral IN and into OH along and north of a warm front. This\nboundary will gradually shift northward due to boundary layer\nheating/mixing to the south and strengthening warm air advection via\na backing 50-60 kt low-level jet. While some of the activity is\ncurrently elevated, a transition may occur in a few hours allowing\nstorms along the warm front to become supercells and/or bows.\nAdditional storms may also form south of the warm front as the air\nmass continues to destabilize, most likely across southern IN, far\nnorthern KY, and southwest OH. Wind profiles will become\nincreasingly favorable for supercells and tornadoes throughout the\nday as the low deepens.\n\n..Jewell/Hart.. 04/03/2018\n\n...Please see www.spc.noaa.gov for graphic product...\n\nATTN...WFO...ILN...LMK...IWX...IND...PAH...ILX...\n\nLAT...LON   37828769 38538776 39738706 40628525 40468356 40368310\n            40128274 39658275 39248339 38808423 38208503 37818597\n            37738630 37648721 37828769 \n\n\n","title":"SPC MD: Tornado Watch 95%","valid_ts":null,"warning":null,"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1522773660000000,"event_type":"NwsSwo","expires_ts":1522782900000000,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":null,"point":null,"poly":[{"lat":37.82,"lon":-87.69},{"lat":38.53,"lon":-87.76},{"lat":39.73,"lon":-87.06},{"lat":40.62,"lon":-85.25},{"lat":40.46,"lon":-83.56},{"lat":40.36,"lon":-83.1},{"lat":40.12,"lon":-82.74},{"lat":39.65,"lon":-82.75},{"lat":39.24,"lon":-83.39},{"lat":38.8,"lon":-84.23},{"lat":38.2,"lon":-85.03},{"lat":37.81,"lon":-85.97},{"lat":37.73,"lon":-86.3},{"lat":37.64,"lon":-87.21},{"lat":37.82,"lon":-87.69}],"county":null},"md":{"id":205,"affected":"Central and southern Indiana...far northern Kentucky...western Ohio","concerning":"NewTorWatch","watch_issuance_probability":95,"wfos":["ILN","LMK","IWX","IND","PAH","ILX"],"summary":"Storms are expected to increase in intensity this afternoon with a few tornadoes possible along with large hail. Additional severe storms are likely later this evening.","watch_ids":[]},"outlook":null,"report":null,"source_id":null,"survey":null,"text":"\n504 \nACUS11 KWNS 031641\nSWOMCD\nSPC MCD 031641 \nOHZ000-KYZ000-INZ000-ILZ000-031915-\n\nMesoscale Discussion 0205\nNWS Storm Prediction Center Norman OK\n1141 AM CDT Tue Apr 03 2018\n\nAreas affected...Central and southern Indiana...far northern\nKentucky...western Ohio\n\nConcerning...Severe potential...Tornado Watch likely \n\nValid 031641Z - 031915Z\n\nProbability of Watch Issuance...95 percent\n\nSUMMARY...Storms are expected to increase in intensity this\nafternoon with a few tornadoes possible along with large hail.\nAdditional severe storms are likely later this evening.\n\nDISCUSSION...Scattered storms persist from southern IL across\ncentral IN and into OH along and north of a warm front. This\nboundary will gradually shift northward due to boundary layer\nheating/mixing to the south and strengthening warm air advection via\na backing 50-60 kt low-level jet. While some of the activity is\ncurrently elevated, a transition may occur in a few hours allowing\nstorms along the warm front to become supercells and/or bows.\nAdditional storms may also form south of the warm front as the air\nmass continues to destabilize, most likely across southern IN, far\nnorthern KY, and southwest OH. Wind profiles will become\nincreasingly favorable for supercells and tornadoes throughout the\nday as the low deepens.\n\n..Jewell/Hart.. 04/03/2018\n\n...Please see www.spc.noaa.gov for graphic product...\n\nATTN...WFO...ILN...LMK...IWX...IND...PAH...ILX...\n\nLAT...LON   37828769 38538776 39738706 40628525 40468356 40368310\n            40128274 39658275 39248339 38808423 38208503 37818597\n            37738630 37648721 37828769 \n\n\n","title":"SPC MD: Tornado Watch 95%","tropical":null,"valid_ts":1522773660000000,"warning":null,"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -202,16 +298,110 @@ mod tests {
         let product = get_product_from_file("../data/products/swo-md-continues");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1522276380000000,"event_type":"NwsSwo","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":{"wfo":null,"point":null,"poly":[{"lat":33.18,"lon":-90.84},{"lat":34.13,"lon":-90.08},{"lat":34.49,"lon":-89.33},{"lat":34.07,"lon":-88.56},{"lat":32.91,"lon":-89.41},{"lat":32.2,"lon":-90.65},{"lat":31.66,"lon":-91.55},{"lat":31.71,"lon":-91.86},{"lat":32.45,"lon":-91.21},{"lat":33.18,"lon":-100.84}],"county":null},"md":{"id":190,"affected":"West central through north central Mississippi and adjacent portions of Arkansas/Louisiana","concerning":"ExistingTorWatch","watch_issuance_probability":null,"wfos":["MEG","JAN"]},"outlook":null,"report":null,"text":"\n205 \nACUS11 KWNS 282233\nSWOMCD\nSPC MCD 282232 \nMSZ000-LAZ000-290030-\n\nMesoscale Discussion 0190\nNWS Storm Prediction Center Norman OK\n0532 PM CDT Wed Mar 28 2018\n\nAreas affected...West central through north central Mississippi and\nadjacent portions of Arkansas/Louisiana\n\nConcerning...Tornado Watch 23...\n\nValid 282232Z - 290030Z\n\nThe severe weather threat for Tornado Watch 23 continues.\n\nSUMMARY...A risk for thunderstorm activity capable of producing\ndamaging wind gusts and a couple of tornadoes will gradually spread\nacross and northeast of the Vicksburg MS area, toward Greenwood and\nTupelo, through 7-9 PM CDT.\n\nDISCUSSION...The risk for severe weather will gradually increase\nacross west central into north central Mississippi through the\n00-02Z time frame.  This will largely occur in association with the\nnortheastward migration of a weak wave along an effective warm\nfrontal zone/zone of enhanced low-level convergence.  Strengthening\nof southerly 850 mb flow to 40-50 kt appears likely to accompany\nthis feature.  This will contribute to enlarging low-level\nhodographs along the boundary, supportive of supercell structures\nwith a risk for potentially damaging wind gusts and perhaps a couple\nof tornadoes.  Northeast of the Vicksburg area, thermodynamic\nprofiles/instability still appears somewhat marginal, but this may\nchange during the next couple of hours with continued low-level\nmoistening.\n\n..Kerr.. 03/28/2018\n\n...Please see www.spc.noaa.gov for graphic product...\n\nATTN...WFO...MEG...JAN...\n\nLAT...LON   33189084 34139008 34498933 34078856 32918941 32209065\n            31669155 31719186 32459121 33180084 \n\n\n","title":"SPC MD: Existing Tornado Watch","valid_ts":null,"warning":null,"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1522276380000000,"event_type":"NwsSwo","expires_ts":1522283400000000,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":null,"point":null,"poly":[{"lat":33.18,"lon":-90.84},{"lat":34.13,"lon":-90.08},{"lat":34.49,"lon":-89.33},{"lat":34.07,"lon":-88.56},{"lat":32.91,"lon":-89.41},{"lat":32.2,"lon":-90.65},{"lat":31.66,"lon":-91.55},{"lat":31.71,"lon":-91.86},{"lat":32.45,"lon":-91.21},{"lat":33.18,"lon":-100.84}],"county":null},"md":{"id":190,"affected":"West central through north central Mississippi and adjacent portions of Arkansas/Louisiana","concerning":"ExistingTorWatch","watch_issuance_probability":null,"wfos":["MEG","JAN"],"summary":"A risk for thunderstorm activity capable of producing damaging wind gusts and a couple of tornadoes will gradually spread across and northeast of the Vicksburg MS area, toward Greenwood and Tupelo, through 7-9 PM CDT.","watch_ids":[23]},"outlook":null,"report":null,"source_id":null,"survey":null,"text":"\n205 \nACUS11 KWNS 282233\nSWOMCD\nSPC MCD 282232 \nMSZ000-LAZ000-290030-\n\nMesoscale Discussion 0190\nNWS Storm Prediction Center Norman OK\n0532 PM CDT Wed Mar 28 2018\n\nAreas affected...West central through north central Mississippi and\nadjacent portions of Arkansas/Louisiana\n\nConcerning...Tornado Watch 23...\n\nValid 282232Z - 290030Z\n\nThe severe weather threat for Tornado Watch 23 continues.\n\nSUMMARY...A risk for thunderstorm activity capable of producing\ndamaging wind gusts and a couple of tornadoes will gradually spread\nacross and northeast of the Vicksburg MS area, toward Greenwood and\nTupelo, through 7-9 PM CDT.\n\nDISCUSSION...The risk for severe weather will gradually increase\nacross west central into north central Mississippi through the\n00-02Z time frame.  This will largely occur in association with the\nnortheastward migration of a weak wave along an effective warm\nfrontal zone/zone of enhanced low-level convergence.  Strengthening\nof southerly 850 mb flow to 40-50 kt appears likely to accompany\nthis feature.  This will contribute to enlarging low-level\nhodographs along the boundary, supportive of supercell structures\nwith a risk for potentially damaging wind gusts and perhaps a couple\nof tornadoes.  Northeast of the Vicksburg area, thermodynamic\nprofiles/instability still appears somewhat marginal, but this may\nchange during the next couple of hours with continued low-level\nmoistening.\n\n..Kerr.. 03/28/2018\n\n...Please see www.spc.noaa.gov for graphic product...\n\nATTN...WFO...MEG...JAN...\n\nLAT...LON   33189084 34139008 34498933 34078856 32918941 32209065\n            31669155 31719186 32459121 33180084 \n\n\n","title":"SPC MD: Existing Tornado Watch","tropical":null,"valid_ts":1522276320000000,"warning":null,"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
+    #[test]
+    fn parse_swo_md_winter() {
+        let product = get_product_from_file("../data/products/swo-md-heavy-snow");
+        let result = parse(&product).unwrap();
+        assert_eq!(result.title, "SPC MD: Heavy Snow");
+        assert_eq!(result.valid_ts, Some(1548688260000000));
+        assert_eq!(result.expires_ts, Some(1548702900000000));
+        let md = result.md.unwrap();
+        assert_eq!(md.concerning, MdConcerning::HeavySnow);
+        assert_eq!(md.watch_issuance_probability, None);
+        assert_eq!(md.wfos, vec!["MQT", "GRB", "MKX", "ARX", "MPX"]);
+        assert!(md
+            .summary
+            .unwrap()
+            .starts_with("Heavy snow rates of 1-2 inches"));
+    }
+
+    #[test]
+    fn parse_md_should_only_read_watch_ids_for_existing_watches() {
+        let mut product = get_product_from_file("../data/products/swo-md-heavy-snow");
+        product.product_text = product.product_text.replace(
+            "Concerning...Heavy snow",
+            "Concerning...Heavy snow 1-2 in/hr",
+        );
+        let md = parse(&product).unwrap().md.unwrap();
+        assert_eq!(md.concerning, MdConcerning::HeavySnow);
+        assert!(md.watch_ids.is_empty());
+    }
+
+    #[test]
+    fn parse_swo_md_watch_unlikely() {
+        let product = get_product_from_file("../data/products/swo-md-watch-unlikely");
+        let result = parse(&product).unwrap();
+        assert_eq!(result.title, "SPC MD: Watch Unlikely 20%");
+        // Valid 312349Z - 010145Z rolls over into June
+        assert_eq!(result.valid_ts, Some(1559346540000000));
+        assert_eq!(result.expires_ts, Some(1559353500000000));
+        let md = result.md.unwrap();
+        assert_eq!(md.concerning, MdConcerning::SevereWatchUnlikely);
+        assert_eq!(md.watch_issuance_probability, Some(20));
+        assert!(md.watch_ids.is_empty());
+    }
+
+    #[test]
+    fn parse_md_should_not_require_probability() {
+        let mut product = get_product_from_file("../data/products/swo-md-tor-watch-likely");
+        product.product_text = product
+            .product_text
+            .replace("Probability of Watch Issuance...95 percent", "");
+        let result = parse(&product).unwrap();
+        assert_eq!(result.title, "SPC MD: Tornado Watch");
+    }
+
+    #[test]
+    fn get_md_concerning_should_map_all_concerns() {
+        let tests = vec![
+            ("Severe potential...Watch likely", MdConcerning::NewSvrWatch),
+            (
+                "Severe potential...Watch possible",
+                MdConcerning::NewSvrWatch,
+            ),
+            (
+                "Severe potential...Tornado Watch likely",
+                MdConcerning::NewTorWatch,
+            ),
+            (
+                "Severe potential...watch unlikely",
+                MdConcerning::SevereWatchUnlikely,
+            ),
+            (
+                "Severe Thunderstorm Watch 45...",
+                MdConcerning::ExistingSvrWatch,
+            ),
+            (
+                "Severe Thunderstorm Watch 45...Tornado Watch 46...",
+                MdConcerning::ExistingTorWatch,
+            ),
+            ("Heavy rainfall", MdConcerning::HeavyRain),
+            ("Heavy snow...Freezing rain", MdConcerning::HeavySnow),
+            ("Freezing rain", MdConcerning::FreezingRain),
+            ("Blizzard", MdConcerning::Blizzard),
+            ("Snow squall", MdConcerning::SnowSquall),
+            (
+                "Winter mixed precipitation",
+                MdConcerning::WinterMixedPrecipitation,
+            ),
+            ("Something new", MdConcerning::Unknown),
+        ];
+
+        tests.into_iter().for_each(|(text, expected)| {
+            assert_eq!(get_md_concerning(text), expected, "{}", text);
+        });
+    }
+
     #[test]
     fn parse_swo_day1_no_severe() {
         let product = get_product_from_file("../data/products/swo-day1-no-severe");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1522524900000000,"event_type":"NwsSwo","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":null,"md":null,"outlook":{"swo_type":"Day1","max_risk":"TSTM","polys":null},"report":null,"text":"\n931 \nACUS01 KWNS 311935\nSWODY1\nSPC AC 311934\n\nDay 1 Convective Outlook  \nNWS Storm Prediction Center Norman OK\n0234 PM CDT Sat Mar 31 2018\n\nValid 312000Z - 011200Z\n\n...NO SEVERE THUNDERSTORM AREAS FORECAST...\n\n...SUMMARY...\nThunderstorms are possible from southern Oklahoma across the Ozarks\nregion and over parts of the Florida Peninsula.\n\n...Discussion...\n\nNo changes to 1630z outlook are warranted.\n\n..Darrow.. 03/31/2018\n\n.PREV DISCUSSION... /ISSUED 1126 AM CDT Sat Mar 31 2018/\n\n...TX/OK into the Ozarks...\nA strong surface cold front is surging southward across KS, and will\nmove into parts of TX/OK/AR/MO later this evening.  Southerly\nlow-level winds ahead of the front will continue to moisten the\nregion, leading to a corridor of marginal CAPE values by late\nafternoon.  Virtually all 12z model guidance is consistent in the\ndevelopment of scattered showers and a few thunderstorms along/ahead\nof the front later today.  Shear profiles would be conditionally\nconducive for organized/rotating updrafts.  However, weak low-level\nconvergence/shear and some weak capping inversion are expected to\nlimit updraft strength and resultant severe risk.  One or two cells\nmay briefly approach severe limits producing hail, but the overall\nrisk appears to warrant a continuation of less-than-5% hail\nprobabilities at this time.\n\n$$\n\n","title":"SPC Day1 Outlook: TSTM","valid_ts":null,"warning":null,"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1522524900000000,"event_type":"NwsSwo","expires_ts":null,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":null,"md":null,"outlook":{"swo_type":"Day1","max_risk":"TSTM","polys":null},"report":null,"source_id":null,"survey":null,"text":"\n931 \nACUS01 KWNS 311935\nSWODY1\nSPC AC 311934\n\nDay 1 Convective Outlook  \nNWS Storm Prediction Center Norman OK\n0234 PM CDT Sat Mar 31 2018\n\nValid 312000Z - 011200Z\n\n...NO SEVERE THUNDERSTORM AREAS FORECAST...\n\n...SUMMARY...\nThunderstorms are possible from southern Oklahoma across the Ozarks\nregion and over parts of the Florida Peninsula.\n\n...Discussion...\n\nNo changes to 1630z outlook are warranted.\n\n..Darrow.. 03/31/2018\n\n.PREV DISCUSSION... /ISSUED 1126 AM CDT Sat Mar 31 2018/\n\n...TX/OK into the Ozarks...\nA strong surface cold front is surging southward across KS, and will\nmove into parts of TX/OK/AR/MO later this evening.  Southerly\nlow-level winds ahead of the front will continue to moisten the\nregion, leading to a corridor of marginal CAPE values by late\nafternoon.  Virtually all 12z model guidance is consistent in the\ndevelopment of scattered showers and a few thunderstorms along/ahead\nof the front later today.  Shear profiles would be conditionally\nconducive for organized/rotating updrafts.  However, weak low-level\nconvergence/shear and some weak capping inversion are expected to\nlimit updraft strength and resultant severe risk.  One or two cells\nmay briefly approach severe limits producing hail, but the overall\nrisk appears to warrant a continuation of less-than-5% hail\nprobabilities at this time.\n\n$$\n\n","title":"SPC Day1 Outlook: TSTM","tropical":null,"valid_ts":null,"warning":null,"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -220,7 +410,7 @@ mod tests {
         let product = get_product_from_file("../data/products/swo-day1-moderate");
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1522777200000000,"event_type":"NwsSwo","expires_ts":null,"ext_uri":null,"ingest_ts":0,"location":null,"md":null,"outlook":{"swo_type":"Day1","max_risk":"MDT","polys":null},"report":null,"text":"\n587 \nACUS01 KWNS 031740\nSWODY1\nSPC AC 031739\n\nDay 1 Convective Outlook CORR 1\nNWS Storm Prediction Center Norman OK\n1239 PM CDT Tue Apr 03 2018\n\nValid 031630Z - 041200Z\n\n...THERE IS A MODERATE RISK OF SEVERE THUNDERSTORMS OVER PARTS OF\nEASTERN ARKANSAS...NORTHERN MISSISSIPPI...WESTERN AND MIDDLE\nTENNESSEE...SOUTHEAST MISSOURI...SOUTHERN ILLINOIS...WESTERN AND\nCENTRAL KENTUCKY...SOUTHERN AND CENTRAL INDIANA...AND SOUTHWEST\nOHIO...\n\n...THERE IS AN ENHANCED RISK OF SEVERE THUNDERSTORMS SURROUNDING THE\nMODERATE RISK AREA OVER PARTS OF THE LOWER AND MID\nMISSISSIPPI...OHIO...AND TENNESSEE VALLEYS...\n\n...THERE IS A SLIGHT RISK OF SEVERE THUNDERSTORMS FROM CENTRAL TEXAS\nINTO OHIO...\n\n...THERE IS A MARGINAL RISK OF SEVERE THUNDERSTORMS FROM CENTRAL\nTEXAS INTO WESTERN PENNSYLVANIA...\n\nCORRECTED SMALL TEXT ERROR\n\n...SUMMARY...\nA Moderate Risk for thunderstorms producing widespread damaging\nwinds, large hail, and a few tornadoes exists over parts of the Ohio\nValley and Mid-South regions.\n\n...AR/MS northeastward through much of the OH/TN Valleys...\nAn active severe weather day is expected across the MS/OH/TN Valleys\ntoday with numerous strong/severe thunderstorms affecting a large\narea.  The primary focus for severe storms will be a progressive and\ndeepening shortwave trough moving across the central Plains.  A\ndeepening surface low and cold front in advance of this system will\nsweep across the risk area this afternoon through tonight, resulting\nin a fast-moving squall line extending from IL/IN/OH southward into\nthe Mid South.  Visible satellite imagery shows broken cloud cover\nacross most of the warm sector, promoting heating and\ndestabilization.  Forecast soundings suggest a corridor of moderate\nCAPE values ahead of the front by mid-afternoon as thunderstorms\nbegin to form.  Initial activity may be supercellular in nature,\nwith a risk of tornadoes (some strong) and large hail from northeast\nAR/western MS into parts of southern IL/IN and western KY. \nEventually, the storms should congeal into a line with multiple\nbowing segments as it progresses across the MDT and ENH risk areas\nwith the potential for widespread damaging winds and a few QLCS\ntornadoes.\n\n...TX/LA...\nScattered strong to severe thunderstorms have developed this morning\nover central TX, ahead of a southern stream shortwave trough.  This\nactivity will persist through the day and spread into LA, with a\nrisk of large hail and damaging wind gusts.  By mid-afternoon,\nthunderstorms are expected to form along the advancing cold front\nand affect these same areas.\n\n...IN/OH...\nA persistent cluster of thunderstorms is affecting much of\ncentral/northern IN and OH.  The air mass south of the activity\ncontinues to warm, leading to a favorable environment for\nsevere/supercell thunderstorms.  This corridor remains in the higher\ntornado/damaging wind probability area for both warm frontal\nactivity this afternoon, and the squall line activity later today.\n\n..Hart.. 04/03/2018\n\n$$\n\n","title":"SPC Day1 Outlook: MDT","valid_ts":null,"warning":null,"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1522777200000000,"event_type":"NwsSwo","expires_ts":null,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":null,"md":null,"outlook":{"swo_type":"Day1","max_risk":"MDT","polys":null},"report":null,"source_id":null,"survey":null,"text":"\n587 \nACUS01 KWNS 031740\nSWODY1\nSPC AC 031739\n\nDay 1 Convective Outlook CORR 1\nNWS Storm Prediction Center Norman OK\n1239 PM CDT Tue Apr 03 2018\n\nValid 031630Z - 041200Z\n\n...THERE IS A MODERATE RISK OF SEVERE THUNDERSTORMS OVER PARTS OF\nEASTERN ARKANSAS...NORTHERN MISSISSIPPI...WESTERN AND MIDDLE\nTENNESSEE...SOUTHEAST MISSOURI...SOUTHERN ILLINOIS...WESTERN AND\nCENTRAL KENTUCKY...SOUTHERN AND CENTRAL INDIANA...AND SOUTHWEST\nOHIO...\n\n...THERE IS AN ENHANCED RISK OF SEVERE THUNDERSTORMS SURROUNDING THE\nMODERATE RISK AREA OVER PARTS OF THE LOWER AND MID\nMISSISSIPPI...OHIO...AND TENNESSEE VALLEYS...\n\n...THERE IS A SLIGHT RISK OF SEVERE THUNDERSTORMS FROM CENTRAL TEXAS\nINTO OHIO...\n\n...THERE IS A MARGINAL RISK OF SEVERE THUNDERSTORMS FROM CENTRAL\nTEXAS INTO WESTERN PENNSYLVANIA...\n\nCORRECTED SMALL TEXT ERROR\n\n...SUMMARY...\nA Moderate Risk for thunderstorms producing widespread damaging\nwinds, large hail, and a few tornadoes exists over parts of the Ohio\nValley and Mid-South regions.\n\n...AR/MS northeastward through much of the OH/TN Valleys...\nAn active severe weather day is expected across the MS/OH/TN Valleys\ntoday with numerous strong/severe thunderstorms affecting a large\narea.  The primary focus for severe storms will be a progressive and\ndeepening shortwave trough moving across the central Plains.  A\ndeepening surface low and cold front in advance of this system will\nsweep across the risk area this afternoon through tonight, resulting\nin a fast-moving squall line extending from IL/IN/OH southward into\nthe Mid South.  Visible satellite imagery shows broken cloud cover\nacross most of the warm sector, promoting heating and\ndestabilization.  Forecast soundings suggest a corridor of moderate\nCAPE values ahead of the front by mid-afternoon as thunderstorms\nbegin to form.  Initial activity may be supercellular in nature,\nwith a risk of tornadoes (some strong) and large hail from northeast\nAR/western MS into parts of southern IL/IN and western KY. \nEventually, the storms should congeal into a line with multiple\nbowing segments as it progresses across the MDT and ENH risk areas\nwith the potential for widespread damaging winds and a few QLCS\ntornadoes.\n\n...TX/LA...\nScattered strong to severe thunderstorms have developed this morning\nover central TX, ahead of a southern stream shortwave trough.  This\nactivity will persist through the day and spread into LA, with a\nrisk of large hail and damaging wind gusts.  By mid-afternoon,\nthunderstorms are expected to form along the advancing cold front\nand affect these same areas.\n\n...IN/OH...\nA persistent cluster of thunderstorms is affecting much of\ncentral/northern IN and OH.  The air mass south of the activity\ncontinues to warm, leading to a favorable environment for\nsevere/supercell thunderstorms.  This corridor remains in the higher\ntornado/damaging wind probability area for both warm frontal\nactivity this afternoon, and the squall line activity later today.\n\n..Hart.. 04/03/2018\n\n$$\n\n","title":"SPC Day1 Outlook: MDT","tropical":null,"valid_ts":null,"warning":null,"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -0,0 +1,133 @@
+use crate::nws_regexes::Regexes;
+use crate::parser_util::get_storm_position;
+use domain::{Event, EventType, Location, Product, TropicalCyclone};
+use regex::Regex;
+use util::safe_result;
+
+const HEADLINE_PATTERN: &str = r"\n\.{3}(?P<headline>[\s\S]+?)\.{3}\n";
+const WIND_PATTERN: &str = r"MAXIMUM SUSTAINED WINDS\.{3}(?P<mph>\d+) MPH";
+const MOVEMENT_PATTERN: &str = r"PRESENT MOVEMENT\.{3}.*?(?P<deg>\d+) DEGREES AT (?P<mph>\d+) MPH";
+const PRESSURE_PATTERN: &str = r"MINIMUM CENTRAL PRESSURE\.{3}(?P<mb>\d+) MB";
+
+lazy_static! {
+    static ref HEADLINE_REGEX: Regex =
+        Regex::new(HEADLINE_PATTERN).expect("Unable to compile regex");
+    static ref WIND_REGEX: Regex = Regex::new(WIND_PATTERN).expect("Unable to compile regex");
+    static ref MOVEMENT_REGEX: Regex =
+        Regex::new(MOVEMENT_PATTERN).expect("Unable to compile regex");
+    static ref PRESSURE_REGEX: Regex =
+        Regex::new(PRESSURE_PATTERN).expect("Unable to compile regex");
+}
+
+/**
+ * Parses an NHC Tropical Cyclone Public Advisory (TCP). The storm's position, winds, pressure and
+ * motion come from the summary block. Intermediate advisories, ie. 23A, have the same summary.
+ */
+pub fn parse(product: &Product) -> Option<Event> {
+    let regexes = Regexes::new();
+    let text = &product.product_text;
+    let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
+    let header = regexes.storm_header.captures(text)?;
+    let storm_id = match regexes.storm_id.captures(text) {
+        Some(storm_id) => storm_id["id"].to_string(),
+        None => {
+            warn!("TCP has no storm id: {}", text);
+            return None;
+        }
+    };
+
+    let name = header["name"].to_string();
+    let advisory = header["advisory"].to_uppercase();
+    let title = format!("NHC: {} Advisory {}", name, advisory);
+    let movement = MOVEMENT_REGEX.captures(text);
+    let headline = HEADLINE_REGEX.captures(text).map(|x| {
+        x["headline"]
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ")
+    });
+
+    let tropical = TropicalCyclone {
+        storm_id,
+        name,
+        advisory,
+        max_wind_mph: WIND_REGEX
+            .captures(text)
+            .and_then(|x| x["mph"].parse().ok()),
+        pressure_mb: PRESSURE_REGEX
+            .captures(text)
+            .and_then(|x| x["mb"].parse().ok()),
+        motion_deg: movement.as_ref().and_then(|x| x["deg"].parse().ok()),
+        motion_mph: movement.as_ref().and_then(|x| x["mph"].parse().ok()),
+        headline,
+    };
+
+    let location = Some(Location {
+        wfo: Some(product.issuing_office.to_string()),
+        point: get_storm_position(text, &regexes),
+        poly: None,
+        county: None,
+    });
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type: EventType::NwsTcp,
+        expires_ts: None,
+        ext_uri: None,
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0,
+        location,
+        md: None,
+        outlook: None,
+        report: None,
+        source_id: None,
+        survey: None,
+        text: Some(text.to_string()),
+        title,
+        tropical: Some(tropical),
+        valid_ts: Some(event_ts),
+        warning: None,
+        watch: None,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::get_product_from_file;
+    use super::*;
+
+    #[test]
+    fn parse_hurricane_advisory() {
+        let product = get_product_from_file("../data/products/tcp-hurricane");
+        let result = parse(&product).unwrap();
+        assert_eq!(result.event_type, EventType::NwsTcp);
+        assert_eq!(result.title, "NHC: Hurricane Ian Advisory 23");
+
+        let serialized_result = serde_json::to_string(&result.location).unwrap();
+        let expected =
+            r#"{"wfo":"KNHC","point":{"lat":26.7,"lon":-82.2},"poly":null,"county":null}"#;
+        assert_eq!(expected, serialized_result);
+
+        let serialized_result = serde_json::to_string(&result.tropical).unwrap();
+        let expected = r#"{"storm_id":"AL092022","name":"Hurricane Ian","advisory":"23","max_wind_mph":155,"pressure_mb":937,"motion_deg":20,"motion_mph":9,"headline":"EYEWALL OF IAN MOVING ONSHORE ALONG THE SOUTHWESTERN COAST OF FLORIDA"}"#;
+        assert_eq!(expected, serialized_result);
+    }
+
+    #[test]
+    fn parse_intermediate_advisory_should_keep_storm_id() {
+        let mut product = get_product_from_file("../data/products/tcp-hurricane");
+        product.product_text = product
+            .product_text
+            .replace("Advisory Number  23", "Intermediate Advisory Number  23A")
+            .replace("Hurricane Ian", "Tropical Storm Ian");
+        let tropical = parse(&product).unwrap().tropical.unwrap();
+        assert_eq!(tropical.storm_id, "AL092022");
+        assert_eq!(tropical.name, "Tropical Storm Ian");
+        assert_eq!(tropical.advisory, "23A");
+    }
+}
@@ -1,17 +1,13 @@
 use crate::nws_regexes::Regexes;
-use crate::parser_util::{cap, short_time_to_ticks, str_to_latlon};
-use domain::{Coordinates, Event, EventType, Location, Product, Warning};
+use crate::parser_util::{cap, get_polygon, get_storm_motion, get_vtec_id, short_time_to_ticks};
+use domain::{Event, EventType, Location, Product, Warning};
 use util;
 use util::safe_result;
 
 pub fn parse(product: &Product) -> Option<Event> {
     let regexes = Regexes::new();
     let text = &product.product_text;
-    let movement = regexes.movement.captures(&text).unwrap();
-    let poly_captures = regexes.poly.captures_iter(&text);
     let source = regexes.source.captures(&text).unwrap();
-    let lat = str_to_latlon(cap(movement.name("lat")), false);
-    let lon = str_to_latlon(cap(movement.name("lon")), true);
     let valid_range = regexes.valid.captures(&text).unwrap();
     let issued_for = regexes.warning_for.captures(&text).unwrap();
     let issued_for = issued_for[1]
@@ -22,25 +18,17 @@ pub fn parse(product: &Product) -> Option<Event> {
     let if_len = issued_for.len() - 1;
     let issued_for = issued_for[..if_len].to_string();
 
-    let mut poly: Vec<Coordinates> = vec![];
-    for latlon in poly_captures.take(4) {
-        let splits: Vec<&str> = latlon[0].split(' ').collect();
-        poly.push(Coordinates {
-            lat: str_to_latlon(splits[0], false),
-            lon: str_to_latlon(splits[1], true),
-        });
-    }
-
     let wfo = product.issuing_office.to_string();
     let valid_ts = Some(safe_result!(short_time_to_ticks(&valid_range[1])));
     let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
+    let motion = get_storm_motion(text, event_ts, &regexes)?;
     let expires_ts = Some(safe_result!(short_time_to_ticks(&valid_range[2])));
     let title = format!("Tornado Warning ({})", wfo);
 
     let location = Some(Location {
         wfo: Some(wfo),
-        point: Some(Coordinates { lat, lon }),
-        poly: Some(poly),
+        point: motion.points.first().copied(),
+        poly: Some(get_polygon(text, &regexes)),
         county: None,
     });
 
@@ -50,28 +38,42 @@ pub fn parse(product: &Product) -> Option<Event> {
         is_pds: lower_case_text.contains("particularly dangerous situation"),
         was_observed: Some(lower_case_text.contains("tornado...observed")),
         is_tor_emergency: Some(lower_case_text.contains("tornado emergency")),
-        motion_deg: Some(safe_result!(cap(movement.name("deg")).parse::<u16>())),
-        motion_kt: Some(safe_result!(cap(movement.name("kt")).parse::<u16>())),
+        motion_deg: Some(motion.deg),
+        motion_kt: Some(motion.kt),
         source: Some(cap(source.name("src")).to_string()),
         issued_for,
-        time: cap(movement.name("time")).to_string(),
+        time: Some(motion.time),
+        vtec: get_vtec_id(text, &regexes),
+        hail_size: None,
+        wind_gust: None,
+        waterspout: None,
+        mpd_source_id: None,
+        storm_points: motion.points,
+        severity: None,
     });
 
     let event = Event {
+        afd: None,
         event_ts,
         event_type: EventType::NwsTor,
         expires_ts,
         ext_uri: None,
+        fire: None,
+        fire_outlook: None,
         ingest_ts: 0,
         location,
         md: None,
         outlook: None,
         report: None,
+        source_id: None,
+        survey: None,
         text: Some(text.to_string()),
         title,
+        tropical: None,
         valid_ts,
         warning,
         watch: None,
+        winter: None,
     };
 
     Some(event)
@@ -81,6 +83,7 @@ pub fn parse(product: &Product) -> Option<Event> {
 mod tests {
     use super::super::test_util::get_product_from_file;
     use super::*;
+    use domain::Coordinates;
 
     #[test]
     fn parse_tor_product_happy_path() {
@@ -97,7 +100,7 @@ mod tests {
 
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1525222860000000,"event_type":"NwsTor","expires_ts":1525225500000000,"ext_uri":null,"ingest_ts":0,"location":{"wfo":"KTOP","point":{"lat":39.52,"lon":-97.28},"poly":[{"lat":39.77,"lon":-96.97},{"lat":39.5,"lon":-96.8},{"lat":39.39,"lon":-97.37},{"lat":39.59,"lon":-97.37}],"county":null},"md":null,"outlook":null,"report":null,"text":"\n271 \nWFUS53 KTOP 020101\nTORTOP\nKSC027-161-201-020145-\n/O.NEW.KTOP.TO.W.0009.180502T0101Z-180502T0145Z/\n\nBULLETIN - EAS ACTIVATION REQUESTED\nTornado Warning\nNational Weather Service Topeka KS\n801 PM CDT TUE MAY 1 2018\n\nThe National Weather Service in Topeka has issued a\n\n* Tornado Warning for...\n  Northwestern Riley County in northeastern Kansas...\n  Southern Washington County in north central Kansas...\n  Northern Clay County in north central Kansas...\n\n* Until 845 PM CDT\n    \n* At 800 PM CDT, a large and extremely dangerous tornado was located\n  2 miles south of Clifton, moving northeast at 25 mph.\n\n  TAKE COVER NOW! \n\n  HAZARD...Damaging tornado. \n\n  SOURCE...Radar indicated rotation. \n\n  IMPACT...You are in a life-threatening situation. Flying debris \n           may be deadly to those caught without shelter. Mobile \n           homes will be destroyed. Considerable damage to homes, \n           businesses, and vehicles is likely and complete \n           destruction is possible. \n\n* The tornado will be near...\n  Morganville around 805 PM CDT. \n  Palmer around 820 PM CDT. \n  Linn around 830 PM CDT. \n  Greenleaf around 845 PM CDT. \n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nTo repeat, a large, extremely dangerous and potentially deadly\ntornado is developing. To protect your life, TAKE COVER NOW! Move to\na basement or an interior room on the lowest floor of a sturdy\nbuilding. Avoid windows. If you are outdoors, in a mobile home, or in\na vehicle, move to the closest substantial shelter and protect\nyourself from flying debris.\n\nTornadoes are extremely difficult to see and confirm at night. Do not\nwait to see or hear the tornado. TAKE COVER NOW!\n\n&&\n\nLAT...LON 3977 9697 3950 9680 3939 9737 3959 9737\nTIME...MOT...LOC 0100Z 245DEG 24KT 3952 9728 \n\nTORNADO...RADAR INDICATED\nTORNADO DAMAGE THREAT...CONSIDERABLE\nHAIL...2.00IN\n\n$$\n\nBaerg\n\n","title":"Tornado Warning (KTOP)","valid_ts":1525222860000000,"warning":{"is_pds":false,"is_tor_emergency":false,"was_observed":false,"issued_for":"Northwestern Riley County in northeastern Kansas, Southern Washington County in north central Kansas, Northern Clay County in north central Kansas","motion_deg":245,"motion_kt":24,"source":"Radar indicated rotation","time":"0100Z"},"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1525222860000000,"event_type":"NwsTor","expires_ts":1525225500000000,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KTOP","point":{"lat":39.52,"lon":-97.28},"poly":[{"lat":39.77,"lon":-96.97},{"lat":39.5,"lon":-96.8},{"lat":39.39,"lon":-97.37},{"lat":39.59,"lon":-97.37}],"county":null},"md":null,"outlook":null,"report":null,"source_id":null,"survey":null,"text":"\n271 \nWFUS53 KTOP 020101\nTORTOP\nKSC027-161-201-020145-\n/O.NEW.KTOP.TO.W.0009.180502T0101Z-180502T0145Z/\n\nBULLETIN - EAS ACTIVATION REQUESTED\nTornado Warning\nNational Weather Service Topeka KS\n801 PM CDT TUE MAY 1 2018\n\nThe National Weather Service in Topeka has issued a\n\n* Tornado Warning for...\n  Northwestern Riley County in northeastern Kansas...\n  Southern Washington County in north central Kansas...\n  Northern Clay County in north central Kansas...\n\n* Until 845 PM CDT\n    \n* At 800 PM CDT, a large and extremely dangerous tornado was located\n  2 miles south of Clifton, moving northeast at 25 mph.\n\n  TAKE COVER NOW! \n\n  HAZARD...Damaging tornado. \n\n  SOURCE...Radar indicated rotation. \n\n  IMPACT...You are in a life-threatening situation. Flying debris \n           may be deadly to those caught without shelter. Mobile \n           homes will be destroyed. Considerable damage to homes, \n           businesses, and vehicles is likely and complete \n           destruction is possible. \n\n* The tornado will be near...\n  Morganville around 805 PM CDT. \n  Palmer around 820 PM CDT. \n  Linn around 830 PM CDT. \n  Greenleaf around 845 PM CDT. \n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nTo repeat, a large, extremely dangerous and potentially deadly\ntornado is developing. To protect your life, TAKE COVER NOW! Move to\na basement or an interior room on the lowest floor of a sturdy\nbuilding. Avoid windows. If you are outdoors, in a mobile home, or in\na vehicle, move to the closest substantial shelter and protect\nyourself from flying debris.\n\nTornadoes are extremely difficult to see and confirm at night. Do not\nwait to see or hear the tornado. TAKE COVER NOW!\n\n&&\n\nLAT...LON 3977 9697 3950 9680 3939 9737 3959 9737\nTIME...MOT...LOC 0100Z 245DEG 24KT 3952 9728 \n\nTORNADO...RADAR INDICATED\nTORNADO DAMAGE THREAT...CONSIDERABLE\nHAIL...2.00IN\n\n$$\n\nBaerg\n\n","title":"Tornado Warning (KTOP)","tropical":null,"valid_ts":1525222860000000,"warning":{"is_pds":false,"is_tor_emergency":false,"was_observed":false,"issued_for":"Northwestern Riley County in northeastern Kansas, Southern Washington County in north central Kansas, Northern Clay County in north central Kansas","motion_deg":245,"motion_kt":24,"source":"Radar indicated rotation","time":1525222800000000,"vtec":"KTOP.TO.W.0009.2018","hail_size":null,"wind_gust":null,"waterspout":null,"mpd_source_id":null,"storm_points":[{"lat":39.52,"lon":-97.28}],"severity":null},"watch":null,"winter":null}"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -116,7 +119,41 @@ mod tests {
 
         let result = parse(&product).unwrap();
         let serialized_result = serde_json::to_string(&result).unwrap();
-        let expected = r#"{"event_ts":1525222860000000,"event_type":"NwsTor","expires_ts":1525225500000000,"ext_uri":null,"ingest_ts":0,"location":{"wfo":"KTOP","point":{"lat":39.52,"lon":-97.28},"poly":[{"lat":39.77,"lon":-96.97},{"lat":39.5,"lon":-96.8},{"lat":39.39,"lon":-97.37},{"lat":39.59,"lon":-97.37}],"county":null},"md":null,"outlook":null,"report":null,"text":"\n271 \nWFUS53 KTOP 020101\nTORTOP\nKSC027-161-201-020145-\n/O.NEW.KTOP.TO.W.0009.180502T0101Z-180502T0145Z/\n\nBULLETIN - EAS ACTIVATION REQUESTED\nTornado Warning\nNational Weather Service Topeka KS\n801 PM CDT TUE MAY 1 2018\n\nThe National Weather Service in Topeka has issued a\n\n* Tornado Warning for...\n  Northwestern Riley County in northeastern Kansas...\n  Southern Washington County in north central Kansas...\n  Northern Clay County in north central Kansas...\n\n* Until 845 PM CDT\n    \n* At 800 PM CDT, a large and extremely dangerous tornado was located\n  2 miles south of Clifton, moving northeast at 25 mph.\n\n  THIS IS A TORNADO EMERGENCY FOR CLIFTON. \n\n This is a PARTICULARLY DANGEROUS SITUATION. TAKE COVER NOW! \n\n  HAZARD...Damaging tornado. \n\n  SOURCE...Radar indicated rotation. \n\n  IMPACT...You are in a life-threatening situation. Flying debris \n           may be deadly to those caught without shelter. Mobile \n           homes will be destroyed. Considerable damage to homes, \n           businesses, and vehicles is likely and complete \n           destruction is possible. \n\n* The tornado will be near...\n  Morganville around 805 PM CDT. \n  Palmer around 820 PM CDT. \n  Linn around 830 PM CDT. \n  Greenleaf around 845 PM CDT. \n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nTo repeat, a large, extremely dangerous and potentially deadly\ntornado is developing. To protect your life, TAKE COVER NOW! Move to\na basement or an interior room on the lowest floor of a sturdy\nbuilding. Avoid windows. If you are outdoors, in a mobile home, or in\na vehicle, move to the closest substantial shelter and protect\nyourself from flying debris.\n\nTornadoes are extremely difficult to see and confirm at night. Do not\nwait to see or hear the tornado. TAKE COVER NOW!\n\n&&\n\nLAT...LON 3977 9697 3950 9680 3939 9737 3959 9737\nTIME...MOT...LOC 0100Z 245DEG 24KT 3952 9728 \n\nTORNADO...OBSERVED\nTORNADO DAMAGE THREAT...CONSIDERABLE\nHAIL...2.00IN\n\n$$\n\nBaerg\n\n","title":"Tornado Warning (KTOP)","valid_ts":1525222860000000,"warning":{"is_pds":true,"is_tor_emergency":true,"was_observed":true,"issued_for":"Northwestern Riley County in northeastern Kansas, Southern Washington County in north central Kansas, Northern Clay County in north central Kansas","motion_deg":245,"motion_kt":24,"source":"Radar indicated rotation","time":"0100Z"},"watch":null}"#;
+        let expected = r#"{"afd":null,"event_ts":1525222860000000,"event_type":"NwsTor","expires_ts":1525225500000000,"ext_uri":null,"fire":null,"fire_outlook":null,"ingest_ts":0,"location":{"wfo":"KTOP","point":{"lat":39.52,"lon":-97.28},"poly":[{"lat":39.77,"lon":-96.97},{"lat":39.5,"lon":-96.8},{"lat":39.39,"lon":-97.37},{"lat":39.59,"lon":-97.37}],"county":null},"md":null,"outlook":null,"report":null,"source_id":null,"survey":null,"text":"\n271 \nWFUS53 KTOP 020101\nTORTOP\nKSC027-161-201-020145-\n/O.NEW.KTOP.TO.W.0009.180502T0101Z-180502T0145Z/\n\nBULLETIN - EAS ACTIVATION REQUESTED\nTornado Warning\nNational Weather Service Topeka KS\n801 PM CDT TUE MAY 1 2018\n\nThe National Weather Service in Topeka has issued a\n\n* Tornado Warning for...\n  Northwestern Riley County in northeastern Kansas...\n  Southern Washington County in north central Kansas...\n  Northern Clay County in north central Kansas...\n\n* Until 845 PM CDT\n    \n* At 800 PM CDT, a large and extremely dangerous tornado was located\n  2 miles south of Clifton, moving northeast at 25 mph.\n\n  THIS IS A TORNADO EMERGENCY FOR CLIFTON. \n\n This is a PARTICULARLY DANGEROUS SITUATION. TAKE COVER NOW! \n\n  HAZARD...Damaging tornado. \n\n  SOURCE...Radar indicated rotation. \n\n  IMPACT...You are in a life-threatening situation. Flying debris \n           may be deadly to those caught without shelter. Mobile \n           homes will be destroyed. Considerable damage to homes, \n           businesses, and vehicles is likely and complete \n           destruction is possible. \n\n* The tornado will be near...\n  Morganville around 805 PM CDT. \n  Palmer around 820 PM CDT. \n  Linn around 830 PM CDT. \n  Greenleaf around 845 PM CDT. \n\nPRECAUTIONARY/PREPAREDNESS ACTIONS...\n\nTo repeat, a large, extremely dangerous and potentially deadly\ntornado is developing. To protect your life, TAKE COVER NOW! Move to\na basement or an interior room on the lowest floor of a sturdy\nbuilding. Avoid windows. If you are outdoors, in a mobile home, or in\na vehicle, move to the closest substantial shelter and protect\nyourself from flying debris.\n\nTornadoes are extremely difficult to see and confirm at night. Do not\nwait to see or hear the tornado. TAKE COVER NOW!\n\n&&\n\nLAT...LON 3977 9697 3950 9680 3939 9737 3959 9737\nTIME...MOT...LOC 0100Z 245DEG 24KT 3952 9728 \n\nTORNADO...OBSERVED\nTORNADO DAMAGE THREAT...CONSIDERABLE\nHAIL...2.00IN\n\n$$\n\nBaerg\n\n","title":"Tornado Warning (KTOP)","tropical":null,"valid_ts":1525222860000000,"warning":{"is_pds":true,"is_tor_emergency":true,"was_observed":true,"issued_for":"Northwestern Riley County in northeastern Kansas, Southern Washington County in north central Kansas, Northern Clay County in north central Kansas","motion_deg":245,"motion_kt":24,"source":"Radar indicated rotation","time":1525222800000000,"vtec":"KTOP.TO.W.0009.2018","hail_size":null,"wind_gust":null,"waterspout":null,"mpd_source_id":null,"storm_points":[{"lat":39.52,"lon":-97.28}],"severity":null},"watch":null,"winter":null}"#;
+        assert_eq!(expected, serialized_result);
+    }
+
+    #[test]
+    fn parse_tor_should_keep_every_vertex_and_loc_point() {
+        let mut product = get_product_from_file("../data/products/tor-radar-observed");
+        product.product_text = product.product_text.replace(
+            "24KT 3286 9212 ",
+            "24KT 3286 9212 3279 9220\n      3271 9229 ",
+        );
+        let result = parse(&product).unwrap();
+        let location = result.location.unwrap();
+        let poly = location.poly.unwrap();
+        assert_eq!(poly.len(), 11);
+        assert_eq!(
+            poly[10],
+            Coordinates {
+                lat: 32.96,
+                lon: -92.08
+            }
+        );
+        assert_eq!(
+            location.point,
+            Some(Coordinates {
+                lat: 32.86,
+                lon: -92.12
+            })
+        );
+
+        let warning = result.warning.unwrap();
+        assert_eq!(warning.time, Some(1523665440000000));
+        let serialized_result = serde_json::to_string(&warning.storm_points).unwrap();
+        let expected =
+            r#"[{"lat":32.86,"lon":-92.12},{"lat":32.79,"lon":-92.2},{"lat":32.71,"lon":-92.29}]"#;
         assert_eq!(expected, serialized_result);
     }
 
@@ -0,0 +1,186 @@
+use crate::nws_regexes::Regexes;
+use crate::parser_util::{cap, get_vtec_id, get_zones, short_time_to_ticks};
+use domain::{Event, EventType, Location, Product, WinterHazard, WinterHazardType};
+use regex::Regex;
+use util::safe_result;
+
+const BULLET_PATTERN: &str = r"\* (?P<name>WHAT|WHERE|WHEN)\.{3}(?P<text>[\s\S]*?)(\n\n|$)";
+
+lazy_static! {
+    static ref BULLET_REGEX: Regex = Regex::new(BULLET_PATTERN).expect("Unable to compile regex");
+}
+
+/**
+ * Parses an NWS Winter Weather Message (WSW). Each segment of the product covers one set of zones
+ * and parses to its own event. Winter hazards we don't track, ie. wind chill, are skipped.
+ */
+pub fn parse(product: &Product) -> Vec<Option<Event>> {
+    let event_ts = match util::ts_to_ticks(&product.issuance_time) {
+        Ok(event_ts) => event_ts,
+        Err(_) => return vec![],
+    };
+    let regexes = Regexes::new();
+
+    product
+        .product_text
+        .split("$$")
+        .map(|segment| parse_segment(product, segment, event_ts, &regexes))
+        .collect()
+}
+
+fn parse_segment(
+    product: &Product,
+    segment: &str,
+    event_ts: u64,
+    regexes: &Regexes,
+) -> Option<Event> {
+    // A segment can upgrade or cancel one hazard while issuing another, so prefer the line for a
+    // hazard that is in effect
+    let vtec = regexes
+        .vtec
+        .captures_iter(segment)
+        .filter_map(|x| Some((get_hazard_type(&x["phenomena"], &x["significance"])?, x)))
+        .min_by_key(|(_, x)| matches!(&x["action"], "UPG" | "CAN" | "EXP"));
+    let (hazard_type, vtec) = vtec?;
+
+    let action = cap(vtec.name("action")).to_string();
+    let begin = cap(vtec.name("begin"));
+    let valid_ts = if begin.starts_with("000000") {
+        None // already in effect
+    } else {
+        Some(safe_result!(short_time_to_ticks(begin)))
+    };
+    let expires_ts = Some(safe_result!(short_time_to_ticks(cap(vtec.name("end")))));
+    let vtec_id = get_vtec_id(&vtec[0], regexes)?;
+
+    let zones = regexes
+        .ugc_purge
+        .find(segment)
+        .map(|x| get_zones(x.as_str()))
+        .unwrap_or_default();
+
+    let mut what = None;
+    let mut r#where = None;
+    let mut when = None;
+    for bullet in BULLET_REGEX.captures_iter(segment) {
+        let text = Some(
+            bullet["text"]
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" "),
+        );
+        match &bullet["name"] {
+            "WHAT" => what = text,
+            "WHERE" => r#where = text,
+            _ => when = text,
+        }
+    }
+
+    let wfo = product.issuing_office.to_string();
+    let title = format!("{} ({})", get_hazard_name(&hazard_type), wfo);
+
+    let winter = WinterHazard {
+        hazard_type,
+        action,
+        vtec: vtec_id,
+        zones,
+        what,
+        r#where,
+        when,
+    };
+
+    let location = Some(Location {
+        wfo: Some(wfo),
+        point: None,
+        poly: None,
+        county: None,
+    });
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type: EventType::NwsWsw,
+        expires_ts,
+        ext_uri: None,
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0,
+        location,
+        md: None,
+        outlook: None,
+        report: None,
+        source_id: None,
+        survey: None,
+        text: Some(segment.trim().to_string()),
+        title,
+        tropical: None,
+        valid_ts,
+        warning: None,
+        watch: None,
+        winter: Some(winter),
+    };
+
+    Some(event)
+}
+
+fn get_hazard_type(phenomena: &str, significance: &str) -> Option<WinterHazardType> {
+    match (phenomena, significance) {
+        ("WS", "W") => Some(WinterHazardType::WinterStormWarning),
+        ("WS", "A") => Some(WinterHazardType::WinterStormWatch),
+        ("BZ", "W") => Some(WinterHazardType::BlizzardWarning),
+        ("IS", "W") => Some(WinterHazardType::IceStormWarning),
+        ("WW", "Y") => Some(WinterHazardType::WinterWeatherAdvisory),
+        _ => None,
+    }
+}
+
+fn get_hazard_name(hazard_type: &WinterHazardType) -> &'static str {
+    match hazard_type {
+        WinterHazardType::WinterStormWarning => "Winter Storm Warning",
+        WinterHazardType::WinterStormWatch => "Winter Storm Watch",
+        WinterHazardType::BlizzardWarning => "Blizzard Warning",
+        WinterHazardType::IceStormWarning => "Ice Storm Warning",
+        WinterHazardType::WinterWeatherAdvisory => "Winter Weather Advisory",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::get_product_from_file;
+    use super::*;
+
+    #[test]
+    fn parse_blizzard_warning_segment() {
+        let product = get_product_from_file("../data/products/wsw-mpx");
+        let result = parse(&product);
+        assert_eq!(result.len(), 5);
+
+        let event = result[0].as_ref().unwrap();
+        assert_eq!(event.title, "Blizzard Warning (KMPX)");
+        assert_eq!(event.valid_ts, Some(1548612000000000));
+        assert_eq!(event.expires_ts, Some(1548655200000000));
+
+        let mut serialized_result = serde_json::to_string(&event.winter).unwrap();
+        let expected = r#"{"hazard_type":"BlizzardWarning","action":"NEW","vtec":"KMPX.BZ.W.0002.2019","zones":["MNZ047","MNZ048","MNZ054","MNZ055","MNZ056","MNZ057","MNZ064","MNZ065","MNZ073"],"what":"Blizzard conditions expected. Total snow accumulations of 4 to 7 inches. Winds gusting as high as 45 mph.","where":"Portions of central and west central Minnesota.","when":"From noon today to midnight CST tonight."}"#;
+        assert_eq!(expected, serialized_result);
+
+        let event = result[1].as_ref().unwrap();
+        assert_eq!(event.title, "Winter Storm Warning (KMPX)");
+        let winter = event.winter.as_ref().unwrap();
+        assert_eq!(winter.action, "NEW");
+        assert_eq!(winter.vtec, "KMPX.WS.W.0004.2019");
+        assert_eq!(
+            winter.zones,
+            vec!["MNZ060", "MNZ062", "MNZ063", "WIZ014", "WIZ015", "WIZ016"]
+        );
+
+        serialized_result = serde_json::to_string(&result[2].as_ref().unwrap().winter).unwrap();
+        let expected = r#"{"hazard_type":"WinterWeatherAdvisory","action":"CON","vtec":"KMPX.WW.Y.0007.2019","zones":["MNZ041","MNZ042","MNZ043"],"what":"Snow. Additional snow accumulations of 1 to 2 inches.","where":"Portions of west central Minnesota.","when":"Until 6 PM CST this evening."}"#;
+        assert_eq!(expected, serialized_result);
+        assert_eq!(result[2].as_ref().unwrap().valid_ts, None);
+
+        // Wind chill advisories and the trailing signature are skipped
+        assert!(result[3].is_none());
+        assert!(result[4].is_none());
+    }
+}
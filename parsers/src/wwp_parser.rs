@@ -0,0 +1,171 @@
+use crate::nws_regexes::Regexes;
+use crate::parser_util::day_time_to_ticks;
+use domain::{Event, EventType, Product, Watch, WatchProbabilities, WatchStatus, WatchType};
+use regex::Regex;
+use util::safe_result;
+
+const HEADER_PATTERN: &str = r"WW\s+(?P<id>\d{1,4})\s+(?P<type>TORNADO|SEVERE TSTM)\s+(?P<states>[A-Z ]+?)\s+(?P<begin>\d{6})Z\s+-\s+(?P<end>\d{6})Z";
+const PROBABILITY_PATTERN: &str = r"(?m)^\s*PROB OF (?P<desc>.+?)\s*:\s*<?\s*(?P<prob>\d+)%";
+const ATTRIBUTE_PATTERN: &str = r"(?m)^\s*(?P<name>MAX HAIL|MAX WIND GUSTS|PARTICULARLY DANGEROUS SITUATION)[^:\n]*:\s*(?P<value>\S+)";
+
+lazy_static! {
+    static ref HEADER_REGEX: Regex = Regex::new(HEADER_PATTERN).expect("Unable to compile regex");
+    static ref PROBABILITY_REGEX: Regex =
+        Regex::new(PROBABILITY_PATTERN).expect("Unable to compile regex");
+    static ref ATTRIBUTE_REGEX: Regex =
+        Regex::new(ATTRIBUTE_PATTERN).expect("Unable to compile regex");
+}
+
+/**
+ * Parses an SPC Watch Probabilities (WWP) product. It's issued alongside the SEL and has SPC's
+ * probabilities for the watch, along with the same hail, wind and storm motion attributes.
+ */
+pub fn parse(product: &Product) -> Option<Event> {
+    let regexes = Regexes::new();
+    let text = &product.product_text;
+    let event_ts = safe_result!(util::ts_to_ticks(&product.issuance_time));
+    let header = HEADER_REGEX.captures(text)?;
+    let id = safe_result!(header["id"].parse::<u16>());
+    let valid_ts = Some(safe_result!(day_time_to_ticks(&header["begin"], event_ts)));
+    let expires_ts = Some(safe_result!(day_time_to_ticks(&header["end"], event_ts)));
+
+    let (watch_type, watch_type_text) = match &header["type"] {
+        "TORNADO" => (WatchType::Tornado, "Tor"),
+        _ => (WatchType::SevereThunderstorm, "Tstm"),
+    };
+
+    let mut max_hail_size = None;
+    let mut max_wind_gust_kt = None;
+    let mut is_pds = false;
+    for attribute in ATTRIBUTE_REGEX.captures_iter(text) {
+        match &attribute["name"] {
+            "MAX HAIL" => max_hail_size = attribute["value"].parse().ok(),
+            "MAX WIND GUSTS" => max_wind_gust_kt = attribute["value"].parse().ok(),
+            _ => is_pds = &attribute["value"] == "YES",
+        }
+    }
+
+    let motion = regexes.storm_motion.captures(text);
+    let probabilities = get_probabilities(text);
+    let pds_text = if is_pds { "PDS " } else { "" };
+    let title = format!(
+        "SPC {}{} Watch {} probabilities",
+        pds_text, watch_type_text, id
+    );
+
+    let watch = Some(Watch {
+        is_pds,
+        id,
+        watch_type,
+        status: WatchStatus::Issued,
+        issued_for: Some(header["states"].to_string()),
+        max_hail_size,
+        max_wind_gust_kt,
+        motion_deg: motion.as_ref().and_then(|x| x["deg"].parse().ok()),
+        motion_kt: motion.as_ref().and_then(|x| x["kt"].parse().ok()),
+        replaces_ids: vec![],
+        probabilities,
+    });
+
+    let event = Event {
+        afd: None,
+        event_ts,
+        event_type: EventType::NwsWwp,
+        expires_ts,
+        ext_uri: None,
+        fire: None,
+        fire_outlook: None,
+        ingest_ts: 0,
+        location: None,
+        md: None,
+        outlook: None,
+        report: None,
+        source_id: None,
+        survey: None,
+        text: Some(text.to_string()),
+        title,
+        tropical: None,
+        valid_ts,
+        warning: None,
+        watch,
+        winter: None,
+    };
+
+    Some(event)
+}
+
+/**
+ * Low probabilities are written as "<02%", which are kept as 2.
+ */
+fn get_probabilities(text: &str) -> Option<WatchProbabilities> {
+    let mut tornadoes = None;
+    let mut strong_tornadoes = None;
+    let mut wind = None;
+    let mut sig_wind = None;
+    let mut hail = None;
+    let mut sig_hail = None;
+    let mut combined = None;
+
+    for probability in PROBABILITY_REGEX.captures_iter(text) {
+        let desc = &probability["desc"];
+        let value = probability["prob"].parse::<u8>().ok();
+        match desc {
+            x if x.contains("COMBINED") => combined = value,
+            x if x.contains("STRONG") => strong_tornadoes = value,
+            x if x.contains("TORNADOES") => tornadoes = value,
+            x if x.contains("WIND EVENTS >=") => sig_wind = value,
+            x if x.contains("WIND") => wind = value,
+            x if x.contains("HAIL EVENTS >=") => sig_hail = value,
+            x if x.contains("HAIL") => hail = value,
+            _ => (),
+        }
+    }
+
+    Some(WatchProbabilities {
+        tornadoes: tornadoes?,
+        strong_tornadoes: strong_tornadoes?,
+        wind: wind?,
+        sig_wind: sig_wind?,
+        hail: hail?,
+        sig_hail: sig_hail?,
+        combined: combined?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_util::get_product_from_file;
+    use super::*;
+
+    #[test]
+    fn parse_tornado_watch_probabilities() {
+        let product = get_product_from_file("../data/products/wwp-tor-watch");
+        let result = parse(&product).unwrap();
+        assert_eq!(result.title, "SPC Tor Watch 26 probabilities");
+        assert_eq!(result.valid_ts, Some(1522775700000000));
+        assert_eq!(result.expires_ts, Some(1522800000000000));
+
+        let serialized_result = serde_json::to_string(&result.watch).unwrap();
+        let expected = r#"{"is_pds":false,"id":26,"watch_type":"Tornado","status":"Issued","issued_for":"IN KY OH","max_hail_size":2.0,"max_wind_gust_kt":60,"motion_deg":240,"motion_kt":35,"replaces_ids":[],"probabilities":{"tornadoes":60,"strong_tornadoes":40,"wind":50,"sig_wind":20,"hail":40,"sig_hail":30,"combined":90}}"#;
+        assert_eq!(expected, serialized_result);
+    }
+
+    #[test]
+    fn get_probabilities_should_handle_low_probabilities() {
+        let text = "
+   PROB OF 2 OR MORE TORNADOES                    :  <02%
+   PROB OF 1 OR MORE STRONG /EF2-EF5/ TORNADOES   :  <02%
+   PROB OF 10 OR MORE SEVERE WIND EVENTS          :  30%
+   PROB OF 1 OR MORE WIND EVENTS >= 65 KNOTS      :  10%
+   PROB OF 10 OR MORE SEVERE HAIL EVENTS          :  60%
+   PROB OF 1 OR MORE HAIL EVENTS >= 2 INCHES      :  40%
+   PROB OF 6 OR MORE COMBINED SEVERE HAIL/WIND EVENTS :  80%";
+        let result = get_probabilities(text).unwrap();
+        assert_eq!(result.tornadoes, 2);
+        assert_eq!(result.strong_tornadoes, 2);
+        assert_eq!(result.hail, 60);
+        assert_eq!(result.combined, 80);
+
+        assert_eq!(get_probabilities("PROB OF 2 OR MORE TORNADOES : 60%"), None);
+    }
+}
@@ -0,0 +1,98 @@
+use crate::http_client::HttpClient;
+use crate::loader::Loader;
+use domain::{Alert, AlertsResult, Event};
+use fnv::FnvHashSet;
+use parsers::alerts_parser;
+
+const API_HOST: &str = "https://api.weather.gov";
+
+/**
+ * Loads warnings from the NWS /alerts API. This is a second source for the warnings parsed from
+ * products, which tend to show up here first. The store reconciles the two by VTEC.
+ */
+pub struct AlertsLoader {
+    client: HttpClient,
+    url: String,
+    seen: FnvHashSet<String>,
+}
+
+impl AlertsLoader {
+    pub fn new(client: HttpClient) -> AlertsLoader {
+        AlertsLoader {
+            client,
+            url: format!("{}/alerts/active?status=actual", API_HOST),
+            seen: FnvHashSet::default(),
+        }
+    }
+}
+
+impl Loader for AlertsLoader {
+    fn name(&self) -> String {
+        "alerts_loader".to_string()
+    }
+
+    fn init(&mut self, checkpoint: Option<String>) {
+        if let Some(seen) = checkpoint.and_then(|x| serde_json::from_str(&x).ok()) {
+            self.seen = seen;
+        }
+    }
+
+    fn poll(&mut self) -> Vec<Event> {
+        poll(&self.client, &self.url, &mut self.seen).unwrap_or_default()
+    }
+
+    fn checkpoint(&self) -> Option<String> {
+        serde_json::to_string(&self.seen).ok()
+    }
+}
+
+/**
+ * Fetches the active alerts and parses those not seen on a previous poll. Active alerts stay in
+ * the response until they expire, so `seen` is replaced with the latest set of ids to keep it
+ * from growing forever.
+ */
+fn poll(client: &HttpClient, url: &str, seen: &mut FnvHashSet<String>) -> Option<Vec<Event>> {
+    let result = client.fetch_geojson::<AlertsResult>(url).ok()?;
+    let latest_set: FnvHashSet<String> = result.features.iter().map(|x| x.id.clone()).collect();
+
+    let new_alerts: Vec<&Alert> = result
+        .features
+        .iter()
+        .filter(|x| !seen.contains(&x.id))
+        .collect();
+    let events = alerts_parser::parse(&new_alerts);
+
+    *seen = latest_set;
+    Some(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::serve_file;
+
+    #[test]
+    fn poll_should_parse_new_warnings() {
+        let url = serve_file("data/alerts-active", "application/geo+json", 1);
+        let client = HttpClient::new();
+        let mut seen = FnvHashSet::default();
+        let events = poll(&client, &url, &mut seen).unwrap();
+
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].title, "Tornado Warning (KTOP)");
+        assert_eq!(events[1].title, "Severe Thunderstorm Warning (KDMX)");
+        assert_eq!(seen.len(), 3);
+    }
+
+    #[test]
+    fn poll_should_skip_seen_alerts() {
+        let url = serve_file("data/alerts-active", "application/geo+json", 2);
+        let client = HttpClient::new();
+        let mut seen = FnvHashSet::default();
+        poll(&client, &url, &mut seen).unwrap();
+        let events = poll(&client, &url, &mut seen).unwrap();
+
+        assert!(events.is_empty());
+        assert_eq!(seen.len(), 3);
+    }
+}
@@ -0,0 +1,260 @@
+use serde::Serialize;
+use std::io::Write;
+use warp::http::header::{HeaderValue, CONTENT_ENCODING, CONTENT_TYPE, VARY};
+use warp::http::StatusCode;
+use warp::reply::Response;
+
+// Small bodies aren't worth the CPU, and compression can make them larger
+const COMPRESSION_THRESHOLD_BYTES: usize = 1024;
+const BROTLI_QUALITY: u32 = 5;
+const BROTLI_WINDOW_SIZE: u32 = 22;
+const BROTLI_BUFFER_SIZE: usize = 4096;
+
+#[derive(Debug, PartialEq)]
+pub enum Encoding {
+    Brotli,
+    Gzip,
+    Identity,
+}
+
+impl Encoding {
+    /**
+     * Picks the encoding the client most prefers from an Accept-Encoding header, favoring brotli
+     * over gzip, and either over identity, when the client has no preference between them. The
+     * `*` wildcard only applies to codings the header doesn't list, so "br;q=0, *" rules out
+     * brotli. Identity only wins when the client prefers it, and is otherwise the fallback when
+     * nothing else is acceptable, even if excluded, since there's nothing else to send.
+     */
+    pub fn negotiate(accept_encoding: Option<&str>) -> Encoding {
+        let accept_encoding = match accept_encoding {
+            Some(value) => value,
+            None => return Encoding::Identity,
+        };
+
+        let mut brotli_q = None;
+        let mut gzip_q = None;
+        let mut identity_q = None;
+        let mut wildcard_q = None;
+
+        for token in accept_encoding.split(',') {
+            let mut parts = token.split(';');
+            let name = parts.next().unwrap_or("").trim().to_lowercase();
+            let q = parts
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .filter_map(|q| q.parse::<f32>().ok())
+                .next()
+                .unwrap_or(1.0);
+
+            match name.as_ref() {
+                "br" => brotli_q = Some(q),
+                "gzip" | "x-gzip" => gzip_q = Some(q),
+                "identity" => identity_q = Some(q),
+                "*" => wildcard_q = Some(q),
+                _ => (),
+            }
+        }
+
+        let brotli_q = brotli_q.or(wildcard_q).unwrap_or(0.0);
+        let gzip_q = gzip_q.or(wildcard_q).unwrap_or(0.0);
+        let identity_q = identity_q.or(wildcard_q).unwrap_or(0.0);
+
+        if brotli_q > 0.0 && brotli_q >= gzip_q && brotli_q >= identity_q {
+            Encoding::Brotli
+        } else if gzip_q > 0.0 && gzip_q >= identity_q {
+            Encoding::Gzip
+        } else {
+            Encoding::Identity
+        }
+    }
+
+    fn header_value(&self) -> Option<&'static str> {
+        match self {
+            Encoding::Brotli => Some("br"),
+            Encoding::Gzip => Some("gzip"),
+            Encoding::Identity => None,
+        }
+    }
+}
+
+/**
+ * Serializes the value as JSON, compressing it if it's large enough and the client accepts a
+ * supported encoding.
+ */
+pub fn json<T: Serialize>(value: &T, accept_encoding: Option<String>) -> Response {
+    json_as(value, "application/json", accept_encoding)
+}
+
+/**
+ * Same as `json`, for JSON-based formats with their own media type.
+ */
+pub fn json_as<T: Serialize>(
+    value: &T,
+    content_type: &'static str,
+    accept_encoding: Option<String>,
+) -> Response {
+    match serde_json::to_vec(value) {
+        Ok(body) => reply(body, content_type, accept_encoding),
+        Err(e) => {
+            error!("Unable to serialize response: {}", e);
+            let mut response = Response::new(Vec::new().into());
+            *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+            response
+        }
+    }
+}
+
+/**
+ * Builds a response for an already serialized body, compressing it if it's large enough and the
+ * client accepts a supported encoding.
+ */
+pub fn reply(
+    body: Vec<u8>,
+    content_type: &'static str,
+    accept_encoding: Option<String>,
+) -> Response {
+    let encoding = if body.len() < COMPRESSION_THRESHOLD_BYTES {
+        Encoding::Identity
+    } else {
+        Encoding::negotiate(accept_encoding.as_deref())
+    };
+
+    let (body, encoding) = match compress(body, &encoding) {
+        Ok(compressed) => (compressed, encoding),
+        Err((body, e)) => {
+            warn!("Unable to compress response with {:?}: {}", encoding, e);
+            (body, Encoding::Identity)
+        }
+    };
+
+    let mut response = Response::new(body.into());
+    let headers = response.headers_mut();
+    headers.insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+    headers.insert(VARY, HeaderValue::from_static("Accept-Encoding"));
+    if let Some(value) = encoding.header_value() {
+        headers.insert(CONTENT_ENCODING, HeaderValue::from_static(value));
+    }
+
+    response
+}
+
+/**
+ * Returns the original body alongside the error if compression fails, so it can still be sent.
+ */
+fn compress(body: Vec<u8>, encoding: &Encoding) -> Result<Vec<u8>, (Vec<u8>, std::io::Error)> {
+    let result = match encoding {
+        Encoding::Identity => return Ok(body),
+        Encoding::Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&body).and_then(|_| encoder.finish())
+        }
+        Encoding::Brotli => {
+            let mut writer = brotli::CompressorWriter::new(
+                Vec::new(),
+                BROTLI_BUFFER_SIZE,
+                BROTLI_QUALITY,
+                BROTLI_WINDOW_SIZE,
+            );
+            writer
+                .write_all(&body)
+                .and_then(|_| writer.flush())
+                .map(|_| writer.into_inner())
+        }
+    };
+
+    result.map_err(|e| (body, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    #[test]
+    fn negotiate_should_prefer_brotli() {
+        let result = Encoding::negotiate(Some("gzip, deflate, br"));
+        assert_eq!(result, Encoding::Brotli);
+    }
+
+    #[test]
+    fn negotiate_should_respect_q_values() {
+        let result = Encoding::negotiate(Some("br;q=0.5, gzip;q=0.8"));
+        assert_eq!(result, Encoding::Gzip);
+        let result = Encoding::negotiate(Some("br;q=0, gzip"));
+        assert_eq!(result, Encoding::Gzip);
+        let result = Encoding::negotiate(Some("*;q=0.1"));
+        assert_eq!(result, Encoding::Brotli);
+    }
+
+    #[test]
+    fn negotiate_should_fall_back_to_identity() {
+        assert_eq!(Encoding::negotiate(None), Encoding::Identity);
+        assert_eq!(Encoding::negotiate(Some("deflate")), Encoding::Identity);
+        assert_eq!(Encoding::negotiate(Some("gzip;q=0")), Encoding::Identity);
+    }
+
+    #[test]
+    fn negotiate_should_only_apply_wildcard_to_unlisted_codings() {
+        assert_eq!(Encoding::negotiate(Some("br;q=0, *")), Encoding::Gzip);
+        assert_eq!(Encoding::negotiate(Some("gzip;q=0, *")), Encoding::Brotli);
+        assert_eq!(
+            Encoding::negotiate(Some("br;q=0, gzip;q=0, *")),
+            Encoding::Identity
+        );
+        assert_eq!(Encoding::negotiate(Some("*;q=0")), Encoding::Identity);
+    }
+
+    #[test]
+    fn negotiate_should_respect_identity() {
+        assert_eq!(
+            Encoding::negotiate(Some("identity, gzip;q=0.5")),
+            Encoding::Identity
+        );
+        assert_eq!(
+            Encoding::negotiate(Some("identity;q=0, gzip;q=0.1")),
+            Encoding::Gzip
+        );
+        assert_eq!(
+            Encoding::negotiate(Some("identity;q=0")),
+            Encoding::Identity
+        );
+    }
+
+    #[test]
+    fn json_should_not_compress_small_bodies() {
+        let response = json(&"small", Some("gzip".to_string()));
+        assert!(response.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(response.headers()[VARY], "Accept-Encoding");
+    }
+
+    #[test]
+    fn json_should_compress_large_bodies() {
+        let value = "x".repeat(COMPRESSION_THRESHOLD_BYTES * 4);
+        let response = json(&value, Some("gzip".to_string()));
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+
+        let response = json(&value, Some("br".to_string()));
+        assert_eq!(response.headers()[CONTENT_ENCODING], "br");
+    }
+
+    #[test]
+    fn compress_should_round_trip() {
+        let body = serde_json::to_vec(&"sware ".repeat(1000)).unwrap();
+
+        let gzipped = compress(body.clone(), &Encoding::Gzip).unwrap();
+        assert!(gzipped.len() < body.len());
+        let mut decoded = vec![];
+        flate2::read::GzDecoder::new(&gzipped[..])
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, body);
+
+        let brotlied = compress(body.clone(), &Encoding::Brotli).unwrap();
+        assert!(brotlied.len() < body.len());
+        let mut decoded = vec![];
+        brotli::Decompressor::new(&brotlied[..], BROTLI_BUFFER_SIZE)
+            .read_to_end(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, body);
+    }
+}
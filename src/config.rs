@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::time::Duration;
+
+const CONFIG_PATH_VAR: &str = "SWARE_CONFIG";
+const DEFAULT_CONFIG_PATH: &str = "sware.json";
+
+/**
+ * Server configuration, read from the JSON file at $SWARE_CONFIG or ./sware.json. Missing files
+ * and fields fall back to the defaults, so a config is only needed to change something.
+ */
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub loaders: Vec<String>, // alerts, mping, nws, sn, spc
+    pub sn_source: SnSource,
+    pub mping_token: Option<String>,
+    pub base_url: String, // where clients reach the server, ie. for KML network links
+    pub timeouts: HashMap<String, u64>, // request timeout seconds by loader, ie. {"nws": 30}
+}
+
+/**
+ * Where the sn loader gets reports from. The placefile is the default, since the API endpoint and
+ * its report schema haven't been verified against the live service yet.
+ */
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SnSource {
+    Api,
+    Placefile,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            loaders: vec!["sn".to_string(), "nws".to_string(), "alerts".to_string()],
+            sn_source: SnSource::Placefile,
+            mping_token: None,
+            base_url: "http://localhost:8080".to_string(),
+            timeouts: HashMap::new(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Config {
+        let path = env::var(CONFIG_PATH_VAR).unwrap_or_else(|_| DEFAULT_CONFIG_PATH.to_string());
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => Config::parse(&contents).unwrap_or_else(|_| {
+                warn!("Using default config");
+                Config::default()
+            }),
+            Err(_) => {
+                info!("No config found at {}, using defaults", path);
+                Config::default()
+            }
+        }
+    }
+
+    pub fn get_timeout(&self, loader: &str) -> Option<Duration> {
+        self.timeouts.get(loader).map(|x| Duration::from_secs(*x))
+    }
+
+    fn parse(contents: &str) -> Result<Config, ()> {
+        serde_json::from_str(contents).map_err(|e| error!("Unable to parse config: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_should_default_missing_fields() {
+        let config = Config::parse("{}").unwrap();
+        assert_eq!(config.loaders, vec!["sn", "nws", "alerts"]);
+        assert_eq!(config.sn_source, SnSource::Placefile);
+        assert_eq!(config.base_url, "http://localhost:8080");
+        assert_eq!(config.get_timeout("nws"), None);
+
+        let config =
+            Config::parse(r#"{"loaders":["nws"],"sn_source":"api","timeouts":{"nws":30}}"#)
+                .unwrap();
+        assert_eq!(config.loaders, vec!["nws"]);
+        assert_eq!(config.sn_source, SnSource::Api);
+        assert_eq!(config.get_timeout("nws"), Some(Duration::from_secs(30)));
+        assert_eq!(config.get_timeout("sn"), None);
+
+        assert!(Config::parse("loaders").is_err());
+    }
+}
@@ -1,86 +1,316 @@
+use chrono::prelude::*;
 use reqwest::blocking::{Client, Response};
-use reqwest::header::{ACCEPT, USER_AGENT};
+use reqwest::header::{ACCEPT, AUTHORIZATION, RETRY_AFTER, USER_AGENT};
 use reqwest::StatusCode;
 use serde::de::DeserializeOwned;
+use std::fmt;
 use std::thread;
 use std::time::Duration;
 
-const APP_USER_AGENT: &str = "sigtor.org";
-const MAX_RETRIES: usize = 3;
+// api.weather.gov requires a User-Agent that identifies the app and a way to contact its owner
+const APP_USER_AGENT: &str = concat!(
+    "sware-server/",
+    env!("CARGO_PKG_VERSION"),
+    " (sigtor.org, rhurkes@gmail.com)"
+);
+const MAX_RETRIES: u32 = 3;
+const BASE_BACKOFF_MILLIS: u64 = 500;
+const MAX_BACKOFF_MILLIS: u64 = 30_000;
+const DEFAULT_TIMEOUT_SECONDS: u64 = 15;
 
+/**
+ * Reasons a fetch can fail. Only some of these are worth retrying.
+ */
+#[derive(Debug, PartialEq)]
+pub enum HttpError {
+    Timeout,
+    Connect(String),
+    ClientError(StatusCode),
+    ServerError(StatusCode),
+    RateLimited(Option<Duration>),
+    Decode(String),
+}
+
+impl HttpError {
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            HttpError::Timeout => true,
+            HttpError::Connect(_) => true,
+            HttpError::ServerError(_) => true,
+            HttpError::RateLimited(_) => true,
+            HttpError::ClientError(_) => false,
+            HttpError::Decode(_) => false,
+        }
+    }
+
+    fn from_status(status: StatusCode, retry_after: Option<Duration>) -> HttpError {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            HttpError::RateLimited(retry_after)
+        } else if status.is_server_error() {
+            HttpError::ServerError(status)
+        } else {
+            HttpError::ClientError(status)
+        }
+    }
+}
+
+impl fmt::Display for HttpError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HttpError::Timeout => write!(f, "timed out"),
+            HttpError::Connect(e) => write!(f, "connection failed: {}", e),
+            HttpError::ClientError(status) => write!(f, "client error: {}", status),
+            HttpError::ServerError(status) => write!(f, "server error: {}", status),
+            HttpError::RateLimited(Some(delay)) => {
+                write!(f, "rate limited, retry after {}s", delay.as_secs())
+            }
+            HttpError::RateLimited(None) => write!(f, "rate limited"),
+            HttpError::Decode(e) => write!(f, "unable to decode body: {}", e),
+        }
+    }
+}
+
+/**
+ * Cloning is cheap, and clones share the underlying connection pool.
+ */
+#[derive(Clone)]
 pub struct HttpClient {
     client: Client,
+    timeout: Duration,
 }
 
 impl HttpClient {
     pub fn new() -> HttpClient {
+        HttpClient::with_timeout(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS))
+    }
+
+    /**
+     * Creates a client whose requests are abandoned after `timeout`. Each attempt gets the full
+     * timeout, so a fetch can take up to (MAX_RETRIES + 1) * timeout plus backoff in total.
+     */
+    pub fn with_timeout(timeout: Duration) -> HttpClient {
         let client = Client::new();
-        HttpClient { client }
-    }
-
-    pub fn fetch_text(&self, url: &str) -> Result<String, ()> {
-        match self.fetch(url, false) {
-            Ok(resp) => match resp.text() {
-                Ok(value) => Ok(value),
-                Err(e) => {
-                    warn!("Unable to consume body: {}", e);
-                    Err(())
-                }
-            },
-            Err(_) => Err(()),
-        }
+        HttpClient { client, timeout }
     }
 
-    pub fn fetch_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, ()> {
-        match self.fetch(url, true) {
-            Ok(resp) => match resp.json() {
-                Ok(value) => Ok(value),
-                Err(e) => {
-                    warn!("Unable to deserialize: {}, {}", e, url);
-                    Err(())
-                }
-            },
-            Err(_) => Err(()),
-        }
+    pub fn fetch_text(&self, url: &str) -> Result<String, HttpError> {
+        let resp = self.fetch(url, "text/plain", None)?;
+        resp.text().map_err(|e| {
+            let error = HttpError::Decode(e.to_string());
+            warn!("Unable to consume body of {}: {}", url, error);
+            error
+        })
     }
 
-    fn fetch(&self, url: &str, is_json: bool) -> Result<Response, ()> {
-        self.fetch_with_retry(url, is_json, 0)
+    pub fn fetch_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, HttpError> {
+        self.fetch_deserialized(url, "application/json", None)
     }
 
-    fn fetch_with_retry(&self, url: &str, is_json: bool, attempts: usize) -> Result<Response, ()> {
-        let accept_header = if is_json {
-            "application/json"
-        } else {
-            "text/plain"
-        };
+    /**
+     * Same as `fetch_json`, for APIs that require an Authorization header.
+     */
+    pub fn fetch_json_authorized<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        authorization: &str,
+    ) -> Result<T, HttpError> {
+        self.fetch_deserialized(url, "application/json", Some(authorization))
+    }
+
+    /**
+     * The NWS API returns the same documents as JSON-LD unless GeoJSON is requested explicitly.
+     */
+    pub fn fetch_geojson<T: DeserializeOwned>(&self, url: &str) -> Result<T, HttpError> {
+        self.fetch_deserialized(url, "application/geo+json", None)
+    }
+
+    fn fetch_deserialized<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        accept: &'static str,
+        authorization: Option<&str>,
+    ) -> Result<T, HttpError> {
+        let resp = self.fetch(url, accept, authorization)?;
+        resp.json().map_err(|e| {
+            let error = HttpError::Decode(e.to_string());
+            warn!("Unable to deserialize {}: {}", url, error);
+            error
+        })
+    }
+
+    fn fetch(
+        &self,
+        url: &str,
+        accept: &'static str,
+        authorization: Option<&str>,
+    ) -> Result<Response, HttpError> {
+        let mut attempts = 0;
+
+        loop {
+            let error = match self.send(url, accept, authorization) {
+                Ok(resp) => return Ok(resp),
+                Err(e) => e,
+            };
+
+            if !error.is_retryable() {
+                warn!("Unsuccessful HTTP call {}: {}", url, error);
+                return Err(error);
+            }
+
+            if attempts >= MAX_RETRIES {
+                warn!("Max number of retries for {}: {}", url, error);
+                return Err(error);
+            }
 
-        // Backoff just a bit when retrying
-        thread::sleep(Duration::from_secs(attempts as u64));
+            let delay = match error {
+                HttpError::RateLimited(Some(retry_after)) => retry_after,
+                _ => get_backoff(attempts, util::get_system_micros()),
+            };
 
-        match self
+            info!("Retrying {} in {}ms: {}", url, delay.as_millis(), error);
+            thread::sleep(delay);
+            attempts += 1;
+        }
+    }
+
+    fn send(
+        &self,
+        url: &str,
+        accept: &'static str,
+        authorization: Option<&str>,
+    ) -> Result<Response, HttpError> {
+        let mut request = self
             .client
             .get(url)
-            .header(ACCEPT, accept_header)
+            .header(ACCEPT, accept)
             .header(USER_AGENT, APP_USER_AGENT)
-            .send()
-        {
-            Ok(resp) => {
-                if resp.status() != StatusCode::OK {
-                    warn!("Unsuccessful HTTP call {}: {}", resp.url(), resp.status());
-                    return Err(());
-                }
-                Ok(resp)
+            .timeout(self.timeout);
+        if let Some(authorization) = authorization {
+            request = request.header(AUTHORIZATION, authorization);
+        }
+
+        let resp = request.send().map_err(|e| {
+            if e.is_timeout() {
+                HttpError::Timeout
+            } else {
+                HttpError::Connect(e.to_string())
             }
-            Err(_) => {
-                if attempts < MAX_RETRIES {
-                    info!("Retrying {}", url);
-                    self.fetch_with_retry(url, is_json, attempts + 1)
-                } else {
-                    warn!("Max number of retries for {}", url);
-                    Err(())
-                }
+        })?;
+
+        if resp.status() == StatusCode::OK {
+            return Ok(resp);
+        }
+
+        let retry_after = resp
+            .headers()
+            .get(RETRY_AFTER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| parse_retry_after(value, Utc::now()));
+
+        Err(HttpError::from_status(resp.status(), retry_after))
+    }
+}
+
+/**
+ * Exponential backoff capped at MAX_BACKOFF_MILLIS, with up to 50% jitter so that the fetcher
+ * threads don't all retry against the API in lockstep. `seed` only needs to vary between calls.
+ */
+fn get_backoff(attempts: u32, seed: u64) -> Duration {
+    let exponential = BASE_BACKOFF_MILLIS.saturating_mul(1 << attempts.min(16));
+    let capped = exponential.min(MAX_BACKOFF_MILLIS);
+    let jitter = seed % (capped / 2 + 1);
+    Duration::from_millis(capped / 2 + jitter)
+}
+
+/**
+ * Retry-After can either be a number of seconds or an HTTP date. Either way the delay is capped at
+ * MAX_BACKOFF_MILLIS, so a server asking for a day doesn't stall the loader thread for a day.
+ */
+fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    let max_delay = Duration::from_millis(MAX_BACKOFF_MILLIS);
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds).min(max_delay));
+    }
+
+    match DateTime::parse_from_rfc2822(value) {
+        Ok(dt) => {
+            let seconds = (dt.with_timezone(&Utc) - now).num_seconds().max(0);
+            Some(Duration::from_secs(seconds as u64).min(max_delay))
+        }
+        Err(_) => {
+            warn!("Unable to parse Retry-After: {}", value);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_transient_errors_should_be_retryable() {
+        assert!(HttpError::Timeout.is_retryable());
+        assert!(HttpError::Connect("refused".to_string()).is_retryable());
+        assert!(HttpError::ServerError(StatusCode::BAD_GATEWAY).is_retryable());
+        assert!(HttpError::RateLimited(None).is_retryable());
+        assert!(!HttpError::ClientError(StatusCode::NOT_FOUND).is_retryable());
+        assert!(!HttpError::Decode("eof".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn from_status_should_classify_status_codes() {
+        assert_eq!(
+            HttpError::from_status(StatusCode::TOO_MANY_REQUESTS, None),
+            HttpError::RateLimited(None)
+        );
+        assert_eq!(
+            HttpError::from_status(StatusCode::SERVICE_UNAVAILABLE, None),
+            HttpError::ServerError(StatusCode::SERVICE_UNAVAILABLE)
+        );
+        assert_eq!(
+            HttpError::from_status(StatusCode::FORBIDDEN, None),
+            HttpError::ClientError(StatusCode::FORBIDDEN)
+        );
+    }
+
+    #[test]
+    fn get_backoff_should_grow_exponentially_within_bounds() {
+        for attempts in 0..10 {
+            let capped = (BASE_BACKOFF_MILLIS << attempts).min(MAX_BACKOFF_MILLIS);
+            for seed in &[0, 1, 7919, u64::MAX] {
+                let backoff = get_backoff(attempts, *seed).as_millis() as u64;
+                assert!(backoff >= capped / 2);
+                assert!(backoff <= capped);
             }
         }
     }
+
+    #[test]
+    fn parse_retry_after_should_handle_seconds_and_dates() {
+        let now = DateTime::parse_from_rfc3339("2020-04-20T12:00:00+00:00")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(parse_retry_after("20", now), Some(Duration::from_secs(20)));
+        assert_eq!(
+            parse_retry_after("Mon, 20 Apr 2020 12:00:30 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Mon, 20 Apr 2020 11:00:00 GMT", now),
+            Some(Duration::from_secs(0))
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn parse_retry_after_should_cap_delay() {
+        let now = Utc::now();
+        let max_delay = Some(Duration::from_millis(MAX_BACKOFF_MILLIS));
+        assert_eq!(parse_retry_after("86400", now), max_delay);
+        let tomorrow = (now + chrono::Duration::days(1)).to_rfc2822();
+        assert_eq!(parse_retry_after(&tomorrow, now), max_delay);
+    }
 }
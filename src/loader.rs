@@ -0,0 +1,345 @@
+use crate::alerts_loader::AlertsLoader;
+use crate::config::{Config, SnSource};
+use crate::http_client::HttpClient;
+use crate::mping_loader::MpingLoader;
+use crate::nws_loader::{NwsLoader, PRODUCT_CODES};
+use crate::sn_api_loader::SnApiLoader;
+use crate::sn_loader::SnLoader;
+use crate::spc_reports_loader::SpcReportsLoader;
+use crate::store::Store;
+use domain::Event;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+const DEFAULT_POLL_INTERVAL_SECONDS: u64 = 60;
+const SHUTDOWN_CHECK_MILLIS: u64 = 500;
+
+/**
+ * A source of events. Loaders only know how to fetch and parse their source; the registry owns
+ * the polling loop, writes events and checkpoints to the store and handles the lifecycle:
+ * init -> (poll -> checkpoint)* -> shutdown.
+ */
+pub trait Loader: Send {
+    /**
+     * Used as the thread name and in logs.
+     */
+    fn name(&self) -> String;
+
+    fn poll_interval(&self) -> Duration {
+        Duration::from_secs(DEFAULT_POLL_INTERVAL_SECONDS)
+    }
+
+    /**
+     * Receives the last persisted checkpoint, if any, so the loader can pick up where it left off
+     * after a restart.
+     */
+    fn init(&mut self, _checkpoint: Option<String>) {}
+
+    /**
+     * Returns events that haven't been returned by a previous poll.
+     */
+    fn poll(&mut self) -> Vec<Event>;
+
+    /**
+     * Called after the events from a poll have been written. The returned cursor is persisted and
+     * passed to `init` on the next start.
+     */
+    fn checkpoint(&self) -> Option<String> {
+        None
+    }
+
+    fn shutdown(&mut self) {}
+}
+
+/**
+ * Where the registry writes events and checkpoints. Implemented by the store, and abstracted so
+ * the polling loop can be tested without RocksDB.
+ */
+pub trait Sink {
+    fn put_event(&self, event: &mut Event);
+    fn get_checkpoint(&self, name: &str) -> Option<String>;
+    fn put_checkpoint(&self, name: &str, checkpoint: &str);
+}
+
+impl Sink for Store {
+    fn put_event(&self, event: &mut Event) {
+        Store::put_event(self, event)
+    }
+
+    fn get_checkpoint(&self, name: &str) -> Option<String> {
+        Store::get_checkpoint(self, name)
+    }
+
+    fn put_checkpoint(&self, name: &str, checkpoint: &str) {
+        Store::put_checkpoint(self, name, checkpoint)
+    }
+}
+
+pub struct Registry {
+    loaders: Vec<Box<dyn Loader>>,
+    running: Arc<AtomicBool>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry {
+            loaders: vec![],
+            running: Arc::new(AtomicBool::new(true)),
+            handles: vec![],
+        }
+    }
+
+    /**
+     * Registers the loaders enabled in the config. Unknown names are logged and skipped. Each
+     * loader's HTTP client uses the timeout configured for it, if any, and the NWS product loaders
+     * share theirs.
+     */
+    pub fn from_config(config: &Config) -> Registry {
+        let mut registry = Registry::new();
+
+        for name in &config.loaders {
+            let client = match config.get_timeout(name) {
+                Some(timeout) => HttpClient::with_timeout(timeout),
+                None => HttpClient::new(),
+            };
+
+            match name.as_ref() {
+                "alerts" => registry.register(Box::new(AlertsLoader::new(client))),
+                "mping" => match &config.mping_token {
+                    Some(token) => registry.register(Box::new(MpingLoader::new(token, client))),
+                    None => warn!("The mping loader needs an mping_token in the config"),
+                },
+                "nws" => PRODUCT_CODES.iter().for_each(|code| {
+                    registry.register(Box::new(NwsLoader::new(code, client.clone())))
+                }),
+                "sn" => match config.sn_source {
+                    SnSource::Api => registry.register(Box::new(SnApiLoader::new(client))),
+                    SnSource::Placefile => registry.register(Box::new(SnLoader::new(client))),
+                },
+                "spc" => registry.register(Box::new(SpcReportsLoader::new(client))),
+                _ => warn!("Unknown loader in config: {}", name),
+            }
+        }
+
+        registry
+    }
+
+    pub fn register(&mut self, loader: Box<dyn Loader>) {
+        self.loaders.push(loader);
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        self.loaders.iter().map(|x| x.name()).collect()
+    }
+
+    /**
+     * Starts each registered loader on its own thread.
+     */
+    pub fn start(&mut self, store: &Arc<Store>) {
+        for mut loader in self.loaders.drain(..) {
+            let store = store.clone();
+            let running = self.running.clone();
+            let handle = thread::Builder::new()
+                .name(loader.name())
+                .spawn(move || run(loader.as_mut(), &running, store.as_ref()))
+                .expect("Unable to create thread");
+            self.handles.push(handle);
+        }
+    }
+
+    /**
+     * Signals every loader to stop after its current poll and waits for them to shut down.
+     */
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        for handle in self.handles.drain(..) {
+            if handle.join().is_err() {
+                error!("Loader thread panicked");
+            }
+        }
+    }
+}
+
+fn run(loader: &mut dyn Loader, running: &AtomicBool, sink: &dyn Sink) {
+    let name = loader.name();
+    info!("starting");
+    loader.init(sink.get_checkpoint(&name));
+
+    while running.load(Ordering::SeqCst) {
+        let start = util::get_system_secs();
+
+        loader
+            .poll()
+            .iter_mut()
+            .for_each(|event| sink.put_event(event));
+        if let Some(checkpoint) = loader.checkpoint() {
+            sink.put_checkpoint(&name, &checkpoint);
+        }
+
+        let elapsed_seconds = util::get_system_secs() - start;
+        let delay = loader
+            .poll_interval()
+            .as_secs()
+            .saturating_sub(elapsed_seconds);
+        sleep_while_running(Duration::from_secs(delay), running);
+    }
+
+    loader.shutdown();
+    info!("stopped");
+}
+
+/**
+ * Sleeps in small increments so that a stop request doesn't wait out a full poll interval.
+ */
+fn sleep_while_running(duration: Duration, running: &AtomicBool) {
+    let step = Duration::from_millis(SHUTDOWN_CHECK_MILLIS);
+    let mut remaining = duration;
+
+    while running.load(Ordering::SeqCst) && remaining > Duration::from_secs(0) {
+        let delay = remaining.min(step);
+        thread::sleep(delay);
+        remaining -= delay;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    struct TestLoader {
+        calls: Arc<Mutex<Vec<&'static str>>>,
+        running: Arc<AtomicBool>,
+        checkpoint: Option<String>,
+    }
+
+    #[derive(Default)]
+    struct TestSink {
+        checkpoints: Mutex<Vec<(String, String)>>,
+    }
+
+    impl Sink for TestSink {
+        fn put_event(&self, _event: &mut Event) {}
+
+        fn get_checkpoint(&self, _name: &str) -> Option<String> {
+            Some("1".to_string())
+        }
+
+        fn put_checkpoint(&self, name: &str, checkpoint: &str) {
+            let mut checkpoints = self.checkpoints.lock().unwrap();
+            checkpoints.push((name.to_string(), checkpoint.to_string()));
+        }
+    }
+
+    impl Loader for TestLoader {
+        fn name(&self) -> String {
+            "test".to_string()
+        }
+
+        fn poll_interval(&self) -> Duration {
+            Duration::from_secs(0)
+        }
+
+        fn init(&mut self, checkpoint: Option<String>) {
+            self.calls.lock().unwrap().push("init");
+            self.checkpoint = checkpoint;
+        }
+
+        fn poll(&mut self) -> Vec<Event> {
+            self.calls.lock().unwrap().push("poll");
+            self.running.store(false, Ordering::SeqCst);
+            self.checkpoint = Some("2".to_string());
+            vec![]
+        }
+
+        fn checkpoint(&self) -> Option<String> {
+            self.calls.lock().unwrap().push("checkpoint");
+            self.checkpoint.clone()
+        }
+
+        fn shutdown(&mut self) {
+            self.calls.lock().unwrap().push("shutdown");
+        }
+    }
+
+    #[test]
+    fn run_should_follow_lifecycle() {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let running = Arc::new(AtomicBool::new(true));
+        let mut loader = TestLoader {
+            calls: calls.clone(),
+            running: running.clone(),
+            checkpoint: None,
+        };
+        let sink = TestSink::default();
+
+        run(&mut loader, &running, &sink);
+
+        let calls = calls.lock().unwrap();
+        assert_eq!(*calls, vec!["init", "poll", "checkpoint", "shutdown"]);
+        let checkpoints = sink.checkpoints.lock().unwrap();
+        assert_eq!(*checkpoints, vec![("test".to_string(), "2".to_string())]);
+    }
+
+    #[test]
+    fn run_should_restore_checkpoint() {
+        let running = Arc::new(AtomicBool::new(false));
+        let mut loader = TestLoader {
+            calls: Arc::new(Mutex::new(vec![])),
+            running: running.clone(),
+            checkpoint: None,
+        };
+
+        run(&mut loader, &running, &TestSink::default());
+        assert_eq!(loader.checkpoint, Some("1".to_string()));
+    }
+
+    #[test]
+    fn from_config_should_register_enabled_loaders() {
+        let config = Config {
+            loaders: vec!["sn".to_string(), "unknown".to_string()],
+            ..Config::default()
+        };
+        let registry = Registry::from_config(&config);
+        assert_eq!(registry.names(), vec!["sn_loader"]);
+
+        let config = Config {
+            loaders: vec!["sn".to_string()],
+            sn_source: SnSource::Api,
+            ..Config::default()
+        };
+        let registry = Registry::from_config(&config);
+        assert_eq!(registry.names(), vec!["sn_api_loader"]);
+
+        let config = Config {
+            loaders: vec!["nws".to_string()],
+            ..Config::default()
+        };
+        let registry = Registry::from_config(&config);
+        assert_eq!(registry.names().len(), PRODUCT_CODES.len());
+        assert_eq!(registry.names()[0], "afd_fetcher");
+
+        let config = Config {
+            loaders: vec!["mping".to_string()],
+            ..Config::default()
+        };
+        assert!(Registry::from_config(&config).names().is_empty());
+
+        let config = Config {
+            loaders: vec!["mping".to_string()],
+            mping_token: Some("token".to_string()),
+            ..Config::default()
+        };
+        assert_eq!(Registry::from_config(&config).names(), vec!["mping_loader"]);
+
+        let config = Config {
+            loaders: vec!["spc".to_string()],
+            ..Config::default()
+        };
+        let registry = Registry::from_config(&config);
+        assert_eq!(registry.names(), vec!["spc_reports_loader"]);
+    }
+}
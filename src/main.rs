@@ -2,47 +2,80 @@
 extern crate lazy_static;
 #[macro_use]
 extern crate log;
+#[macro_use]
+extern crate serde_derive;
 
+use chrono::prelude::*;
+use config::Config;
+use domain::atom::{self, FeedFilter};
+use domain::cap;
+use domain::geojson::FeatureCollection;
+use domain::kml;
+use domain::reports;
+use loader::Registry;
 use log::LevelFilter;
+use std::env;
+use std::process;
 use std::sync::Arc;
-use std::thread;
 use store::Store;
-use warp::Filter;
+use warp::http::header::CONTENT_TYPE;
+use warp::http::StatusCode;
+use warp::{Filter, Reply};
 
+mod alerts_loader;
+mod compression;
+mod config;
 mod http_client;
+mod loader;
+mod migrations;
+mod mping_loader;
 mod nws_loader;
+mod sn_api_loader;
 mod sn_loader;
+mod spc_reports_loader;
 mod store;
+#[cfg(test)]
+mod test_util;
+
+const PORT: u16 = 8080;
+const KML_CONTENT_TYPE: &str = "application/vnd.google-earth.kml+xml";
+const NETWORK_LINK_REFRESH_SECONDS: u32 = 60;
 
 #[tokio::main]
 async fn main() {
     env_logger::builder().filter_level(LevelFilter::Info).init();
+
+    // sware-server import-spc <YYMMDD_rpts_filtered.csv>...
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("import-spc") {
+        match spc_reports_loader::import(&args[1..], &Store::new()) {
+            Ok(count) => info!("Imported {} SPC reports", count),
+            Err(_) => process::exit(1),
+        }
+        return;
+    }
+
+    let config = Config::load();
     let store = Arc::new(Store::new());
-    let mut threads = vec![];
-    let sn_store = store.clone();
-    let nws_api_store = store.clone();
-
-    // Run SpotterNetwork loader
-    threads.push(
-        thread::Builder::new()
-            .name("sn_loader".to_string())
-            .spawn(move || {
-                sn_loader::run(&sn_store);
-            }),
-    );
+    let mut registry = Registry::from_config(&config);
+    info!("Starting loaders: {}", registry.names().join(", "));
+    registry.start(&store);
 
-    // Run NWS API loader
-    threads.push(
-        thread::Builder::new()
-            .name("nws_api_loader".to_string())
-            .spawn(move || {
-                nws_loader::run(&nws_api_store);
-            }),
+    let (_, server) = warp::serve(filters(store, config.base_url)).bind_with_graceful_shutdown(
+        ([127, 0, 0, 1], PORT),
+        async {
+            tokio::signal::ctrl_c().await.ok();
+        },
     );
+    server.await;
 
-    warp::serve(filters(store))
-        .run(([127, 0, 0, 1], 8080))
-        .await;
+    info!("Shutting down loaders");
+    registry.stop();
+}
+
+#[derive(Debug, Deserialize)]
+struct EventsQuery {
+    since: Option<u128>,
 }
 
 fn with_store(
@@ -53,8 +86,17 @@ fn with_store(
 
 fn filters(
     store: Arc<Store>,
+    base_url: String,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
-    events_filter(store.clone()).or(stats_filter(store))
+    events_filter(store.clone())
+        .or(geojson_filter(store.clone()))
+        .or(cap_filter(store.clone()))
+        .or(feed_filter(store.clone()))
+        .or(kml_filter(store.clone()))
+        .or(kmz_filter(store.clone()))
+        .or(network_link_filter(base_url))
+        .or(report_counts_filter(store.clone()))
+        .or(stats_filter(store))
 }
 
 // GET /events/:u128
@@ -63,27 +105,208 @@ fn events_filter(
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::path!("events" / u128)
         .and(warp::get())
+        .and(warp::header::optional::<String>("accept-encoding"))
         .and(with_store(store))
         .map(events_handler)
         .with(warp::cors().allow_any_origin())
 }
 
+// GET /events.geojson/:u128
+fn geojson_filter(
+    store: Arc<Store>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("events.geojson" / u128)
+        .and(warp::get())
+        .and(warp::header::optional::<String>("accept-encoding"))
+        .and(with_store(store))
+        .map(geojson_handler)
+        .with(warp::cors().allow_any_origin())
+}
+
+// GET /events/:u128/cap
+fn cap_filter(
+    store: Arc<Store>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("events" / u128 / "cap")
+        .and(warp::get())
+        .and(warp::header::optional::<String>("accept-encoding"))
+        .and(with_store(store))
+        .map(cap_handler)
+        .with(warp::cors().allow_any_origin())
+}
+
+// GET /feeds/:event_type.atom?since=:u128
+fn feed_filter(
+    store: Arc<Store>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("feeds" / String)
+        .and(warp::get())
+        .and(warp::query::<EventsQuery>())
+        .and(warp::header::optional::<String>("accept-encoding"))
+        .and(with_store(store))
+        .map(feed_handler)
+        .with(warp::cors().allow_any_origin())
+}
+
+// GET /events.kml?since=:u128
+fn kml_filter(
+    store: Arc<Store>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("events.kml")
+        .and(warp::get())
+        .and(warp::query::<EventsQuery>())
+        .and(warp::header::optional::<String>("accept-encoding"))
+        .and(with_store(store))
+        .map(kml_handler)
+        .with(warp::cors().allow_any_origin())
+}
+
+// GET /events.kmz?since=:u128
+fn kmz_filter(
+    store: Arc<Store>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("events.kmz")
+        .and(warp::get())
+        .and(warp::query::<EventsQuery>())
+        .and(with_store(store))
+        .map(kmz_handler)
+        .with(warp::cors().allow_any_origin())
+}
+
+// GET /network-link.kml
+fn network_link_filter(
+    base_url: String,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("network-link.kml")
+        .and(warp::get())
+        .and(warp::any().map(move || base_url.clone()))
+        .map(network_link_handler)
+}
+
+// GET /reports/counts?since=:u128
+fn report_counts_filter(
+    store: Arc<Store>,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::path!("reports" / "counts")
+        .and(warp::get())
+        .and(warp::query::<EventsQuery>())
+        .and(warp::header::optional::<String>("accept-encoding"))
+        .and(with_store(store))
+        .map(report_counts_handler)
+        .with(warp::cors().allow_any_origin())
+}
+
 // GET /stats
 fn stats_filter(
     store: Arc<Store>,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     warp::path!("stats")
         .and(warp::get())
+        .and(warp::header::optional::<String>("accept-encoding"))
         .and(with_store(store))
         .map(stats_handler)
 }
 
-fn events_handler(id: u128, store: Arc<Store>) -> impl warp::Reply {
+fn events_handler(
+    id: u128,
+    accept_encoding: Option<String>,
+    store: Arc<Store>,
+) -> impl warp::Reply {
+    let events = store.get_events(id);
+    compression::json(&events, accept_encoding)
+}
+
+fn geojson_handler(
+    id: u128,
+    accept_encoding: Option<String>,
+    store: Arc<Store>,
+) -> impl warp::Reply {
     let events = store.get_events(id);
-    warp::reply::json(&events)
+    let collection = FeatureCollection::new(&events);
+    compression::json_as(&collection, "application/geo+json", accept_encoding)
+}
+
+fn cap_handler(
+    id: u128,
+    accept_encoding: Option<String>,
+    store: Arc<Store>,
+) -> warp::reply::Response {
+    match store.get_event(id).as_ref().and_then(cap::to_cap) {
+        Some(xml) => compression::reply(xml.into_bytes(), "application/cap+xml", accept_encoding),
+        None => warp::reply::with_status(warp::reply(), StatusCode::NOT_FOUND).into_response(),
+    }
+}
+
+fn feed_handler(
+    file_name: String,
+    query: EventsQuery,
+    accept_encoding: Option<String>,
+    store: Arc<Store>,
+) -> warp::reply::Response {
+    let filter = match file_name
+        .strip_suffix(".atom")
+        .and_then(FeedFilter::from_name)
+    {
+        Some(filter) => filter,
+        None => {
+            return warp::reply::with_status(warp::reply(), StatusCode::NOT_FOUND).into_response()
+        }
+    };
+
+    let events = store.get_events(query.since.unwrap_or(0));
+    let feed = atom::to_atom(&events, &filter, Utc::now());
+    compression::reply(feed.into_bytes(), "application/atom+xml", accept_encoding)
+}
+
+fn kml_handler(
+    query: EventsQuery,
+    accept_encoding: Option<String>,
+    store: Arc<Store>,
+) -> impl warp::Reply {
+    let events = store.get_events(query.since.unwrap_or(0));
+    let kml = kml::to_kml(&events);
+    compression::reply(kml.into_bytes(), KML_CONTENT_TYPE, accept_encoding)
+}
+
+fn kmz_handler(query: EventsQuery, store: Arc<Store>) -> warp::reply::Response {
+    let events = store.get_events(query.since.unwrap_or(0));
+
+    match kml::to_kmz(&kml::to_kml(&events)) {
+        Ok(kmz) => warp::reply::with_header(kmz, CONTENT_TYPE, "application/vnd.google-earth.kmz")
+            .into_response(),
+        Err(e) => {
+            error!("Unable to create KMZ: {}", e);
+            warp::reply::with_status(warp::reply(), StatusCode::INTERNAL_SERVER_ERROR)
+                .into_response()
+        }
+    }
+}
+
+fn network_link_handler(base_url: String) -> impl warp::Reply {
+    let href = format!("{}/events.kmz", base_url.trim_end_matches('/'));
+    let kml = kml::to_network_link(&href, NETWORK_LINK_REFRESH_SECONDS);
+    warp::reply::with_header(kml, CONTENT_TYPE, KML_CONTENT_TYPE)
+}
+
+/**
+ * Defaults to the start of the current convective day, which is what SPC's counts cover.
+ */
+fn report_counts_handler(
+    query: EventsQuery,
+    accept_encoding: Option<String>,
+    store: Arc<Store>,
+) -> impl warp::Reply {
+    let since = query.since.unwrap_or_else(|| {
+        let date = spc_reports_loader::get_convective_date(Utc::now());
+        date.and_hms_opt(12, 0, 0)
+            .map(|x| x.timestamp() as u128 * 1_000_000)
+            .unwrap_or_default()
+    });
+    let counts = reports::count_reports(&store.get_events(since));
+    compression::json(&counts, accept_encoding)
 }
 
-fn stats_handler(store: Arc<Store>) -> impl warp::Reply {
+fn stats_handler(accept_encoding: Option<String>, store: Arc<Store>) -> impl warp::Reply {
     let stats = store.get_stats();
-    warp::reply::json(&stats)
+    compression::json(&stats, accept_encoding)
 }
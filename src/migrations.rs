@@ -0,0 +1,395 @@
+use bincode::{deserialize, serialize};
+use domain::Event;
+
+/**
+ * Events are stored with bincode, which encodes fields in order without names, so any change to
+ * the layout of `Event` or the types it contains makes existing events undecodable. Bump this
+ * whenever the layout changes, and add a migration from the previous layout to `migrate`.
+ * Only appending enum variants is safe without one.
+ */
+pub const STORE_VERSION: u32 = 2;
+
+/**
+ * Re-encodes an event written by `version` of the store in the current layout.
+ */
+pub fn migrate(value: &[u8], version: u32) -> bincode::Result<Vec<u8>> {
+    let event: Event = match version {
+        0 => deserialize::<v0::Event>(value)?.into(),
+        1 => deserialize::<v1::Event>(value)?.into(),
+        _ => deserialize(value)?,
+    };
+    serialize(&event)
+}
+
+/**
+ * The layout from before events carried their source id. Only types whose layout has since
+ * changed are copied here; the rest, and every enum, are unchanged.
+ */
+mod v0 {
+    use domain::{
+        EventType, HazardType, Location, MdConcerning, Outlook, Units, WatchStatus, WatchType,
+    };
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Event {
+        pub event_ts: u64,
+        pub event_type: EventType,
+        pub expires_ts: Option<u64>,
+        pub ext_uri: Option<String>,
+        pub ingest_ts: u128,
+        pub location: Option<Location>,
+        pub md: Option<MesoscaleDiscussion>,
+        pub outlook: Option<Outlook>,
+        pub report: Option<Report>,
+        pub text: Option<String>,
+        pub title: String,
+        pub valid_ts: Option<u64>,
+        pub warning: Option<Warning>,
+        pub watch: Option<Watch>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Report {
+        pub reporter: String,
+        pub hazard: HazardType,
+        pub magnitude: Option<f32>,
+        pub units: Option<Units>,
+        pub was_measured: Option<bool>,
+        pub report_ts: Option<u64>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Watch {
+        pub is_pds: bool,
+        pub id: u16,
+        pub watch_type: WatchType,
+        pub status: WatchStatus,
+        pub issued_for: Option<String>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Warning {
+        pub is_pds: bool,
+        pub is_tor_emergency: Option<bool>,
+        pub was_observed: Option<bool>,
+        pub issued_for: String,
+        pub motion_deg: Option<u16>,
+        pub motion_kt: Option<u16>,
+        pub source: Option<String>,
+        pub time: String, // ie. 0100Z
+    }
+
+    #[derive(Deserialize, Serialize)]
+    pub struct MesoscaleDiscussion {
+        pub id: u16,
+        pub affected: String,
+        pub concerning: MdConcerning,
+        pub watch_issuance_probability: Option<u16>,
+        pub wfos: Vec<String>,
+    }
+}
+
+/**
+ * The layout from before warnings carried the alert's severity.
+ */
+mod v1 {
+    use domain::{
+        AreaForecastDiscussion, Coordinates, EventType, FireHazard, FireOutlook, Location,
+        MesoscaleDiscussion, Outlook, Report, TornadoSurvey, TropicalCyclone, Watch, WinterHazard,
+    };
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Event {
+        pub afd: Option<AreaForecastDiscussion>,
+        pub event_ts: u64,
+        pub event_type: EventType,
+        pub expires_ts: Option<u64>,
+        pub ext_uri: Option<String>,
+        pub fire: Option<FireHazard>,
+        pub fire_outlook: Option<FireOutlook>,
+        pub ingest_ts: u128,
+        pub location: Option<Location>,
+        pub md: Option<MesoscaleDiscussion>,
+        pub outlook: Option<Outlook>,
+        pub report: Option<Report>,
+        pub source_id: Option<String>,
+        pub survey: Option<TornadoSurvey>,
+        pub text: Option<String>,
+        pub title: String,
+        pub tropical: Option<TropicalCyclone>,
+        pub valid_ts: Option<u64>,
+        pub warning: Option<Warning>,
+        pub watch: Option<Watch>,
+        pub winter: Option<WinterHazard>,
+    }
+
+    #[derive(Deserialize, Serialize)]
+    pub struct Warning {
+        pub is_pds: bool,
+        pub is_tor_emergency: Option<bool>,
+        pub was_observed: Option<bool>,
+        pub issued_for: String,
+        pub motion_deg: Option<u16>,
+        pub motion_kt: Option<u16>,
+        pub source: Option<String>,
+        pub time: Option<u64>,
+        pub vtec: Option<String>,
+        pub hail_size: Option<f32>,
+        pub wind_gust: Option<String>,
+        pub waterspout: Option<String>,
+        pub mpd_source_id: Option<String>,
+        pub storm_points: Vec<Coordinates>,
+    }
+}
+
+impl From<v0::Event> for Event {
+    fn from(event: v0::Event) -> Event {
+        let event_ts = event.event_ts;
+        let mut migrated = Event::new(event.event_ts, event.event_type, event.title);
+        migrated.expires_ts = event.expires_ts;
+        migrated.ext_uri = event.ext_uri;
+        migrated.ingest_ts = event.ingest_ts;
+        migrated.location = event.location;
+        migrated.outlook = event.outlook;
+        migrated.text = event.text;
+        migrated.valid_ts = event.valid_ts;
+        migrated.md = event.md.map(|md| domain::MesoscaleDiscussion {
+            id: md.id,
+            affected: md.affected,
+            concerning: md.concerning,
+            watch_issuance_probability: md.watch_issuance_probability,
+            wfos: md.wfos,
+            summary: None,
+            watch_ids: vec![],
+        });
+        migrated.report = event.report.map(|report| domain::Report {
+            reporter: report.reporter,
+            hazard: report.hazard,
+            magnitude: report.magnitude,
+            units: report.units,
+            was_measured: report.was_measured,
+            report_ts: report.report_ts,
+            lsr_source_id: None,
+        });
+        migrated.warning = event.warning.map(|warning| domain::Warning {
+            is_pds: warning.is_pds,
+            is_tor_emergency: warning.is_tor_emergency,
+            was_observed: warning.was_observed,
+            issued_for: warning.issued_for,
+            motion_deg: warning.motion_deg,
+            motion_kt: warning.motion_kt,
+            source: warning.source,
+            time: parsers::hour_min_to_ticks(&warning.time, event_ts).ok(),
+            vtec: None,
+            hail_size: None,
+            wind_gust: None,
+            waterspout: None,
+            mpd_source_id: None,
+            storm_points: vec![],
+            severity: None,
+        });
+        migrated.watch = event.watch.map(|watch| domain::Watch {
+            is_pds: watch.is_pds,
+            id: watch.id,
+            watch_type: watch.watch_type,
+            status: watch.status,
+            issued_for: watch.issued_for,
+            max_hail_size: None,
+            max_wind_gust_kt: None,
+            motion_deg: None,
+            motion_kt: None,
+            replaces_ids: vec![],
+            probabilities: None,
+        });
+        migrated
+    }
+}
+
+impl From<v1::Event> for Event {
+    fn from(event: v1::Event) -> Event {
+        Event {
+            afd: event.afd,
+            event_ts: event.event_ts,
+            event_type: event.event_type,
+            expires_ts: event.expires_ts,
+            ext_uri: event.ext_uri,
+            fire: event.fire,
+            fire_outlook: event.fire_outlook,
+            ingest_ts: event.ingest_ts,
+            location: event.location,
+            md: event.md,
+            outlook: event.outlook,
+            report: event.report,
+            source_id: event.source_id,
+            survey: event.survey,
+            text: event.text,
+            title: event.title,
+            tropical: event.tropical,
+            valid_ts: event.valid_ts,
+            warning: event.warning.map(|warning| domain::Warning {
+                is_pds: warning.is_pds,
+                is_tor_emergency: warning.is_tor_emergency,
+                was_observed: warning.was_observed,
+                issued_for: warning.issued_for,
+                motion_deg: warning.motion_deg,
+                motion_kt: warning.motion_kt,
+                source: warning.source,
+                time: warning.time,
+                vtec: warning.vtec,
+                hail_size: warning.hail_size,
+                wind_gust: warning.wind_gust,
+                waterspout: warning.waterspout,
+                mpd_source_id: warning.mpd_source_id,
+                storm_points: warning.storm_points,
+                severity: None,
+            }),
+            watch: event.watch,
+            winter: event.winter,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use domain::{Coordinates, EventType, WatchStatus, WatchType};
+
+    fn get_v0_event() -> v0::Event {
+        v0::Event {
+            event_ts: 1525222860000000,
+            event_type: EventType::NwsTor,
+            expires_ts: Some(1525225500000000),
+            ext_uri: None,
+            ingest_ts: 1525222861000000,
+            location: Some(domain::Location {
+                wfo: Some("KTOP".to_string()),
+                point: Some(Coordinates {
+                    lat: 39.52,
+                    lon: -97.28,
+                }),
+                poly: None,
+                county: None,
+            }),
+            md: None,
+            outlook: None,
+            report: None,
+            text: Some("Tornado Warning".to_string()),
+            title: "Tornado Warning (KTOP)".to_string(),
+            valid_ts: Some(1525222860000000),
+            warning: Some(v0::Warning {
+                is_pds: true,
+                is_tor_emergency: Some(false),
+                was_observed: Some(true),
+                issued_for: "Northwestern Riley County".to_string(),
+                motion_deg: Some(245),
+                motion_kt: Some(24),
+                source: Some("Radar indicated rotation".to_string()),
+                time: "0100Z".to_string(),
+            }),
+            watch: None,
+        }
+    }
+
+    #[test]
+    fn migrate_should_decode_v0_events() {
+        let value = serialize(&get_v0_event()).unwrap();
+        assert!(deserialize::<Event>(&value).is_err());
+
+        let event: Event = deserialize(&migrate(&value, 0).unwrap()).unwrap();
+        assert_eq!(event.event_type, EventType::NwsTor);
+        assert_eq!(event.title, "Tornado Warning (KTOP)");
+        assert_eq!(event.ingest_ts, 1525222861000000);
+        assert_eq!(event.source_id, None);
+        assert_eq!(event.location.unwrap().wfo, Some("KTOP".to_string()));
+        let warning = event.warning.unwrap();
+        assert!(warning.is_pds);
+        assert_eq!(warning.motion_deg, Some(245));
+        assert_eq!(warning.time, Some(1525222800000000));
+    }
+
+    #[test]
+    fn migrate_should_decode_v0_watches_and_reports() {
+        let mut v0_event = get_v0_event();
+        v0_event.event_type = EventType::NwsSel;
+        v0_event.warning = None;
+        v0_event.watch = Some(v0::Watch {
+            is_pds: false,
+            id: 26,
+            watch_type: WatchType::Tornado,
+            status: WatchStatus::Issued,
+            issued_for: None,
+        });
+        v0_event.report = Some(v0::Report {
+            reporter: "Trained Spotter".to_string(),
+            hazard: domain::HazardType::Hail,
+            magnitude: Some(1.75),
+            units: Some(domain::Units::Inches),
+            was_measured: None,
+            report_ts: None,
+        });
+        let value = serialize(&v0_event).unwrap();
+
+        let event: Event = deserialize(&migrate(&value, 0).unwrap()).unwrap();
+        let watch = event.watch.unwrap();
+        assert_eq!(watch.id, 26);
+        assert_eq!(watch.probabilities, None);
+        assert_eq!(event.report.unwrap().magnitude, Some(1.75));
+    }
+
+    #[test]
+    fn migrate_should_decode_v1_warnings() {
+        let v1_event = v1::Event {
+            afd: None,
+            event_ts: 1525222860000000,
+            event_type: EventType::NwsTor,
+            expires_ts: None,
+            ext_uri: None,
+            fire: None,
+            fire_outlook: None,
+            ingest_ts: 1525222861000000,
+            location: None,
+            md: None,
+            outlook: None,
+            report: None,
+            source_id: Some("nws:tor".to_string()),
+            survey: None,
+            text: None,
+            title: "Tornado Warning (KTOP)".to_string(),
+            tropical: None,
+            valid_ts: None,
+            warning: Some(v1::Warning {
+                is_pds: false,
+                is_tor_emergency: Some(false),
+                was_observed: Some(true),
+                issued_for: "Northwestern Riley County".to_string(),
+                motion_deg: Some(245),
+                motion_kt: Some(24),
+                source: None,
+                time: Some(1525222800000000),
+                vtec: Some("KTOP.TO.W.0009.2018".to_string()),
+                hail_size: None,
+                wind_gust: None,
+                waterspout: None,
+                mpd_source_id: None,
+                storm_points: vec![],
+            }),
+            watch: None,
+            winter: None,
+        };
+        let value = serialize(&v1_event).unwrap();
+
+        let event: Event = deserialize(&migrate(&value, 1).unwrap()).unwrap();
+        assert_eq!(event.source_id, Some("nws:tor".to_string()));
+        let warning = event.warning.unwrap();
+        assert_eq!(warning.vtec, Some("KTOP.TO.W.0009.2018".to_string()));
+        assert_eq!(warning.time, Some(1525222800000000));
+        assert_eq!(warning.severity, None);
+    }
+
+    #[test]
+    fn migrate_should_keep_current_events() {
+        let event = Event::new(1525222860000000, EventType::NwsTor, "Tornado".to_string());
+        let value = serialize(&event).unwrap();
+        assert_eq!(migrate(&value, STORE_VERSION).unwrap(), value);
+    }
+}
@@ -0,0 +1,125 @@
+use crate::http_client::HttpClient;
+use crate::loader::Loader;
+use chrono::prelude::*;
+use domain::{Event, MpingReportsResult};
+use fnv::FnvHashSet;
+use parsers::mping_parser;
+use std::time::{Duration, SystemTime};
+
+const API_URL: &str = "https://mping.ou.edu/mping/api/v2/reports";
+const LOOKBACK_SECONDS: u64 = 30 * 60;
+const MAX_PAGES: usize = 5;
+
+/**
+ * Loads crowd-sourced reports from the mPING API, which requires a token. Reports from the last
+ * half hour are requested on each poll, and deduped by id the same way SN reports are.
+ */
+pub struct MpingLoader {
+    client: HttpClient,
+    authorization: String,
+    seen: FnvHashSet<u64>,
+}
+
+impl MpingLoader {
+    pub fn new(token: &str, client: HttpClient) -> MpingLoader {
+        MpingLoader {
+            client,
+            authorization: format!("Token {}", token),
+            seen: FnvHashSet::default(),
+        }
+    }
+}
+
+impl Loader for MpingLoader {
+    fn name(&self) -> String {
+        "mping_loader".to_string()
+    }
+
+    fn init(&mut self, checkpoint: Option<String>) {
+        if let Some(seen) = checkpoint.and_then(|x| serde_json::from_str(&x).ok()) {
+            self.seen = seen;
+        }
+    }
+
+    fn poll(&mut self) -> Vec<Event> {
+        let since = SystemTime::now() - Duration::from_secs(LOOKBACK_SECONDS);
+        let since = DateTime::<Utc>::from(since).format("%Y-%m-%dT%H:%M:%SZ");
+        let url = format!("{}?obtime_gte={}", API_URL, since);
+        poll(&self.client, &url, &self.authorization, &mut self.seen).unwrap_or_default()
+    }
+
+    fn checkpoint(&self) -> Option<String> {
+        serde_json::to_string(&self.seen).ok()
+    }
+}
+
+/**
+ * Fetches every page of reports and parses those not seen on a previous poll. Only reports in the
+ * lookback window are returned, so `seen` is replaced with the latest set of ids to keep it from
+ * growing forever.
+ */
+fn poll(
+    client: &HttpClient,
+    url: &str,
+    authorization: &str,
+    seen: &mut FnvHashSet<u64>,
+) -> Option<Vec<Event>> {
+    let mut latest_set: FnvHashSet<u64> = FnvHashSet::default();
+    let mut events: Vec<Event> = vec![];
+    let mut next = Some(url.to_string());
+
+    for _ in 0..MAX_PAGES {
+        let url = match next {
+            Some(url) => url,
+            None => break,
+        };
+
+        let result = client
+            .fetch_json_authorized::<MpingReportsResult>(&url, authorization)
+            .ok()?;
+
+        for report in &result.results {
+            if latest_set.insert(report.id) && !seen.contains(&report.id) {
+                if let Some(event) = mping_parser::parse(report) {
+                    events.push(event);
+                }
+            }
+        }
+
+        next = result.next;
+    }
+
+    *seen = latest_set;
+    Some(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::serve_file;
+
+    #[test]
+    fn poll_should_parse_new_reports() {
+        let url = serve_file("data/mping-reports.json", "application/json", 1);
+        let client = HttpClient::new();
+        let mut seen = FnvHashSet::default();
+        let events = poll(&client, &url, "Token test", &mut seen).unwrap();
+
+        // The rain report is skipped
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0].title, "Report: 1\" Hail");
+        assert_eq!(seen.len(), 6);
+    }
+
+    #[test]
+    fn poll_should_skip_seen_reports() {
+        let url = serve_file("data/mping-reports.json", "application/json", 2);
+        let client = HttpClient::new();
+        let mut seen: FnvHashSet<u64> = vec![4452141, 4452142].into_iter().collect();
+        let events = poll(&client, &url, "Token test", &mut seen).unwrap();
+        assert_eq!(events.len(), 3);
+
+        let events = poll(&client, &url, "Token test", &mut seen).unwrap();
+        assert!(events.is_empty());
+    }
+}
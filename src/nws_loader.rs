@@ -1,65 +1,189 @@
-use crate::http_client::HttpClient;
-use crate::store::Store;
-use domain::{ListProduct, Product, ProductsResult};
+use crate::http_client::{HttpClient, HttpError};
+use crate::loader::Loader;
+use domain::{Event, ListProduct, Product, ProductsResult};
 use parsers::nws_parser;
-use std::sync::Arc;
-use std::thread;
-use std::time::Duration;
-use util;
 
 const API_HOST: &str = "https://api.weather.gov";
-const POLL_INTERVAL_SECONDS: u64 = 60;
+const MAX_CATCH_UP_PAGES: usize = 20;
 
 lazy_static! {
-    static ref HTTP_CLIENT: HttpClient = HttpClient::new();
-    static ref PRODUCT_CODES: Vec<&'static str> =
-        vec!["afd", "ffw", "lsr", "sel", "svr", "svs", "swo", "tor"];
+    pub static ref PRODUCT_CODES: Vec<&'static str> = vec![
+        "afd", "ffw", "lsr", "sel", "svr", "svs", "swo", "tor", "wsw", "smw", "sps", "pns", "wwp",
+        "ffg", "tcp", "hls", "rfw", "fwd"
+    ];
 }
 
-pub fn run(writer: &Arc<Store>) {
-    info!("starting");
-
-    PRODUCT_CODES.iter().for_each(|code| {
-        let product_writer = writer.clone();
-        thread::Builder::new()
-            .name(format!("{}_fetcher", code))
-            .spawn(move || {
-                let url = format!("{}/products/types/{}", API_HOST, code);
-                let mut last_product_ts = util::get_system_micros();
-
-                loop {
-                    let start = util::get_system_secs();
-
-                    // Get the list of all events for this product
-                    if let Ok(product_list) = HTTP_CLIENT.fetch_json::<ProductsResult>(&url) {
-                        let new_products = get_new_products(last_product_ts, product_list);
-
-                        if !new_products.is_empty() {
-                            // info!("found {} new {} event[s]", new_products.len(), code);
-                            last_product_ts =
-                                util::ts_to_ticks(&new_products[0].issuance_time).unwrap();
-                        }
-
-                        // Fetch all new events, run each through the parser, and store in the db
-                        new_products
-                            .iter()
-                            .map(|x| match HTTP_CLIENT.fetch_json::<Product>(&x._id) {
-                                Ok(value) => Some(value),
-                                Err(_) => None,
-                            })
-                            .filter(Option::is_some)
-                            .map(|x| nws_parser::parse(&x.unwrap()))
-                            .filter(Option::is_some)
-                            .for_each(|event| product_writer.put_event(&mut event.unwrap()));
-                    }
-
-                    let elapsed_seconds = util::get_system_secs() - start;
-                    let delay = POLL_INTERVAL_SECONDS.saturating_sub(elapsed_seconds);
-                    thread::sleep(Duration::from_secs(delay));
-                }
-            })
-            .expect("Unable to create thread");
-    });
+/**
+ * Loads a single product type from the NWS API. Each product code gets its own loader, so one
+ * slow product list doesn't hold up the others. The checkpoint is the issuance time of the latest
+ * product fetched, so products issued while the server was down are caught up on at startup.
+ */
+pub struct NwsLoader {
+    client: HttpClient,
+    code: &'static str,
+    url: String,
+    last_product_ts: u64,
+}
+
+impl NwsLoader {
+    pub fn new(code: &'static str, client: HttpClient) -> NwsLoader {
+        NwsLoader {
+            client,
+            code,
+            url: format!("{}/products/types/{}", API_HOST, code),
+            last_product_ts: util::get_system_micros(),
+        }
+    }
+}
+
+impl Loader for NwsLoader {
+    fn name(&self) -> String {
+        format!("{}_fetcher", self.code)
+    }
+
+    fn init(&mut self, checkpoint: Option<String>) {
+        if let Some(ts) = checkpoint.and_then(|x| x.parse().ok()) {
+            info!("catching up on {} products since {}", self.code, ts);
+            self.last_product_ts = ts;
+        }
+    }
+
+    fn poll(&mut self) -> Vec<Event> {
+        // Get the list of all events for this product, paging back to the last one seen
+        let client = &self.client;
+        let code = self.code;
+        let url = &self.url;
+        let new_products = catch_up(self.last_product_ts, |end| {
+            let url = match end {
+                Some(end) => get_page_url(code, end),
+                None => url.to_string(),
+            };
+            client.fetch_json::<ProductsResult>(&url).ok()
+        });
+
+        // Fetch all new events and run each through the parser
+        let (products, last_product_ts) =
+            fetch_products(self.last_product_ts, &new_products, |x| {
+                client.fetch_json::<Product>(&x._id)
+            });
+        self.last_product_ts = last_product_ts;
+
+        products.iter().flat_map(nws_parser::parse).collect()
+    }
+
+    fn checkpoint(&self) -> Option<String> {
+        Some(self.last_product_ts.to_string())
+    }
+}
+
+/**
+ * The product list is a single page of the most recent products. Older products are requested
+ * from the product search, using the oldest issuance time on the previous page as the end.
+ */
+fn get_page_url(code: &str, end: &str) -> String {
+    format!(
+        "{}/products?type={}&end={}",
+        API_HOST,
+        code.to_uppercase(),
+        end.replace("+", "%2B")
+    )
+}
+
+/**
+ * Returns products newer than `last_ts`, newest first, requesting older pages until a page
+ * reaches `last_ts`. Pages can overlap, since products can share an issuance time.
+ */
+fn catch_up<F>(last_ts: u64, mut fetch_page: F) -> Vec<ListProduct>
+where
+    F: FnMut(Option<&str>) -> Option<ProductsResult>,
+{
+    let mut new_products: Vec<ListProduct> = vec![];
+    let mut end: Option<String> = None;
+
+    for page in 0..MAX_CATCH_UP_PAGES {
+        let products_result = match fetch_page(end.as_deref()) {
+            Some(products_result) => products_result,
+            None => break,
+        };
+
+        let oldest = match products_result.products.last() {
+            Some(product) => product.issuance_time.to_string(),
+            None => break,
+        };
+        let is_caught_up = products_result.products.iter().any(|x| {
+            util::ts_to_ticks(&x.issuance_time)
+                .map(|ticks| ticks <= last_ts)
+                .unwrap_or(false)
+        });
+
+        for product in get_new_products(last_ts, products_result) {
+            if !new_products.iter().any(|x| x.id == product.id) {
+                new_products.push(product);
+            }
+        }
+
+        if is_caught_up || end.as_ref() == Some(&oldest) {
+            break;
+        }
+
+        if page == MAX_CATCH_UP_PAGES - 1 {
+            warn!("Stopped catching up after {} pages", MAX_CATCH_UP_PAGES);
+        }
+
+        end = Some(oldest);
+    }
+
+    new_products
+}
+
+/**
+ * Fetches products oldest first, stopping at the first retryable failure so the next poll retries
+ * from it. A product that can never be fetched, ie. a 404 or a body that doesn't deserialize, is
+ * skipped so it doesn't hold up every later product. Returns the fetched products and the issuance
+ * time to resume after. Products can share an issuance time, so the cursor only moves past a time
+ * once every product issued at it is fetched or skipped, and products from a partly fetched time
+ * are dropped to be fetched again.
+ */
+fn fetch_products<F>(
+    last_ts: u64,
+    new_products: &[ListProduct],
+    mut fetch: F,
+) -> (Vec<Product>, u64)
+where
+    F: FnMut(&ListProduct) -> Result<Product, HttpError>,
+{
+    let mut products: Vec<Product> = vec![];
+    let mut fetched = 0;
+    let mut cursor = last_ts;
+    let mut products_iter = new_products
+        .iter()
+        .rev()
+        .filter_map(|x| Some((util::ts_to_ticks(&x.issuance_time).ok()?, x)))
+        .peekable();
+
+    while let Some((ticks, list_product)) = products_iter.next() {
+        match fetch(list_product) {
+            Ok(product) => products.push(product),
+            Err(e) if !e.is_retryable() => {
+                warn!("Skipping product {}: {}", list_product.id, e);
+            }
+            Err(_) => {
+                warn!(
+                    "Unable to fetch product {}, retrying next poll",
+                    list_product.id
+                );
+                products.truncate(fetched);
+                break;
+            }
+        }
+
+        if products_iter.peek().map(|(next, _)| *next) != Some(ticks) {
+            fetched = products.len();
+            cursor = ticks;
+        }
+    }
+
+    (products, cursor)
 }
 
 /**
@@ -83,7 +207,155 @@ fn get_new_products(last_ts: u64, products_result: ProductsResult) -> Vec<ListPr
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
-    // use std::fs::File;
-    // use std::io::Read;
+    use super::*;
+    use reqwest::StatusCode;
+    use std::fs::File;
+    use std::io::Read;
+
+    fn get_product_list() -> ProductsResult {
+        let mut file = File::open("data/product-list-tor").expect("unable to open file");
+        let mut body = String::new();
+        file.read_to_string(&mut body).expect("unable to read file");
+        serde_json::from_str(&body).unwrap()
+    }
+
+    fn get_product() -> Product {
+        let body = std::fs::read_to_string("data/products/tor-normal").unwrap();
+        serde_json::from_str(&body).unwrap()
+    }
+
+    #[test]
+    fn catch_up_should_stop_at_last_seen_product() {
+        let last_ts = util::ts_to_ticks("2019-04-22T23:51:00+00:00").unwrap();
+        let mut ends = vec![];
+        let result = catch_up(last_ts, |end| {
+            ends.push(end.map(|x| x.to_string()));
+            Some(get_product_list())
+        });
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(ends, vec![None]);
+    }
+
+    #[test]
+    fn catch_up_should_page_until_last_seen_product() {
+        let last_ts = util::ts_to_ticks("2019-04-22T23:50:00+00:00").unwrap();
+        let mut ends = vec![];
+        let result = catch_up(last_ts, |end| {
+            ends.push(end.map(|x| x.to_string()));
+            let mut page = get_product_list();
+            match end {
+                // First page ends at 23:52, second overlaps it and continues to 23:50
+                None => page.products.truncate(2),
+                Some(_) => {
+                    page.products.remove(0);
+                }
+            }
+            Some(page)
+        });
+
+        let issuance_times: Vec<&str> = result.iter().map(|x| &*x.issuance_time).collect();
+        assert_eq!(
+            issuance_times,
+            vec![
+                "2019-04-22T23:53:00+00:00",
+                "2019-04-22T23:52:00+00:00",
+                "2019-04-22T23:51:00+00:00"
+            ]
+        );
+        assert_eq!(
+            ends,
+            vec![None, Some("2019-04-22T23:52:00+00:00".to_string())]
+        );
+    }
+
+    #[test]
+    fn catch_up_should_stop_when_fetch_fails() {
+        let result = catch_up(0, |_| None);
+        assert!(result.is_empty());
+    }
+
+    #[test]
+    fn fetch_products_should_stop_at_first_failure() {
+        let last_ts = util::ts_to_ticks("2019-04-22T23:50:00+00:00").unwrap();
+        let new_products = get_new_products(last_ts, get_product_list());
+        let mut fetched = vec![];
+        let (products, cursor) = fetch_products(last_ts, &new_products, |x| {
+            fetched.push(x.issuance_time.to_string());
+            match x.issuance_time.as_ref() {
+                "2019-04-22T23:52:00+00:00" => Err(HttpError::Timeout),
+                _ => Ok(get_product()),
+            }
+        });
+
+        assert_eq!(products.len(), 1);
+        assert_eq!(
+            cursor,
+            util::ts_to_ticks("2019-04-22T23:51:00+00:00").unwrap()
+        );
+        assert_eq!(
+            fetched,
+            vec!["2019-04-22T23:51:00+00:00", "2019-04-22T23:52:00+00:00"]
+        );
+
+        let (products, cursor) =
+            fetch_products(last_ts, &new_products, |_| Err(HttpError::Timeout));
+        assert!(products.is_empty());
+        assert_eq!(cursor, last_ts);
+    }
+
+    #[test]
+    fn fetch_products_should_not_split_an_issuance_time() {
+        let last_ts = util::ts_to_ticks("2019-04-22T23:50:00+00:00").unwrap();
+        let mut new_products = get_new_products(last_ts, get_product_list());
+        new_products[0].issuance_time = "2019-04-22T23:52:00+00:00".to_string();
+        let failed_id = new_products[0].id.to_string();
+        let (products, cursor) = fetch_products(last_ts, &new_products, |x| {
+            if x.id == failed_id {
+                Err(HttpError::Timeout)
+            } else {
+                Ok(get_product())
+            }
+        });
+
+        // The other 23:52 product is dropped, and fetched again with this one on the next poll
+        assert_eq!(products.len(), 1);
+        assert_eq!(
+            cursor,
+            util::ts_to_ticks("2019-04-22T23:51:00+00:00").unwrap()
+        );
+
+        let (products, cursor) = fetch_products(last_ts, &new_products, |_| Ok(get_product()));
+        assert_eq!(products.len(), 3);
+        assert_eq!(
+            cursor,
+            util::ts_to_ticks("2019-04-22T23:52:00+00:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn fetch_products_should_skip_unfetchable_products() {
+        let last_ts = util::ts_to_ticks("2019-04-22T23:50:00+00:00").unwrap();
+        let new_products = get_new_products(last_ts, get_product_list());
+        let (products, cursor) =
+            fetch_products(last_ts, &new_products, |x| match x.issuance_time.as_ref() {
+                "2019-04-22T23:52:00+00:00" => Err(HttpError::ClientError(StatusCode::NOT_FOUND)),
+                _ => Ok(get_product()),
+            });
+
+        assert_eq!(products.len(), 2);
+        assert_eq!(
+            cursor,
+            util::ts_to_ticks("2019-04-22T23:53:00+00:00").unwrap()
+        );
+    }
+
+    #[test]
+    fn get_page_url_should_encode_end() {
+        let result = get_page_url("tor", "2019-04-22T23:52:00+00:00");
+        assert_eq!(
+            result,
+            "https://api.weather.gov/products?type=TOR&end=2019-04-22T23:52:00%2B00:00"
+        );
+    }
 }
@@ -0,0 +1,99 @@
+use crate::http_client::HttpClient;
+use crate::loader::Loader;
+use domain::{Event, SnReportsResult};
+use fnv::FnvHashSet;
+use parsers::sn_api_parser;
+
+// NOTE: Unverified - this endpoint hasn't been checked against the live service yet
+const API_URL: &str = "https://www.spotternetwork.org/api/reports";
+
+/**
+ * Loads reports from the SpotterNetwork JSON API. Reports have stable ids, so there's no need for
+ * the line normalization the placefile loader does.
+ */
+pub struct SnApiLoader {
+    client: HttpClient,
+    url: String,
+    seen: FnvHashSet<u64>,
+}
+
+impl SnApiLoader {
+    pub fn new(client: HttpClient) -> SnApiLoader {
+        SnApiLoader {
+            client,
+            url: API_URL.to_string(),
+            seen: FnvHashSet::default(),
+        }
+    }
+}
+
+impl Loader for SnApiLoader {
+    fn name(&self) -> String {
+        "sn_api_loader".to_string()
+    }
+
+    fn init(&mut self, checkpoint: Option<String>) {
+        if let Some(seen) = checkpoint.and_then(|x| serde_json::from_str(&x).ok()) {
+            self.seen = seen;
+        }
+    }
+
+    fn poll(&mut self) -> Vec<Event> {
+        poll(&self.client, &self.url, &mut self.seen).unwrap_or_default()
+    }
+
+    fn checkpoint(&self) -> Option<String> {
+        serde_json::to_string(&self.seen).ok()
+    }
+}
+
+/**
+ * Fetches recent reports and parses those not seen on a previous poll. The API only returns
+ * recent reports, so `seen` is replaced with the latest set of ids to keep it from growing
+ * forever.
+ */
+fn poll(client: &HttpClient, url: &str, seen: &mut FnvHashSet<u64>) -> Option<Vec<Event>> {
+    let result = client.fetch_json::<SnReportsResult>(url).ok()?;
+    let latest_set: FnvHashSet<u64> = result.reports.iter().map(|x| x.id).collect();
+
+    let events = result
+        .reports
+        .iter()
+        .filter(|x| !seen.contains(&x.id))
+        .filter_map(sn_api_parser::parse)
+        .collect();
+
+    *seen = latest_set;
+    Some(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::serve_file;
+
+    #[test]
+    fn poll_should_parse_new_reports() {
+        let url = serve_file("data/sn-reports.json", "application/json", 1);
+        let client = HttpClient::new();
+        let mut seen = FnvHashSet::default();
+        let events = poll(&client, &url, &mut seen).unwrap();
+
+        // The Other report without notes is skipped
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0].title, "Report: 0.75\" Hail");
+        assert_eq!(seen.len(), 4);
+    }
+
+    #[test]
+    fn poll_should_skip_seen_reports() {
+        let url = serve_file("data/sn-reports.json", "application/json", 2);
+        let client = HttpClient::new();
+        let mut seen: FnvHashSet<u64> = vec![1181042].into_iter().collect();
+        let events = poll(&client, &url, &mut seen).unwrap();
+        assert_eq!(events.len(), 2);
+
+        let events = poll(&client, &url, &mut seen).unwrap();
+        assert!(events.is_empty());
+    }
+}
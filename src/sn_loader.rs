@@ -1,73 +1,99 @@
 use crate::http_client::HttpClient;
-use crate::store::Store;
-use fnv::FnvHashSet;
+use crate::loader::Loader;
+use domain::Event;
+use fnv::{FnvHashSet, FnvHasher};
 use parsers::sn_parser;
-use std::sync::Arc;
-use std::thread;
-use std::time::Duration;
-use util;
+use std::hash::{Hash, Hasher};
 
 const API_URL: &str = "http://www.spotternetwork.org/feeds/reports.txt";
-const POLL_INTERVAL_SECONDS: u64 = 60;
-
-lazy_static! {
-    static ref HTTP_CLIENT: HttpClient = HttpClient::new();
-}
 
 #[derive(Debug)]
 pub struct Comparison {
-    latest_set: FnvHashSet<String>,
+    latest_set: FnvHashSet<u64>,
     new: Vec<String>,
 }
 
-pub fn run(writer: &Arc<Store>) {
-    let mut seen: FnvHashSet<String> = FnvHashSet::default();
-    info!("starting");
-
-    loop {
-        let start = util::get_system_secs();
-
-        if let Ok(body) = HTTP_CLIENT.fetch_text(API_URL) {
-            let comparison = get_comparison(&body, seen);
-            seen = comparison.latest_set;
-            comparison
-                .new
-                .iter()
-                .map(|report| sn_parser::parse(report))
-                .for_each(|event| {
-                    if let Some(mut event) = event {
-                        writer.put_event(&mut event)
-                    }
-                });
+/**
+ * Loads reports from the SpotterNetwork placefile. Seen reports are tracked by the hash of their
+ * normalized line, and the hashes are checkpointed so a restart doesn't re-ingest the feed.
+ */
+pub struct SnLoader {
+    client: HttpClient,
+    seen: FnvHashSet<u64>,
+}
+
+impl SnLoader {
+    pub fn new(client: HttpClient) -> SnLoader {
+        SnLoader {
+            client,
+            seen: FnvHashSet::default(),
+        }
+    }
+}
+
+impl Loader for SnLoader {
+    fn name(&self) -> String {
+        "sn_loader".to_string()
+    }
+
+    fn init(&mut self, checkpoint: Option<String>) {
+        if let Some(seen) = checkpoint.and_then(|x| serde_json::from_str(&x).ok()) {
+            self.seen = seen;
+        }
+    }
+
+    fn poll(&mut self) -> Vec<Event> {
+        let body = match self.client.fetch_text(API_URL) {
+            Ok(body) => body,
+            Err(_) => return vec![],
         };
 
-        let elapsed_seconds = util::get_system_secs() - start;
-        let delay = POLL_INTERVAL_SECONDS.saturating_sub(elapsed_seconds);
-        thread::sleep(Duration::from_secs(delay));
+        let seen = std::mem::take(&mut self.seen);
+        let comparison = get_comparison(&body, seen);
+        self.seen = comparison.latest_set;
+        comparison
+            .new
+            .iter()
+            .filter_map(|report| {
+                let mut event = sn_parser::parse(report)?;
+                event.source_id = Some(format!("sn-placefile:{:016x}", hash_line(report)));
+                Some(event)
+            })
+            .collect()
+    }
+
+    fn checkpoint(&self) -> Option<String> {
+        serde_json::to_string(&self.seen).ok()
     }
 }
 
-fn get_comparison(body: &str, seen: FnvHashSet<String>) -> Comparison {
-    let latest_set: FnvHashSet<String> = body
+fn get_comparison(body: &str, seen: FnvHashSet<u64>) -> Comparison {
+    let mut latest_set: FnvHashSet<u64> = FnvHashSet::default();
+    let mut new: Vec<String> = vec![];
+
+    for line in body
         .lines()
         .filter(|x| x.starts_with("Icon:"))
         .map(|x| normalize_line(x))
-        .collect();
-
-    let new: Vec<String> = latest_set
-        .iter()
-        .filter_map(|x| {
-            if !seen.contains(x) {
-                Some(x.to_string())
-            } else {
-                None
-            }
-        })
-        .collect();
+    {
+        let hash = hash_line(&line);
+        if latest_set.insert(hash) && !seen.contains(&hash) {
+            new.push(line);
+        }
+    }
 
     Comparison { latest_set, new }
 }
 
+/**
+ * FNV isn't randomly seeded, so hashes are stable across restarts and can be checkpointed.
+ */
+fn hash_line(line: &str) -> u64 {
+    let mut hasher = FnvHasher::default();
+    line.hash(&mut hasher);
+    hasher.finish()
+}
+
 /**
  * Normalizes raw report lines as returned by the SpotterNetwork API. Since there is no offset,
  * you will see the same report multiple times and need to de-dupe. Unfortunately, the same
@@ -133,14 +159,14 @@ mod tests {
         let mut body = String::new();
         file.read_to_string(&mut body).expect("unable to read file");
 
-        let seen: FnvHashSet<String> = vec![
+        let seen: FnvHashSet<u64> = [
             "Icon: 41.338901,-96.059708,000,0,5,\"Reported By: Will Dupe\\nHigh Wind\\nTime: 2018-09-21 00:26:06 UTC\\n50 mphNotes: None\"".to_string(),
             "Icon: 47.617706,-111.215248,000,0,4,\"Reported By: Will Dupe\\nHail\\nTime: 2018-09-20 22:49:29 UTC\\nSize: 0.75\" (Penny)\\nNotes: None\"".to_string(),
             "Icon: 43.112000,-94.610001,000,0,6,\"Reported By: Will Dupe\\nFlooding\\nTime: 2018-09-20 22:58:00 UTC\\nNotes: Water over road on US 18\"".to_string(),
             "Icon: 41.338715,-96.059563,000,0,5,\"Reported By: Will Dupe\\nHigh Wind\\nTime: 2018-09-21 00:34:00 UTC\\n60 mphNotes: Wind gusting to 63mph\"".to_string(),
             "Icon: 35.851399,-90.708198,000,0,8,\"Reported By: Will Dupe\\nOther - See Note\\nTime: 2018-11-14 20:22:00 UTC\\nNotes: i got snow and a little of sleet\"".to_string(),
             "Icon: 41.230400,-95.850403,000,0,3,\"Reported By: Will Dupe\\nNot Rotating Wall Cloud\\nTime: 2018-09-21 00:34:00 UTC\\nNotes: None\"".to_string(),
-        ].into_iter().collect();
+        ].iter().map(|x| hash_line(x)).collect();
 
         let seen_length = seen.len();
         let comparison = get_comparison(&body, seen);
@@ -151,4 +177,17 @@ mod tests {
             comparison.latest_set.len() - seen_length
         );
     }
+
+    #[test]
+    fn checkpoint_should_restore_seen_reports() {
+        let mut loader = SnLoader::new(HttpClient::new());
+        loader.seen = vec![1, 2].into_iter().collect();
+        let mut restored = SnLoader::new(HttpClient::new());
+        restored.init(loader.checkpoint());
+        assert_eq!(restored.seen, loader.seen);
+
+        let mut restored = SnLoader::new(HttpClient::new());
+        restored.init(Some("not json".to_string()));
+        assert!(restored.seen.is_empty());
+    }
 }
@@ -0,0 +1,196 @@
+use crate::http_client::HttpClient;
+use crate::loader::{Loader, Sink};
+use chrono::{DateTime, Duration as ChronoDuration, NaiveDate, Utc};
+use domain::Event;
+use fnv::FnvHashSet;
+use parsers::spc_reports_parser;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+const API_URL: &str = "https://www.spc.noaa.gov/climo/reports/today_filtered.csv";
+const POLL_INTERVAL_SECONDS: u64 = 600; // SPC updates the reports every few minutes
+const CONVECTIVE_DAY_OFFSET_HOURS: i64 = 12; // convective days run 12Z to 12Z
+
+/**
+ * Loads SPC's filtered storm reports for the current convective day. These are compiled from LSRs
+ * and are what SPC's daily counts are based on; the store links each one to its LSR.
+ */
+pub struct SpcReportsLoader {
+    client: HttpClient,
+    url: String,
+    seen: FnvHashSet<String>,
+}
+
+impl SpcReportsLoader {
+    pub fn new(client: HttpClient) -> SpcReportsLoader {
+        SpcReportsLoader {
+            client,
+            url: API_URL.to_string(),
+            seen: FnvHashSet::default(),
+        }
+    }
+}
+
+impl Loader for SpcReportsLoader {
+    fn name(&self) -> String {
+        "spc_reports_loader".to_string()
+    }
+
+    fn poll_interval(&self) -> Duration {
+        Duration::from_secs(POLL_INTERVAL_SECONDS)
+    }
+
+    fn init(&mut self, checkpoint: Option<String>) {
+        if let Some(seen) = checkpoint.and_then(|x| serde_json::from_str(&x).ok()) {
+            self.seen = seen;
+        }
+    }
+
+    fn poll(&mut self) -> Vec<Event> {
+        let date = get_convective_date(Utc::now());
+        poll(&self.client, &self.url, date, &mut self.seen).unwrap_or_default()
+    }
+
+    fn checkpoint(&self) -> Option<String> {
+        serde_json::to_string(&self.seen).ok()
+    }
+}
+
+/**
+ * Fetches the day's reports and parses those not seen on a previous poll. The file starts over
+ * each convective day, so `seen` is replaced with the latest set of source ids to keep it from
+ * growing forever.
+ */
+fn poll(
+    client: &HttpClient,
+    url: &str,
+    date: NaiveDate,
+    seen: &mut FnvHashSet<String>,
+) -> Option<Vec<Event>> {
+    let csv = client.fetch_text(url).ok()?;
+    let events = spc_reports_parser::parse(&csv, date);
+    let latest_set: FnvHashSet<String> =
+        events.iter().filter_map(|x| x.source_id.clone()).collect();
+
+    let events = events
+        .into_iter()
+        .filter(|x| match &x.source_id {
+            Some(source_id) => !seen.contains(source_id),
+            None => true,
+        })
+        .collect();
+
+    *seen = latest_set;
+    Some(events)
+}
+
+/**
+ * Returns the convective day, which runs 12Z to 12Z, that `now` falls in.
+ */
+pub fn get_convective_date(now: DateTime<Utc>) -> NaiveDate {
+    (now - ChronoDuration::hours(CONVECTIVE_DAY_OFFSET_HOURS))
+        .naive_utc()
+        .date()
+}
+
+/**
+ * Imports archived report files, ie. 200412_rpts_filtered.csv from
+ * https://www.spc.noaa.gov/climo/reports/. The convective day comes from the file name. Reports
+ * that are already stored are skipped by the store, so files can be imported more than once.
+ */
+pub fn import(paths: &[String], sink: &dyn Sink) -> Result<usize, ()> {
+    let mut count = 0;
+
+    for path in paths {
+        let date = get_archive_date(path)?;
+        let csv = fs::read_to_string(path).map_err(|e| error!("Unable to read {}: {}", path, e))?;
+        let mut events = spc_reports_parser::parse(&csv, date);
+        events.iter_mut().for_each(|event| sink.put_event(event));
+        info!("Imported {} reports from {}", events.len(), path);
+        count += events.len();
+    }
+
+    Ok(count)
+}
+
+fn get_archive_date(path: &str) -> Result<NaiveDate, ()> {
+    Path::new(path)
+        .file_name()
+        .and_then(|x| x.to_str())
+        .and_then(|x| x.get(0..6))
+        .and_then(|x| NaiveDate::parse_from_str(x, "%y%m%d").ok())
+        .ok_or_else(|| error!("Unable to get a date from file name {}", path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::serve_file;
+    use std::sync::Mutex;
+
+    fn get_date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2018, 3, 26).unwrap()
+    }
+
+    #[derive(Default)]
+    struct TestSink {
+        events: Mutex<Vec<String>>,
+    }
+
+    impl Sink for TestSink {
+        fn put_event(&self, event: &mut Event) {
+            self.events.lock().unwrap().push(event.title.to_string());
+        }
+
+        fn get_checkpoint(&self, _name: &str) -> Option<String> {
+            None
+        }
+
+        fn put_checkpoint(&self, _name: &str, _checkpoint: &str) {}
+    }
+
+    #[test]
+    fn poll_should_skip_seen_reports() {
+        let url = serve_file("data/spc-reports.csv", "text/csv", 2);
+        let client = HttpClient::new();
+        let mut seen = FnvHashSet::default();
+        seen.insert("spc:180326:torn:2140:41.73:-91.69".to_string());
+
+        let events = poll(&client, &url, get_date(), &mut seen).unwrap();
+        assert_eq!(events.len(), 5);
+        assert_eq!(events[0].title, "Report: Tornado");
+        assert_eq!(seen.len(), 6);
+
+        let events = poll(&client, &url, get_date(), &mut seen).unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn get_convective_date_should_start_at_12z() {
+        let now = DateTime::parse_from_rfc3339("2018-03-27T11:59:00+00:00").unwrap();
+        assert_eq!(get_convective_date(now.with_timezone(&Utc)), get_date());
+        let now = DateTime::parse_from_rfc3339("2018-03-26T12:00:00+00:00").unwrap();
+        assert_eq!(get_convective_date(now.with_timezone(&Utc)), get_date());
+    }
+
+    #[test]
+    fn get_archive_date_should_use_file_name() {
+        let date = get_archive_date("archive/180326_rpts_filtered.csv");
+        assert_eq!(date, Ok(get_date()));
+        assert!(get_archive_date("data/spc-reports.csv").is_err());
+    }
+
+    #[test]
+    fn import_should_put_reports() {
+        let sink = TestSink::default();
+        assert!(import(&["data/spc-reports.csv".to_string()], &sink).is_err());
+        assert!(sink.events.lock().unwrap().is_empty());
+
+        let path = std::env::temp_dir().join("180326_rpts_filtered.csv");
+        fs::copy("data/spc-reports.csv", &path).unwrap();
+        let path = path.to_string_lossy().to_string();
+        assert_eq!(import(&[path], &sink), Ok(6));
+        assert_eq!(sink.events.lock().unwrap()[4], "Report: 1.25\" Hail");
+    }
+}
@@ -1,40 +1,430 @@
+use crate::migrations::{self, STORE_VERSION};
 use bincode::{deserialize, serialize};
+use domain::mpds::is_ffw_in_mpd;
 use domain::optimized::OptimizedEvent;
-use domain::Event;
-use rocksdb::{DBCompressionType, Direction, IteratorMode, Options, DB};
+use domain::reports::ReportKey;
+use domain::{AreaForecastDiscussion, Event, EventType, WatchStatus};
+use parsers::alerts_parser;
+use rocksdb::{DBCompressionType, Direction, IteratorMode, Options, WriteBatch, DB};
+use std::convert::TryInto;
 use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const STORE_PATH: &str = "sware_store";
 const EVENT_THRESHOLD_MICROS: u128 = 1000 * 1000 * 60 * 60; // 1 hr
+const RECONCILE_WINDOW_MICROS: u128 = 1000 * 1000 * 60 * 60 * 24; // 24 hrs
+const MPD_WINDOW_MICROS: u128 = 1000 * 1000 * 60 * 60 * 12; // 12 hrs, longer than an MPD is valid
+const WATCH_WINDOW_MICROS: u64 = 1000 * 1000 * 60 * 60; // 1 hr, SELs and WWPs are issued together
+const SOURCES_CF: &str = "sources"; // event source id -> event key
+const VTEC_CF: &str = "vtec"; // VTEC tracking number -> event key
+const REPORTS_CF: &str = "reports"; // LSR source id -> key of the SPC report linked to it
+const WATCHES_CF: &str = "watches"; // sel:<watch id> or wwp:<watch id> -> latest event key
+const CHECKPOINTS_CF: &str = "checkpoints"; // loader name -> loader cursor
+const META_CF: &str = "meta"; // store metadata, ie. the version events are encoded with
+const VERSION_KEY: &str = "version";
 
 pub struct Store {
     db: DB,
     opts: Options,
     mutex: Mutex<()>,
+    index_mutex: Mutex<()>,
 }
 
 impl Store {
     pub fn new() -> Store {
         let mut opts = Options::default();
         opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
         opts.enable_statistics();
         opts.set_compression_type(DBCompressionType::Lz4hc);
-        let db = DB::open(&opts, STORE_PATH).expect("Unable to open store");
+        let db = DB::open_cf(
+            &opts,
+            STORE_PATH,
+            [
+                SOURCES_CF,
+                VTEC_CF,
+                REPORTS_CF,
+                WATCHES_CF,
+                CHECKPOINTS_CF,
+                META_CF,
+            ],
+        )
+        .expect("Unable to open store");
         let mutex = Mutex::new(());
+        let index_mutex = Mutex::new(());
 
-        Store { db, opts, mutex }
+        let store = Store {
+            db,
+            opts,
+            mutex,
+            index_mutex,
+        };
+        store.migrate();
+        store
     }
 
+    /**
+     * Brings events written by an older version of the store up to the current layout, see
+     * `migrations`. Stores from before versioning have no version and are taken to be version 0.
+     * Events that can't be migrated are left as they are and logged. A store written by a newer
+     * version can't be read, so that's fatal rather than silently dropping its events.
+     */
+    fn migrate(&self) {
+        let cf = self
+            .db
+            .cf_handle(META_CF)
+            .expect("Unable to get meta column family");
+        let version = match self.db.get_cf(cf, VERSION_KEY.as_bytes()) {
+            Ok(Some(value)) => value
+                .as_slice()
+                .try_into()
+                .map(u32::from_be_bytes)
+                .expect("Unable to decode store version"),
+            Ok(None) => 0,
+            Err(e) => panic!("Unable to get store version: {}", e),
+        };
+
+        if version > STORE_VERSION {
+            panic!(
+                "Store was written by version {}, newer than {}. Upgrade or remove {}",
+                version, STORE_VERSION, STORE_PATH
+            );
+        }
+        if version == STORE_VERSION {
+            return;
+        }
+
+        let mut batch = WriteBatch::default();
+        let mut result = Ok(());
+        let mut migrated = 0;
+        for (key, value) in self.db.iterator(IteratorMode::Start) {
+            match migrations::migrate(&value, version) {
+                Ok(value) => {
+                    result = result.and_then(|_| batch.put(&key, &value));
+                    migrated += 1;
+                }
+                Err(e) => error!("Unable to migrate event with key {:?}: {}", key, e),
+            }
+        }
+        result = result.and_then(|_| batch.put_cf(cf, VERSION_KEY, STORE_VERSION.to_be_bytes()));
+
+        match result.and_then(|_| self.db.write(batch)) {
+            Ok(_) => info!(
+                "Migrated {} events from store version {} to {}",
+                migrated, version, STORE_VERSION
+            ),
+            Err(e) => panic!("Unable to migrate store: {}", e),
+        }
+    }
+
+    /**
+     * Writes are idempotent: an event whose source record (ie. NWS product or SN report) has
+     * already been stored is skipped, so retries, restarts and overlapping loaders don't create
+     * duplicate events. Warnings can also arrive from both the products and alerts APIs, so they
+     * are reconciled by their VTEC tracking number. The alert usually arrives first, so the copy
+     * parsed from the product replaces it under a new key, keeping anything only the alert had,
+     * and clients pick the merged event up on their next poll. Any other second copy is skipped.
+     * SPC reports are linked to the LSR they were compiled from, whichever is stored first, see
+     * `reconcile_report` and `reconcile_lsr`. A watch's WWP probabilities are copied onto its SEL,
+     * see `join_watch`, and FFWs are linked to the MPD they were issued under, see `link_mpd`.
+     */
     pub fn put_event(&self, event: &mut Event) {
+        let is_product = matches!(&event.source_id, Some(x) if x.starts_with("nws:"));
+        let mut index_keys: Vec<(&'static str, String)> = vec![];
+        if let Some(source_id) = &event.source_id {
+            index_keys.push((SOURCES_CF, source_id.to_string()));
+        }
+        if let Some(vtec) = event.warning.as_ref().and_then(|x| x.vtec.as_ref()) {
+            index_keys.push((VTEC_CF, vtec.to_string()));
+        }
+
+        if index_keys.is_empty() {
+            return self.write_event(event, &index_keys, WriteBatch::default());
+        }
+
+        let _guard = self.index_mutex.lock().expect("Unable to acquire lock");
+        let mut alert_copy = None;
+        for (cf_name, index_key) in &index_keys {
+            let cf = match self.db.cf_handle(cf_name) {
+                Some(cf) => cf,
+                None => {
+                    error!("Unable to get column family {}", cf_name);
+                    return;
+                }
+            };
+
+            match self.db.get_cf(cf, index_key.as_bytes()) {
+                Ok(None) => (),
+                Ok(Some(key)) if is_product && *cf_name == VTEC_CF => {
+                    match self.get_alert_copy(&key) {
+                        Some(alert) => alert_copy = Some(alert),
+                        None => {
+                            debug!("Skipping duplicate event {}", index_key);
+                            return;
+                        }
+                    }
+                }
+                Ok(Some(_)) => {
+                    debug!("Skipping duplicate event {}", index_key);
+                    return;
+                }
+                Err(e) => {
+                    error!("Unable to look up event {}: {}", index_key, e);
+                    return;
+                }
+            }
+        }
+
+        let mut batch = WriteBatch::default();
+        let mut result = Ok(());
+
+        if event.event_type == EventType::SpcReport {
+            if let Some(lsr_source_id) = self.reconcile_report(event) {
+                index_keys.push((REPORTS_CF, lsr_source_id));
+            }
+        }
+
+        if event.event_type == EventType::NwsLsr {
+            if let (Some((key, value)), Some(cf)) =
+                (self.reconcile_lsr(event), self.db.cf_handle(REPORTS_CF))
+            {
+                let lsr_source_id = event.source_id.as_deref().unwrap_or_default();
+                result = batch
+                    .put(key.to_be_bytes(), &value)
+                    .and_then(|_| batch.put_cf(cf, lsr_source_id.as_bytes(), key.to_be_bytes()));
+            }
+        }
+
+        if let Some(index_key) = get_watch_index_key(event) {
+            if let Some((key, value)) = self.join_watch(event) {
+                result = result.and_then(|_| batch.put(key.to_be_bytes(), &value));
+            }
+            index_keys.push((WATCHES_CF, index_key));
+        }
+
+        if event.event_type == EventType::NwsFfw {
+            self.link_mpd(event);
+        }
+
+        if let Some((key, alert)) = alert_copy {
+            // Keep the alert's source id pointing at the merged event, so it's still skipped
+            if let Some(source_id) = &alert.source_id {
+                index_keys.push((SOURCES_CF, source_id.to_string()));
+            }
+            alerts_parser::merge(event, alert);
+            result = result.and_then(|_| batch.delete(key.to_be_bytes()));
+        }
+
+        match result {
+            Ok(_) => self.write_event(event, &index_keys, batch),
+            Err(e) => error!("Unable to put event: {}", e),
+        }
+    }
+
+    /**
+     * Returns the stored event an index entry points to, if it's a warning from the alerts API.
+     */
+    fn get_alert_copy(&self, key: &[u8]) -> Option<(u128, Event)> {
+        let key = u128::from_be_bytes(key.try_into().ok()?);
+        let value = self.db.get(key.to_be_bytes()).ok()??;
+        let event: Event = deserialize(&value).ok()?;
+
+        if event.source_id.as_ref()?.starts_with("alerts:") {
+            Some((key, event))
+        } else {
+            None
+        }
+    }
+
+    /**
+     * SPC compiles its reports from LSRs, so both copies of a report end up in the store. The
+     * matching LSR's source id is recorded on the SPC report so that report counts only include
+     * one of them, and is returned to be indexed so no other SPC report can claim the same LSR.
+     * LSRs are issued after the report time, so only events ingested in the day following the
+     * report are searched.
+     */
+    fn reconcile_report(&self, event: &mut Event) -> Option<String> {
+        let lsr_source_id = {
+            let key = ReportKey::from_event(event)?;
+            let start = u128::from(key.ts);
+            let end = start + RECONCILE_WINDOW_MICROS;
+
+            self.db
+                .iterator(IteratorMode::From(&start.to_be_bytes(), Direction::Forward))
+                .take_while(|(k, _)| match k.as_ref().try_into() {
+                    Ok(bytes) => u128::from_be_bytes(bytes) <= end,
+                    Err(_) => false,
+                })
+                .filter(|(_, value)| has_event_type(value, &EventType::NwsLsr))
+                .filter_map(|(_, value)| deserialize::<OptimizedEvent>(&value).ok())
+                .filter(|x| match &x.source_id {
+                    Some(source_id) => !self.is_lsr_linked(source_id),
+                    None => false,
+                })
+                .find(|x| match ReportKey::from_optimized(x) {
+                    Some(lsr) => key.matches(&lsr),
+                    None => false,
+                })
+                .and_then(|x| x.source_id)
+        };
+
+        if let Some(report) = event.report.as_mut() {
+            report.lsr_source_id = lsr_source_id.clone();
+        }
+
+        lsr_source_id
+    }
+
+    /**
+     * The other side of `reconcile_report`, for an LSR stored after SPC's report of it, ie. when
+     * the LSR was loaded late. The first matching SPC report that isn't linked yet is linked to the
+     * LSR, and returned with its key to be rewritten in place.
+     */
+    fn reconcile_lsr(&self, event: &Event) -> Option<(u128, Vec<u8>)> {
+        let lsr_source_id = event.source_id.as_ref()?;
+        let key = ReportKey::from_event(event)?;
+        let start = u128::from(key.ts);
+        let end = start + RECONCILE_WINDOW_MICROS;
+
+        let (spc_key, mut spc_report) = self
+            .db
+            .iterator(IteratorMode::From(&start.to_be_bytes(), Direction::Forward))
+            .take_while(|(k, _)| match k.as_ref().try_into() {
+                Ok(bytes) => u128::from_be_bytes(bytes) <= end,
+                Err(_) => false,
+            })
+            .filter(|(_, value)| has_event_type(value, &EventType::SpcReport))
+            .filter_map(|(k, value)| {
+                let spc_key = u128::from_be_bytes(k.as_ref().try_into().ok()?);
+                Some((spc_key, deserialize::<Event>(&value).ok()?))
+            })
+            .find(|(_, x)| match (&x.report, ReportKey::from_event(x)) {
+                (Some(report), Some(spc)) => report.lsr_source_id.is_none() && key.matches(&spc),
+                _ => false,
+            })?;
+
+        if let Some(report) = spc_report.report.as_mut() {
+            report.lsr_source_id = Some(lsr_source_id.to_string());
+        }
+
+        match serialize(&spc_report) {
+            Ok(value) => Some((spc_key, value)),
+            Err(e) => {
+                error!("Unable to serialize event: {}", e);
+                None
+            }
+        }
+    }
+
+    /**
+     * SPC issues a WWP with each SEL, with its probabilities for the watch. They're joined by
+     * watch id, so the SEL's watch carries the probabilities no matter which arrives first. An
+     * SEL gets the probabilities from a stored WWP, while a WWP returns the stored SEL with its
+     * probabilities set, to be rewritten in place. Watch ids are reused every year, so the other
+     * product has to have been issued around the same time.
+     */
+    fn join_watch(&self, event: &mut Event) -> Option<(u128, Vec<u8>)> {
+        let watch = event.watch.as_mut()?;
+        let other_prefix = match event.event_type {
+            EventType::NwsSel => "wwp",
+            _ => "sel",
+        };
+        let index_key = format!("{}:{}", other_prefix, watch.id);
+
+        let cf = self.db.cf_handle(WATCHES_CF)?;
+        let key = self.db.get_cf(cf, index_key.as_bytes()).ok()??;
+        let key = u128::from_be_bytes(key.as_slice().try_into().ok()?);
+        let value = self.db.get(key.to_be_bytes()).ok()??;
+        let mut other: Event = deserialize(&value).ok()?;
+
+        let ts_delta = event.event_ts.max(other.event_ts) - event.event_ts.min(other.event_ts);
+        if ts_delta > WATCH_WINDOW_MICROS {
+            return None;
+        }
+
+        let other_watch = other.watch.as_mut()?;
+        if event.event_type == EventType::NwsSel {
+            watch.probabilities = other_watch.probabilities;
+            return None;
+        }
+
+        other_watch.probabilities = watch.probabilities;
+        match serialize(&other) {
+            Ok(value) => Some((key, value)),
+            Err(e) => {
+                error!("Unable to serialize event: {}", e);
+                None
+            }
+        }
+    }
+
+    fn is_lsr_linked(&self, lsr_source_id: &str) -> bool {
+        match self.db.cf_handle(REPORTS_CF) {
+            Some(cf) => matches!(self.db.get_cf(cf, lsr_source_id.as_bytes()), Ok(Some(_))),
+            None => false,
+        }
+    }
+
+    /**
+     * WPC issues MPDs ahead of flash flooding, so an FFW issued inside an MPD is linked to it. MPDs
+     * are stored after they're issued, so searching from the longest an MPD is valid before the
+     * FFW finds any MPD that could cover it, and the search stops as long after the FFW to allow
+     * for MPDs that were loaded late. The latest matching MPD wins.
+     */
+    fn link_mpd(&self, event: &mut Event) {
+        let mpd_source_id = {
+            let start = u128::from(event.event_ts).saturating_sub(MPD_WINDOW_MICROS);
+            let end = u128::from(event.event_ts) + MPD_WINDOW_MICROS;
+
+            self.db
+                .iterator(IteratorMode::From(&start.to_be_bytes(), Direction::Forward))
+                .take_while(|(k, _)| match k.as_ref().try_into() {
+                    Ok(bytes) => u128::from_be_bytes(bytes) <= end,
+                    Err(_) => false,
+                })
+                .filter(|(_, value)| has_event_type(value, &EventType::NwsMpd))
+                .filter_map(|(_, value)| deserialize::<OptimizedEvent>(&value).ok())
+                .filter(|x| is_ffw_in_mpd(event, x))
+                .last()
+                .and_then(|x| x.source_id)
+        };
+
+        if let Some(warning) = event.warning.as_mut() {
+            warning.mpd_source_id = mpd_source_id;
+        }
+    }
+
+    /**
+     * Writes the event and its index entries atomically, along with any changes to other events
+     * already in `batch`, so an index never points to a missing event.
+     */
+    fn write_event(
+        &self,
+        event: &mut Event,
+        index_keys: &[(&'static str, String)],
+        mut batch: WriteBatch,
+    ) {
         let key = self.get_key();
         event.ingest_ts = key;
-        match serialize(event) {
-            Ok(value) => match self.db.put(&key.to_be_bytes(), &value) {
-                Ok(_) => (),
-                Err(e) => error!("Unable to put event: {}", e),
-            },
-            Err(e) => error!("Unable to serialize event: {}", e),
+        let value = match serialize(event) {
+            Ok(value) => value,
+            Err(e) => {
+                error!("Unable to serialize event: {}", e);
+                return;
+            }
+        };
+
+        let mut result = batch.put(key.to_be_bytes(), &value);
+        for (cf_name, index_key) in index_keys {
+            if let Some(cf) = self.db.cf_handle(cf_name) {
+                result =
+                    result.and_then(|_| batch.put_cf(cf, index_key.as_bytes(), key.to_be_bytes()));
+            }
+        }
+
+        match result.and_then(|_| self.db.write(batch)) {
+            Ok(_) => (),
+            Err(e) => error!("Unable to put event: {}", e),
         };
     }
 
@@ -59,6 +449,55 @@ impl Store {
             .collect()
     }
 
+    pub fn get_event(&self, key: u128) -> Option<OptimizedEvent> {
+        match self.db.get(key.to_be_bytes()) {
+            Ok(Some(value)) => match deserialize(&value) {
+                Ok(event) => Some(event),
+                Err(e) => {
+                    error!("Unable to deserialize event with key {}: {}", key, e);
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(e) => {
+                error!("Unable to get event with key {}: {}", key, e);
+                None
+            }
+        }
+    }
+
+    pub fn get_checkpoint(&self, name: &str) -> Option<String> {
+        let cf = self.db.cf_handle(CHECKPOINTS_CF)?;
+        match self.db.get_cf(cf, name.as_bytes()) {
+            Ok(Some(value)) => match String::from_utf8(value) {
+                Ok(checkpoint) => Some(checkpoint),
+                Err(e) => {
+                    error!("Unable to decode checkpoint for {}: {}", name, e);
+                    None
+                }
+            },
+            Ok(None) => None,
+            Err(e) => {
+                error!("Unable to get checkpoint for {}: {}", name, e);
+                None
+            }
+        }
+    }
+
+    pub fn put_checkpoint(&self, name: &str, checkpoint: &str) {
+        let cf = match self.db.cf_handle(CHECKPOINTS_CF) {
+            Some(cf) => cf,
+            None => {
+                error!("Unable to get column family {}", CHECKPOINTS_CF);
+                return;
+            }
+        };
+
+        if let Err(e) = self.db.put_cf(cf, name.as_bytes(), checkpoint.as_bytes()) {
+            error!("Unable to put checkpoint for {}: {}", name, e);
+        }
+    }
+
     pub fn get_stats(&self) -> String {
         self.opts
             .get_statistics()
@@ -79,6 +518,35 @@ impl Store {
     }
 }
 
+/**
+ * The leading fields of a stored event, so events can be filtered by type without deserializing
+ * the rest. Must match the field order of `Event`, since bincode encodes fields in order, which
+ * `has_event_type_should_decode_event_header` checks.
+ */
+#[derive(Deserialize)]
+struct EventHeader {
+    _afd: Option<AreaForecastDiscussion>,
+    _event_ts: u64,
+    event_type: EventType,
+}
+
+/**
+ * Only issued watches are joined, since cancellations reuse the watch id.
+ */
+fn get_watch_index_key(event: &Event) -> Option<String> {
+    let watch = event.watch.as_ref()?;
+    let prefix = match event.event_type {
+        EventType::NwsSel if watch.status == WatchStatus::Issued => "sel",
+        EventType::NwsWwp => "wwp",
+        _ => return None,
+    };
+    Some(format!("{}:{}", prefix, watch.id))
+}
+
+fn has_event_type(value: &[u8], event_type: &EventType) -> bool {
+    matches!(deserialize::<EventHeader>(value), Ok(header) if header.event_type == *event_type)
+}
+
 fn get_system_micros() -> u128 {
     let start = SystemTime::now();
     let since_the_epoch = start
@@ -86,3 +554,20 @@ fn get_system_micros() -> u128 {
         .expect("Unable to get system time");
     since_the_epoch.as_micros()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn has_event_type_should_decode_event_header() {
+        let mut event = Event::new(1525222860000000, EventType::NwsAfd, "AFD".to_string());
+        event.afd = Some(AreaForecastDiscussion {
+            sections: vec![],
+            highlights: vec![],
+        });
+        let value = serialize(&event).unwrap();
+        assert!(has_event_type(&value, &EventType::NwsAfd));
+        assert!(!has_event_type(&value, &EventType::NwsTor));
+    }
+}
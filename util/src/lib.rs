@@ -45,6 +45,19 @@ pub fn ts_to_ticks(input: &str) -> Result<u64, ()> {
     }
 }
 
+/**
+ * Converts an RFC3339 timestamp with any UTC offset to microsecond ticks.
+ */
+pub fn rfc3339_to_ticks(input: &str) -> Result<u64, ()> {
+    match DateTime::parse_from_rfc3339(input) {
+        Ok(dt) => Ok(dt.timestamp() as u64 * 1_000_000),
+        Err(_) => {
+            warn!("Unable to convert ts {}", input);
+            Err(())
+        }
+    }
+}
+
 pub fn get_system_micros() -> u64 {
     let start = SystemTime::now();
     let since_the_epoch = start.duration_since(UNIX_EPOCH).unwrap();
@@ -101,4 +114,11 @@ mod tests {
         let result = ts_to_ticks(&ts).unwrap();
         assert_eq!(result, 1543185983000000);
     }
+
+    #[test]
+    fn rfc3339_to_ticks_should_handle_offsets() {
+        let ts = "2018-11-25T16:46:23-06:00";
+        let result = rfc3339_to_ticks(ts).unwrap();
+        assert_eq!(result, 1543185983000000);
+    }
 }